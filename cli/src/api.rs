@@ -0,0 +1,111 @@
+use std::time::Duration;
+
+use reqwest::blocking::{Client as HttpClient, RequestBuilder};
+use reqwest::Method;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::errors::CliError;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Thin wrapper over the dashboard API. Responses are returned as plain JSON so every
+/// command can hand them straight to the output formatter.
+pub struct ApiClient {
+    http: HttpClient,
+    base_url: String,
+    access_token: Option<String>,
+}
+
+impl ApiClient {
+    pub fn new(base_url: &str, access_token: Option<String>) -> Result<Self, CliError> {
+        let http = HttpClient::builder().timeout(REQUEST_TIMEOUT).build()?;
+
+        Ok(Self {
+            http,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            access_token,
+        })
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub fn get(&self, path: &str) -> Result<Value, CliError> {
+        self.send(self.request(Method::GET, path))
+    }
+
+    pub fn get_with_query<Q: Serialize + ?Sized>(
+        &self,
+        path: &str,
+        query: &Q,
+    ) -> Result<Value, CliError> {
+        self.send(self.request(Method::GET, path).query(query))
+    }
+
+    pub fn post<B: Serialize + ?Sized>(&self, path: &str, body: &B) -> Result<Value, CliError> {
+        self.send(self.request(Method::POST, path).json(body))
+    }
+
+    pub fn post_empty(&self, path: &str) -> Result<Value, CliError> {
+        self.send(self.request(Method::POST, path))
+    }
+
+    pub fn put<B: Serialize + ?Sized>(&self, path: &str, body: &B) -> Result<Value, CliError> {
+        self.send(self.request(Method::PUT, path).json(body))
+    }
+
+    pub fn put_empty(&self, path: &str) -> Result<Value, CliError> {
+        self.send(self.request(Method::PUT, path))
+    }
+
+    pub fn delete(&self, path: &str) -> Result<Value, CliError> {
+        self.send(self.request(Method::DELETE, path))
+    }
+
+    /// Posts to the SDK ingestion routes, which take namespace credentials instead
+    /// of a user's token.
+    pub fn post_sdk<B: Serialize + ?Sized>(
+        &self,
+        path: &str,
+        client_id: &str,
+        client_secret: &str,
+        body: &B,
+    ) -> Result<Value, CliError> {
+        let request = self
+            .http
+            .post(format!("{}{}", self.base_url, path))
+            .header("client_id", client_id)
+            .header("client_secret", client_secret)
+            .json(body);
+        self.send(request)
+    }
+
+    // The server reads the raw token from the Authorization header, without a scheme
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let request = self
+            .http
+            .request(method, format!("{}{}", self.base_url, path));
+        match &self.access_token {
+            Some(token) => request.header("Authorization", token),
+            None => request,
+        }
+    }
+
+    fn send(&self, request: RequestBuilder) -> Result<Value, CliError> {
+        let response = request.send()?;
+        let status = response.status();
+        let body = response.text()?;
+
+        if !status.is_success() {
+            return Err(CliError::Api { status, body });
+        }
+
+        // Some routes answer with an empty body
+        if body.trim().is_empty() {
+            return Ok(Value::Null);
+        }
+        Ok(serde_json::from_str(&body)?)
+    }
+}
@@ -0,0 +1,175 @@
+use clap::{Args, Subcommand};
+use shared_types::error_dtos::ErrorLevel;
+use shared_types::namespace_alert_dtos::{
+    AlertConditionDTO, CreateNamespaceAlertRequestDTO, NamespaceAlertSubscriptionRequestDTO,
+};
+use uuid::Uuid;
+
+use super::{parse_level, Context};
+use crate::errors::CliError;
+use crate::output;
+
+#[derive(Debug, Subcommand)]
+pub enum AlertCommand {
+    /// List a namespace's alerts
+    List { namespace_id: Uuid },
+    /// Create an alert, matching errors by path, line, message or stack trace
+    Create(Box<CreateAlertArgs>),
+    /// Delete an alert
+    Delete { id: Uuid },
+    /// Re-arm an alert that already fired
+    Reset { id: Uuid },
+    /// Send a test payload to a webhook alert and show the response status
+    TestWebhook { id: Uuid },
+    /// Subscribe a user, yourself by default, to an alert
+    Subscribe {
+        id: Uuid,
+        #[arg(long)]
+        namespace_id: Uuid,
+        #[arg(long)]
+        user_id: Option<Uuid>,
+    },
+}
+
+#[derive(Debug, Args)]
+pub struct CreateAlertArgs {
+    #[arg(long)]
+    pub namespace_id: Uuid,
+    /// email, discord, sms, slack or webhook
+    #[arg(long)]
+    pub method: String,
+    #[arg(long)]
+    pub discord_channel_id: Option<String>,
+    /// Slack incoming webhook, e.g. https://hooks.slack.com/services/...
+    #[arg(long)]
+    pub slack_webhook_url: Option<String>,
+    /// Where the webhook method POSTs, the signing secret is only shown in the output
+    #[arg(long)]
+    pub webhook_url: Option<String>,
+    #[arg(long)]
+    pub path: Option<String>,
+    #[arg(long)]
+    pub line: Option<i32>,
+    #[arg(long)]
+    pub message: Option<String>,
+    #[arg(long)]
+    pub stack_trace: Option<String>,
+    #[arg(long)]
+    pub count_threshold: Option<i32>,
+    /// Milliseconds counted towards the count threshold
+    #[arg(long)]
+    pub time_window: Option<i64>,
+    /// Milliseconds an error may stay unresolved
+    #[arg(long)]
+    pub unresolved_time_threshold: Option<i64>,
+    #[arg(long)]
+    pub rate_threshold: Option<i32>,
+    /// Milliseconds counted towards the rate threshold
+    #[arg(long)]
+    pub rate_time_window: Option<i64>,
+    #[arg(long, value_parser = parse_level)]
+    pub min_level: Option<ErrorLevel>,
+    /// A condition tree replacing the thresholds, e.g.
+    /// `{"type":"any","conditions":[{"type":"unresolvedAge","threshold":3600000}]}`
+    #[arg(long, value_parser = parse_conditions)]
+    pub conditions: Option<AlertConditionDTO>,
+    /// Milliseconds after firing before the alert re-arms on its own
+    #[arg(long)]
+    pub cooldown: Option<i64>,
+    /// A condition tree, the alert re-arms once it stops firing
+    #[arg(long, value_parser = parse_conditions)]
+    pub recovery_condition: Option<AlertConditionDTO>,
+    /// Send a recovered message when the recovery condition re-arms the alert
+    #[arg(long)]
+    pub notify_recovery: bool,
+}
+
+pub fn run(ctx: &Context, command: AlertCommand) -> Result<(), CliError> {
+    let (api, session) = ctx.authenticated()?;
+
+    let response = match command {
+        AlertCommand::List { namespace_id } => {
+            api.get(&format!("/api/alert/namespace/{}", namespace_id))?
+        }
+        AlertCommand::Create(args) => {
+            let CreateAlertArgs {
+                namespace_id,
+                method,
+                discord_channel_id,
+                slack_webhook_url,
+                webhook_url,
+                path,
+                line,
+                message,
+                stack_trace,
+                count_threshold,
+                time_window,
+                unresolved_time_threshold,
+                rate_threshold,
+                rate_time_window,
+                min_level,
+                conditions,
+                cooldown,
+                recovery_condition,
+                notify_recovery,
+            } = *args;
+            // Carries the webhook signing secret, which is only shown once
+            api.post(
+                "/api/alert/",
+                &CreateNamespaceAlertRequestDTO {
+                    namespace_id,
+                    alert_method: method,
+                    discord_channel_id,
+                    slack_webhook_url,
+                    path,
+                    line,
+                    message,
+                    stack_trace,
+                    count_threshold,
+                    time_window,
+                    unresolved_time_threshold,
+                    rate_threshold,
+                    rate_time_window,
+                    min_level,
+                    conditions,
+                    cooldown,
+                    recovery_condition,
+                    notify_recovery: Some(notify_recovery),
+                    webhook_url,
+                },
+            )?
+        }
+        AlertCommand::Delete { id } => {
+            api.delete(&format!("/api/alert/{}", id))?;
+            eprintln!("Deleted alert {}", id);
+            return Ok(());
+        }
+        AlertCommand::Reset { id } => {
+            api.put_empty(&format!("/api/alert/{}/reset-trigger", id))?;
+            eprintln!("Reset alert {}", id);
+            return Ok(());
+        }
+        AlertCommand::TestWebhook { id } => {
+            api.post_empty(&format!("/api/alert/{}/test-webhook", id))?
+        }
+        AlertCommand::Subscribe {
+            id,
+            namespace_id,
+            user_id,
+        } => api.post(
+            "/api/alert/subscribe",
+            &NamespaceAlertSubscriptionRequestDTO {
+                user_id: user_id.unwrap_or(session.user_id),
+                namespace_id,
+                namespace_alert_id: id,
+            },
+        )?,
+    };
+
+    output::print(&response, ctx.output);
+    Ok(())
+}
+
+fn parse_conditions(conditions: &str) -> Result<AlertConditionDTO, String> {
+    serde_json::from_str(conditions).map_err(|err| err.to_string())
+}
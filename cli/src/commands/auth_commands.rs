@@ -0,0 +1,58 @@
+use clap::Args;
+use serde_json::json;
+use shared_types::user_dtos::{UserLoginDTO, UserResponseDTO};
+
+use super::Context;
+use crate::errors::CliError;
+use crate::output;
+use crate::session::Session;
+
+#[derive(Debug, Args)]
+pub struct LoginArgs {
+    #[arg(long, env = "ERRORDASH_EMAIL")]
+    pub email: String,
+    /// Prompted for when neither the flag nor the variable is set
+    #[arg(long, env = "ERRORDASH_PASSWORD", hide_env_values = true)]
+    pub password: Option<String>,
+}
+
+pub fn login(ctx: &Context, args: LoginArgs) -> Result<(), CliError> {
+    let password = match args.password {
+        Some(password) => password,
+        None => rpassword::prompt_password("Password: ")?,
+    };
+
+    let api = ctx.anonymous()?;
+    let response = api.post(
+        "/api/auth/login",
+        &UserLoginDTO {
+            email: args.email.clone(),
+            password,
+        },
+    )?;
+    let user_response: UserResponseDTO = serde_json::from_value(response)?;
+
+    let session = Session {
+        server: api.base_url().to_string(),
+        user_id: user_response.user.id,
+        email: user_response.user.email,
+        access_token: user_response.access_token,
+    };
+    session.save()?;
+
+    output::print(
+        &json!({
+            "server": session.server,
+            "userId": session.user_id,
+            "email": session.email,
+        }),
+        ctx.output,
+    );
+    Ok(())
+}
+
+pub fn logout() -> Result<(), CliError> {
+    Session::clear()?;
+    eprintln!("Logged out");
+    Ok(())
+}
@@ -0,0 +1,139 @@
+use clap::Subcommand;
+use serde_json::Value;
+use shared_types::error_dtos::{CreateErrorDTO, ErrorLevel, UpdateErrorDTO};
+use shared_types::extra_dtos::ErrorQueryParams;
+use tungstenite::Message;
+use uuid::Uuid;
+
+use super::{parse_level, Context};
+use crate::errors::CliError;
+use crate::output::{self, OutputFormat};
+
+#[derive(Debug, Subcommand)]
+pub enum ErrorCommand {
+    /// List a namespace's errors, grouped the way the dashboard groups them
+    List {
+        namespace_id: Uuid,
+        /// One of issue, tags, line, message or level
+        #[arg(long)]
+        group_by: Option<String>,
+        /// Comma separated `path:value` context filters, e.g. `os.name:Linux`
+        #[arg(long)]
+        context: Option<String>,
+        #[arg(long, value_parser = parse_level)]
+        level: Option<ErrorLevel>,
+        #[arg(long, default_value_t = 0)]
+        offset: u64,
+        #[arg(long, default_value_t = 20)]
+        limit: u64,
+    },
+    /// Show an error with its frames, breadcrumbs and contexts
+    Show { id: Uuid },
+    /// Mark an error as resolved
+    Resolve {
+        id: Uuid,
+        /// Mark it as unresolved again instead
+        #[arg(long)]
+        reopen: bool,
+    },
+    /// Print errors as they are ingested until interrupted
+    Tail { namespace_id: Uuid },
+}
+
+pub fn run(ctx: &Context, command: ErrorCommand) -> Result<(), CliError> {
+    let api = || ctx.authenticated().map(|(api, _)| api);
+
+    let response = match command {
+        ErrorCommand::List {
+            namespace_id,
+            group_by,
+            context,
+            level,
+            offset,
+            limit,
+        } => api()?.get_with_query(
+            &format!("/api/namespace/{}/errors", namespace_id),
+            &ErrorQueryParams {
+                offset,
+                limit,
+                group_by,
+                context,
+                level,
+            },
+        )?,
+        ErrorCommand::Show { id } => api()?.get(&format!("/api/error/{}", id))?,
+        ErrorCommand::Resolve { id, reopen } => api()?.put(
+            "/api/error/",
+            &UpdateErrorDTO {
+                id,
+                resolved: Some(!reopen),
+                tags: None,
+            },
+        )?,
+        // The live feed needs no login and prints as it goes
+        ErrorCommand::Tail { namespace_id } => return tail(ctx, namespace_id),
+    };
+
+    output::print(&response, ctx.output);
+    Ok(())
+}
+
+// The namespace feed pushes every stored error as a JSON text frame
+fn tail(ctx: &Context, namespace_id: Uuid) -> Result<(), CliError> {
+    let api = ctx.anonymous()?;
+    let url = format!(
+        "{}/api/ws/namespace/{}/error",
+        websocket_url(api.base_url()),
+        namespace_id
+    );
+
+    let (mut socket, _) = tungstenite::connect(url.as_str())?;
+    eprintln!(
+        "Watching namespace {} for new errors, press Ctrl+C to stop",
+        namespace_id
+    );
+
+    loop {
+        match socket.read()? {
+            Message::Text(text) => {
+                let error: CreateErrorDTO = serde_json::from_str(&text)?;
+                print_tailed(&error, ctx.output)?;
+            }
+            Message::Close(_) => return Ok(()),
+            // Pings are answered by the next read
+            _ => {}
+        }
+    }
+}
+
+// One line per error: JSON lines for scripts, a short summary otherwise
+fn print_tailed(error: &CreateErrorDTO, format: OutputFormat) -> Result<(), CliError> {
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string(error)?),
+        OutputFormat::Table => {
+            let issue = error
+                .issue_id
+                .map(|issue_id| issue_id.to_string())
+                .unwrap_or_else(|| "-".to_string());
+            let message = output::cell(&Value::String(error.message.clone()));
+            println!(
+                "{}  issue {}  user {}  {}",
+                error.id,
+                issue,
+                error.user_affected,
+                message
+            );
+        }
+    }
+    Ok(())
+}
+
+fn websocket_url(base_url: &str) -> String {
+    match base_url.strip_prefix("https://") {
+        Some(rest) => format!("wss://{}", rest),
+        None => match base_url.strip_prefix("http://") {
+            Some(rest) => format!("ws://{}", rest),
+            None => base_url.to_string(),
+        },
+    }
+}
@@ -0,0 +1,50 @@
+pub mod alert_commands;
+pub mod auth_commands;
+pub mod error_commands;
+pub mod namespace_commands;
+pub mod sdk_commands;
+
+use shared_types::error_dtos::ErrorLevel;
+
+use crate::api::ApiClient;
+use crate::errors::CliError;
+use crate::output::OutputFormat;
+use crate::session::Session;
+
+// Where `shuttle run` serves the API locally
+pub const DEFAULT_SERVER: &str = "http://localhost:8000";
+
+/// Global options shared by every command.
+pub struct Context {
+    // Overrides the server stored at login
+    pub server: Option<String>,
+    pub output: OutputFormat,
+}
+
+impl Context {
+    /// A client carrying the stored access token, for the dashboard API.
+    pub fn authenticated(&self) -> Result<(ApiClient, Session), CliError> {
+        let session = Session::load()?.ok_or(CliError::NotLoggedIn)?;
+        let server = self.server.as_deref().unwrap_or(&session.server);
+        let api = ApiClient::new(server, Some(session.access_token.clone()))?;
+
+        Ok((api, session))
+    }
+
+    /// A client without a token, for login, the SDK routes and the WebSocket feed.
+    pub fn anonymous(&self) -> Result<ApiClient, CliError> {
+        let server = match &self.server {
+            Some(server) => server.clone(),
+            None => Session::load()?
+                .map(|session| session.server)
+                .unwrap_or_else(|| DEFAULT_SERVER.to_string()),
+        };
+
+        ApiClient::new(&server, None)
+    }
+}
+
+pub fn parse_level(level: &str) -> Result<ErrorLevel, String> {
+    ErrorLevel::parse(level)
+        .ok_or_else(|| "expected one of fatal, error, warning, info, debug".to_string())
+}
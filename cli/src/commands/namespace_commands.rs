@@ -0,0 +1,83 @@
+use clap::Subcommand;
+use shared_types::extra_dtos::PaginationParams;
+use shared_types::namespace_dtos::{CreateNamespaceDTO, UpdateNamespaceDTO};
+use uuid::Uuid;
+
+use super::Context;
+use crate::api::ApiClient;
+use crate::errors::CliError;
+use crate::output;
+
+#[derive(Debug, Subcommand)]
+pub enum NamespaceCommand {
+    /// List the namespaces you are a member of
+    List {
+        #[arg(long, default_value_t = 0)]
+        offset: u64,
+        #[arg(long, default_value_t = 50)]
+        limit: u64,
+    },
+    /// Show a namespace with its credentials, quotas and usage
+    Show { id: Uuid },
+    /// Create a namespace owned by you
+    Create {
+        #[arg(long)]
+        service_name: String,
+        #[arg(long)]
+        environment_type: String,
+    },
+    /// Issue a new client ID and secret. SDKs still using the old ones are rejected
+    RotateCredentials { id: Uuid },
+}
+
+pub fn run(ctx: &Context, command: NamespaceCommand) -> Result<(), CliError> {
+    let (api, session) = ctx.authenticated()?;
+
+    let response = match command {
+        NamespaceCommand::List { offset, limit } => api.get_with_query(
+            &format!("/api/namespace/user/{}", session.user_id),
+            &PaginationParams { offset, limit },
+        )?,
+        NamespaceCommand::Show { id } => get_namespace(&api, id)?,
+        NamespaceCommand::Create {
+            service_name,
+            environment_type,
+        } => {
+            let created = api.post(
+                "/api/namespace/",
+                &CreateNamespaceDTO {
+                    service_name,
+                    environment_type,
+                },
+            )?;
+            let id: Uuid = serde_json::from_value(created)?;
+            get_namespace(&api, id)?
+        }
+        NamespaceCommand::RotateCredentials { id } => {
+            api.put(
+                &format!("/api/namespace/{}", id),
+                &UpdateNamespaceDTO {
+                    id,
+                    active: None,
+                    service_name: None,
+                    environment_type: None,
+                    client_id: Some(Uuid::new_v4()),
+                    client_secret: Some(true),
+                    quota_per_minute: None,
+                    quota_per_day: None,
+                    sample_rate: None,
+                    scrub_patterns: None,
+                    scrub_tag_keys: None,
+                },
+            )?;
+            get_namespace(&api, id)?
+        }
+    };
+
+    output::print(&response, ctx.output);
+    Ok(())
+}
+
+fn get_namespace(api: &ApiClient, id: Uuid) -> Result<serde_json::Value, CliError> {
+    api.get(&format!("/api/namespace/{}", id))
+}
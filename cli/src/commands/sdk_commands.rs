@@ -0,0 +1,56 @@
+use clap::Args;
+use shared_types::error_dtos::{CreateErrorRequest, ErrorLevel};
+use shared_types::tag_dtos::CreateTagClientNoIdDTO;
+use uuid::Uuid;
+
+use super::{parse_level, Context};
+use crate::errors::CliError;
+use crate::output;
+
+#[derive(Debug, Args)]
+pub struct SendTestArgs {
+    #[arg(long, env = "ERRORDASH_CLIENT_ID")]
+    pub client_id: Uuid,
+    #[arg(long, env = "ERRORDASH_CLIENT_SECRET", hide_env_values = true)]
+    pub client_secret: String,
+    #[arg(long, default_value = "Test error sent from errordash")]
+    pub message: String,
+    #[arg(long, value_parser = parse_level)]
+    pub level: Option<ErrorLevel>,
+    #[arg(long)]
+    pub environment: Option<String>,
+}
+
+/// Sends an error through `/sdk/error` with the namespace's credentials, the way an
+/// SDK would, to check that ingestion and alerting work end to end.
+pub fn send_test(ctx: &Context, args: SendTestArgs) -> Result<(), CliError> {
+    let api = ctx.anonymous()?;
+
+    let request = CreateErrorRequest {
+        user_affected: "errordash".to_string(),
+        stack_trace: format!("TestError: {}", args.message),
+        message: args.message,
+        tags: Some(vec![CreateTagClientNoIdDTO {
+            tag_key: "source".to_string(),
+            tag_value: "errordash".to_string(),
+        }]),
+        platform: None,
+        frames: None,
+        breadcrumbs: None,
+        release: None,
+        environment: args.environment,
+        event_id: Some(Uuid::new_v4().simple().to_string()),
+        contexts: None,
+        level: args.level,
+    };
+
+    let response = api.post_sdk(
+        "/sdk/error/",
+        &args.client_id.to_string(),
+        &args.client_secret,
+        &request,
+    )?;
+
+    output::print(&response, ctx.output);
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use reqwest::StatusCode;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum CliError {
+    #[error("Not logged in, run `errordash login` first")]
+    NotLoggedIn,
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+    #[error("Request failed with {status}: {body}")]
+    Api { status: StatusCode, body: String },
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+    // Boxed, the tungstenite error is large enough to bloat every Result
+    #[error("WebSocket error: {0}")]
+    WebSocket(Box<tungstenite::Error>),
+    #[error("Invalid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl From<tungstenite::Error> for CliError {
+    fn from(err: tungstenite::Error) -> Self {
+        CliError::WebSocket(Box::new(err))
+    }
+}
@@ -0,0 +1,169 @@
+//! `errordash`, a command-line client for the dashboard API.
+//!
+//! ```text
+//! errordash login --email ops@example.com
+//! errordash namespace list
+//! errordash error tail <namespace-id>
+//! errordash --output json alert list <namespace-id>
+//! ```
+
+mod api;
+mod commands;
+mod errors;
+mod output;
+mod session;
+
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+
+use commands::alert_commands::{self, AlertCommand};
+use commands::auth_commands::{self, LoginArgs};
+use commands::error_commands::{self, ErrorCommand};
+use commands::namespace_commands::{self, NamespaceCommand};
+use commands::sdk_commands::{self, SendTestArgs};
+use commands::Context;
+use output::OutputFormat;
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "errordash",
+    version,
+    about = "Manage the error dashboard from a terminal"
+)]
+struct Cli {
+    /// Server URL, defaults to the one logged into
+    #[arg(long, env = "ERRORDASH_SERVER", global = true)]
+    server: Option<String>,
+    #[arg(long, short, value_enum, default_value_t = OutputFormat::Table, global = true)]
+    output: OutputFormat,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Log in and store the access token for the other commands
+    Login(LoginArgs),
+    /// Forget the stored access token
+    Logout,
+    #[command(subcommand)]
+    Namespace(NamespaceCommand),
+    #[command(subcommand)]
+    Error(ErrorCommand),
+    #[command(subcommand)]
+    Alert(AlertCommand),
+    /// Send a test error with a namespace's SDK credentials
+    SendTest(SendTestArgs),
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let ctx = Context {
+        server: cli.server,
+        output: cli.output,
+    };
+
+    let result = match cli.command {
+        Command::Login(args) => auth_commands::login(&ctx, args),
+        Command::Logout => auth_commands::logout(),
+        Command::Namespace(command) => namespace_commands::run(&ctx, command),
+        Command::Error(command) => error_commands::run(&ctx, command),
+        Command::Alert(command) => alert_commands::run(&ctx, command),
+        Command::SendTest(args) => sdk_commands::send_test(&ctx, args),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared_types::error_dtos::ErrorLevel;
+    use uuid::Uuid;
+
+    fn parse(args: &[&str]) -> Result<Cli, clap::Error> {
+        Cli::try_parse_from(std::iter::once("errordash").chain(args.iter().copied()))
+    }
+
+    #[test]
+    fn parses_global_options_after_the_subcommand() {
+        let cli = parse(&[
+            "namespace",
+            "list",
+            "--output",
+            "json",
+            "--server",
+            "https://errors.example.com",
+        ])
+        .unwrap();
+
+        assert_eq!(cli.output, OutputFormat::Json);
+        assert_eq!(cli.server.as_deref(), Some("https://errors.example.com"));
+        assert!(matches!(
+            cli.command,
+            Command::Namespace(NamespaceCommand::List {
+                offset: 0,
+                limit: 50
+            })
+        ));
+    }
+
+    #[test]
+    fn parses_error_commands() {
+        let namespace_id = Uuid::new_v4();
+        let cli = parse(&[
+            "error",
+            "list",
+            &namespace_id.to_string(),
+            "--level",
+            "warning",
+            "--group-by",
+            "issue",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Command::Error(ErrorCommand::List {
+                namespace_id: parsed,
+                level,
+                group_by,
+                limit,
+                ..
+            }) => {
+                assert_eq!(parsed, namespace_id);
+                assert_eq!(level, Some(ErrorLevel::Warning));
+                assert_eq!(group_by.as_deref(), Some("issue"));
+                assert_eq!(limit, 20);
+            }
+            command => panic!("unexpected command {:?}", command),
+        }
+
+        let cli = parse(&["error", "resolve", &namespace_id.to_string(), "--reopen"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Command::Error(ErrorCommand::Resolve { reopen: true, .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_invalid_arguments() {
+        assert!(parse(&["error", "show", "not-a-uuid"]).is_err());
+        assert!(parse(&[
+            "error",
+            "list",
+            &Uuid::new_v4().to_string(),
+            "--level",
+            "loud"
+        ])
+        .is_err());
+        assert!(parse(&["--output", "yaml", "namespace", "list"]).is_err());
+        assert!(parse(&["namespace"]).is_err());
+    }
+}
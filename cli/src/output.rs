@@ -0,0 +1,102 @@
+use clap::ValueEnum;
+use serde_json::Value;
+
+// Longer cells are cut so one large field (a stack trace) doesn't wreck the table
+const MAX_CELL_WIDTH: usize = 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    Table,
+    Json,
+}
+
+/// Prints an API response. Lists of objects become a table with a column per field,
+/// single objects a field/value table, and JSON output is printed as returned.
+pub fn print(value: &Value, format: OutputFormat) {
+    match format {
+        OutputFormat::Json => match serde_json::to_string_pretty(value) {
+            Ok(json) => println!("{}", json),
+            Err(_) => println!("{}", value),
+        },
+        OutputFormat::Table => print!("{}", render_table(value)),
+    }
+}
+
+pub fn render_table(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::Array(items) if items.is_empty() => "(none)\n".to_string(),
+        Value::Array(items) if items.iter().all(Value::is_object) => {
+            let mut columns: Vec<&str> = Vec::new();
+            for item in items {
+                for key in item.as_object().into_iter().flat_map(|object| object.keys()) {
+                    if !columns.contains(&key.as_str()) {
+                        columns.push(key.as_str());
+                    }
+                }
+            }
+
+            let rows: Vec<Vec<String>> = items
+                .iter()
+                .map(|item| {
+                    columns
+                        .iter()
+                        .map(|column| cell(item.get(*column).unwrap_or(&Value::Null)))
+                        .collect()
+                })
+                .collect();
+            let header = columns.iter().map(|column| column.to_string()).collect();
+
+            format_rows(header, rows)
+        }
+        Value::Array(items) => items.iter().map(|item| cell(item) + "\n").collect(),
+        Value::Object(object) => {
+            let rows = object
+                .iter()
+                .map(|(key, value)| vec![key.clone(), cell(value)])
+                .collect();
+            format_rows(vec!["field".to_string(), "value".to_string()], rows)
+        }
+        scalar => cell(scalar) + "\n",
+    }
+}
+
+fn format_rows(header: Vec<String>, rows: Vec<Vec<String>>) -> String {
+    let mut widths: Vec<usize> = header.iter().map(|title| title.chars().count()).collect();
+    for row in &rows {
+        for (width, value) in widths.iter_mut().zip(row) {
+            *width = (*width).max(value.chars().count());
+        }
+    }
+
+    let mut output = String::new();
+    for row in std::iter::once(&header).chain(rows.iter()) {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|(value, width)| format!("{:<width$}", value, width = width))
+            .collect::<Vec<_>>()
+            .join("  ");
+        output.push_str(line.trim_end());
+        output.push('\n');
+    }
+    output
+}
+
+/// A value squeezed onto one line of at most `MAX_CELL_WIDTH` characters.
+pub fn cell(value: &Value) -> String {
+    let text = match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        value => value.to_string(),
+    };
+    // Only the first line of multi-line values, e.g. stack traces
+    let first_line = text.lines().next().unwrap_or_default();
+
+    if first_line.chars().count() > MAX_CELL_WIDTH {
+        let truncated: String = first_line.chars().take(MAX_CELL_WIDTH - 3).collect();
+        format!("{}...", truncated)
+    } else {
+        first_line.to_string()
+    }
+}
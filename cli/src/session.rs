@@ -0,0 +1,137 @@
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::errors::CliError;
+
+const SESSION_DIR: &str = ".errordash";
+const SESSION_FILE: &str = "session.json";
+
+/// What `login` leaves behind for the other commands: the server it logged into,
+/// the access token and the user it belongs to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Session {
+    pub server: String,
+    pub user_id: Uuid,
+    pub email: String,
+    pub access_token: String,
+}
+
+impl Session {
+    pub fn load() -> Result<Option<Self>, CliError> {
+        let path = session_path()?;
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let contents = fs::read_to_string(path)?;
+        Ok(Some(serde_json::from_str(&contents)?))
+    }
+
+    pub fn save(&self) -> Result<(), CliError> {
+        let path = session_path()?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let contents = serde_json::to_string_pretty(self)?;
+
+        let mut options = OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        // The access token is as good as the password until it expires, so the file is
+        // never readable by others, not even between creating and writing it
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options.open(&path)?;
+        // An existing file keeps its mode on open, tighten it from earlier versions
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            file.set_permissions(fs::Permissions::from_mode(0o600))?;
+        }
+        file.write_all(contents.as_bytes())?;
+
+        Ok(())
+    }
+
+    pub fn clear() -> Result<(), CliError> {
+        let path = session_path()?;
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+}
+
+// ERRORDASH_HOME takes precedence so scripts can keep separate logins apart
+fn session_path() -> Result<PathBuf, CliError> {
+    let dir = match env::var_os("ERRORDASH_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => match env::var_os("HOME").or_else(|| env::var_os("USERPROFILE")) {
+            Some(home) => PathBuf::from(home).join(SESSION_DIR),
+            None => {
+                return Err(CliError::InvalidArgument(
+                    "HOME is not set, set ERRORDASH_HOME instead".to_string(),
+                ))
+            }
+        },
+    };
+
+    Ok(dir.join(SESSION_FILE))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The only test touching ERRORDASH_HOME, the environment is shared by every test
+    #[test]
+    fn saves_and_loads_the_session() {
+        let home = env::temp_dir().join(format!("errordash-{}", Uuid::new_v4()));
+        env::set_var("ERRORDASH_HOME", &home);
+
+        assert!(Session::load().unwrap().is_none());
+
+        let session = Session {
+            server: "https://errors.example.com".to_string(),
+            user_id: Uuid::new_v4(),
+            email: "ops@example.com".to_string(),
+            access_token: "token".to_string(),
+        };
+        session.save().unwrap();
+
+        let loaded = Session::load().unwrap().unwrap();
+        assert_eq!(loaded.server, session.server);
+        assert_eq!(loaded.user_id, session.user_id);
+        assert_eq!(loaded.email, session.email);
+        assert_eq!(loaded.access_token, session.access_token);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let path = home.join(SESSION_FILE);
+
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+
+            // Files left readable by earlier versions are tightened on the next save
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+            session.save().unwrap();
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        Session::clear().unwrap();
+        assert!(Session::load().unwrap().is_none());
+
+        env::remove_var("ERRORDASH_HOME");
+        fs::remove_dir_all(home).unwrap();
+    }
+}
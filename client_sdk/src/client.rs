@@ -0,0 +1,115 @@
+use std::env::consts;
+use std::sync::Arc;
+use std::time::Duration;
+
+use shared_types::error_dtos::{
+    CreateErrorRequest, DeviceContextDTO, ErrorContextsDTO, ErrorLevel, OsContextDTO,
+    RuntimeContextDTO,
+};
+use uuid::Uuid;
+
+use crate::errors::ClientError;
+use crate::options::ClientOptions;
+use crate::transport::Transport;
+
+/// Captures errors and sends them in the background. Cloning is cheap and every
+/// clone shares the same buffer; the sender thread stops once the last clone is dropped.
+#[derive(Clone)]
+pub struct Client {
+    inner: Arc<ClientInner>,
+}
+
+struct ClientInner {
+    options: ClientOptions,
+    transport: Transport,
+}
+
+impl Client {
+    pub fn new(options: ClientOptions) -> Result<Self, ClientError> {
+        if options.base_url.is_empty() {
+            return Err(ClientError::InvalidOptions("base_url is required"));
+        }
+        if options.buffer_size == 0 || options.batch_size == 0 {
+            return Err(ClientError::InvalidOptions(
+                "buffer_size and batch_size must be positive",
+            ));
+        }
+
+        let transport = Transport::new(&options)?;
+
+        Ok(Self {
+            inner: Arc::new(ClientInner { options, transport }),
+        })
+    }
+
+    pub fn options(&self) -> &ClientOptions {
+        &self.inner.options
+    }
+
+    /// Queues an error, filling in the event ID, release, environment and runtime
+    /// details it doesn't set. Returns false when the buffer was full and it was dropped.
+    pub fn capture_error(&self, mut request: CreateErrorRequest) -> bool {
+        let options = &self.inner.options;
+
+        if request.user_affected.is_empty() {
+            request.user_affected = options.user_affected.clone();
+        }
+        if request.event_id.is_none() {
+            request.event_id = Some(Uuid::new_v4().simple().to_string());
+        }
+        if request.release.is_none() {
+            request.release = options.release.clone();
+        }
+        if request.environment.is_none() {
+            request.environment = options.environment.clone();
+        }
+
+        let contexts = request
+            .contexts
+            .get_or_insert_with(ErrorContextsDTO::default);
+        contexts.runtime.get_or_insert(RuntimeContextDTO {
+            name: Some("rust".to_string()),
+            version: None,
+        });
+        contexts.os.get_or_insert(OsContextDTO {
+            name: Some(consts::OS.to_string()),
+            version: None,
+        });
+        contexts.device.get_or_insert(DeviceContextDTO {
+            model: None,
+            family: None,
+            arch: Some(consts::ARCH.to_string()),
+        });
+
+        self.inner.transport.send(request)
+    }
+
+    /// Queues a plain message without a stack trace.
+    pub fn capture_message(&self, message: &str, level: ErrorLevel) -> bool {
+        self.capture_error(CreateErrorRequest {
+            user_affected: String::new(),
+            stack_trace: message.to_string(),
+            message: message.to_string(),
+            tags: None,
+            platform: Some("rust".to_string()),
+            frames: None,
+            breadcrumbs: None,
+            release: None,
+            environment: None,
+            event_id: None,
+            contexts: None,
+            level: Some(level),
+        })
+    }
+
+    /// Blocks until everything captured so far was sent, or the timeout passes.
+    /// Call it before the process exits, queued errors are lost otherwise.
+    pub fn flush(&self, timeout: Duration) -> bool {
+        self.inner.transport.flush(timeout)
+    }
+
+    /// Errors dropped because the buffer was full or the server kept refusing them.
+    pub fn dropped_count(&self) -> u64 {
+        self.inner.transport.dropped()
+    }
+}
@@ -0,0 +1,11 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ClientError {
+    #[error("Invalid client options: {0}")]
+    InvalidOptions(&'static str),
+    #[error("Failed to build the HTTP client: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("Failed to start the sender thread: {0}")]
+    Io(#[from] std::io::Error),
+}
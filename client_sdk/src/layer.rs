@@ -0,0 +1,127 @@
+use std::fmt;
+
+use serde_json::{Map, Value};
+use shared_types::error_dtos::{CreateErrorRequest, ErrorContextsDTO, ErrorLevel, StackFrameDTO};
+use shared_types::tag_dtos::CreateTagClientNoIdDTO;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+use crate::client::Client;
+
+// Events from the HTTP stack could be raised while sending, reporting them would loop
+const IGNORED_TARGETS: [&str; 4] = ["client_sdk", "reqwest", "hyper", "h2"];
+
+/// A `tracing` layer that reports every `ERROR` event. The event's fields other than
+/// `message` are sent as extra context, and its target as the `target` tag.
+pub struct ErrorLayer {
+    client: Client,
+}
+
+impl ErrorLayer {
+    pub fn new(client: Client) -> Self {
+        Self { client }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for ErrorLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        if *metadata.level() != Level::ERROR
+            || IGNORED_TARGETS
+                .iter()
+                .any(|target| metadata.target().starts_with(target))
+        {
+            return;
+        }
+
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+        let message = visitor
+            .message
+            .unwrap_or_else(|| metadata.name().to_string());
+
+        // The callsite is known exactly, a backtrace would only add the subscriber's frames
+        let frames = metadata.file().map(|file| {
+            vec![StackFrameDTO {
+                file: file.to_string(),
+                line: metadata.line().unwrap_or_default() as i32,
+                column: None,
+                function: String::new(),
+                module: metadata.module_path().unwrap_or_default().to_string(),
+                in_app: true,
+            }]
+        });
+
+        let contexts = (!visitor.fields.is_empty()).then(|| ErrorContextsDTO {
+            extra: Some(visitor.fields),
+            ..Default::default()
+        });
+
+        self.client.capture_error(CreateErrorRequest {
+            user_affected: String::new(),
+            stack_trace: format!("{}: {}", metadata.target(), message),
+            message,
+            tags: Some(vec![CreateTagClientNoIdDTO {
+                tag_key: "target".to_string(),
+                tag_value: metadata.target().to_string(),
+            }]),
+            platform: Some("rust".to_string()),
+            frames,
+            breadcrumbs: None,
+            release: None,
+            environment: None,
+            event_id: None,
+            contexts,
+            level: Some(ErrorLevel::Error),
+        });
+    }
+}
+
+#[derive(Default)]
+struct FieldVisitor {
+    message: Option<String>,
+    fields: Map<String, Value>,
+}
+
+impl FieldVisitor {
+    fn record(&mut self, field: &Field, value: Value) {
+        match field.name() {
+            "message" => {
+                self.message = Some(match value {
+                    Value::String(message) => message,
+                    value => value.to_string(),
+                })
+            }
+            name => {
+                self.fields.insert(name.to_string(), value);
+            }
+        }
+    }
+}
+
+impl Visit for FieldVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record(field, Value::from(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.record(field, Value::from(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.record(field, Value::from(value));
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.record(field, Value::from(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.record(field, Value::from(value));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.record(field, Value::from(format!("{:?}", value)));
+    }
+}
@@ -0,0 +1,31 @@
+//! Reports errors from Rust services to the `/sdk/error` endpoints.
+//!
+//! ```no_run
+//! use client_sdk::{install_panic_hook, Client, ClientOptions, ErrorLayer};
+//! use std::time::Duration;
+//! use tracing_subscriber::prelude::*;
+//!
+//! let client_id = "00000000-0000-0000-0000-000000000000".parse().unwrap();
+//! let client = Client::new(ClientOptions::new("http://localhost:8080", client_id, "secret")).unwrap();
+//!
+//! install_panic_hook(client.clone());
+//! tracing_subscriber::registry()
+//!     .with(ErrorLayer::new(client.clone()))
+//!     .init();
+//!
+//! // On shutdown
+//! client.flush(Duration::from_secs(5));
+//! ```
+
+pub mod client;
+pub mod errors;
+pub mod layer;
+pub mod options;
+pub mod panic;
+mod transport;
+
+pub use client::Client;
+pub use errors::ClientError;
+pub use layer::ErrorLayer;
+pub use options::ClientOptions;
+pub use panic::install_panic_hook;
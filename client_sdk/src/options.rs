@@ -0,0 +1,50 @@
+use std::time::Duration;
+
+use uuid::Uuid;
+
+/// Where errors are sent and how the background sender behaves. Only the server
+/// address and the namespace credentials are required, everything else has a default.
+#[derive(Debug, Clone)]
+pub struct ClientOptions {
+    // e.g. `https://errors.example.com`, the SDK routes are appended to it
+    pub base_url: String,
+    pub client_id: Uuid,
+    pub client_secret: String,
+    pub release: Option<String>,
+    pub environment: Option<String>,
+    // Sent as the affected user when an error doesn't name one
+    pub user_affected: String,
+    // Errors waiting to be sent. Once full, new errors are dropped rather than blocking
+    pub buffer_size: usize,
+    // Errors sent in a single request
+    pub batch_size: usize,
+    // Retries after the first attempt for connection failures, 429 and 5xx responses.
+    // A 429 waits at least as long as its Retry-After header asks
+    pub max_retries: u32,
+    // Doubled after every failed attempt, up to max_backoff
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub request_timeout: Duration,
+    // How long the panic hook waits for the panic to be delivered
+    pub shutdown_timeout: Duration,
+}
+
+impl ClientOptions {
+    pub fn new(base_url: &str, client_id: Uuid, client_secret: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            client_id,
+            client_secret: client_secret.to_string(),
+            release: None,
+            environment: None,
+            user_affected: "unknown".to_string(),
+            buffer_size: 1000,
+            batch_size: 100,
+            max_retries: 5,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            request_timeout: Duration::from_secs(10),
+            shutdown_timeout: Duration::from_secs(2),
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use std::backtrace::Backtrace;
+use std::panic::{self, PanicHookInfo};
+use std::thread;
+
+use shared_types::error_dtos::{CreateErrorRequest, ErrorLevel};
+
+use crate::client::Client;
+
+/// Reports panics as fatal errors, then runs the hook that was installed before.
+/// The backtrace is always captured, whatever `RUST_BACKTRACE` is set to.
+pub fn install_panic_hook(client: Client) {
+    let previous_hook = panic::take_hook();
+
+    panic::set_hook(Box::new(move |info| {
+        client.capture_error(panic_to_request(info));
+        // The process may be about to exit, so wait for the panic to be delivered
+        client.flush(client.options().shutdown_timeout);
+
+        previous_hook(info);
+    }));
+}
+
+// Formatted the way the default hook prints panics so the server's Rust parser
+// picks out the location and frames
+fn panic_to_request(info: &PanicHookInfo<'_>) -> CreateErrorRequest {
+    let message = match info.payload().downcast_ref::<&str>() {
+        Some(message) => message.to_string(),
+        None => match info.payload().downcast_ref::<String>() {
+            Some(message) => message.clone(),
+            None => "Box<dyn Any>".to_string(),
+        },
+    };
+
+    let thread = thread::current();
+    let thread_name = thread.name().unwrap_or("<unnamed>");
+    let location = match info.location() {
+        Some(location) => format!(
+            "{}:{}:{}",
+            location.file(),
+            location.line(),
+            location.column()
+        ),
+        None => "<unknown>:0:0".to_string(),
+    };
+
+    let stack_trace = format!(
+        "thread '{}' panicked at {}:\n{}\nstack backtrace:\n{}",
+        thread_name,
+        location,
+        message,
+        Backtrace::force_capture()
+    );
+
+    CreateErrorRequest {
+        user_affected: String::new(),
+        stack_trace,
+        message,
+        tags: None,
+        platform: Some("rust".to_string()),
+        frames: None,
+        breadcrumbs: None,
+        release: None,
+        environment: None,
+        event_id: None,
+        contexts: None,
+        level: Some(ErrorLevel::Fatal),
+    }
+}
@@ -0,0 +1,212 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, SyncSender, TrySendError};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use reqwest::blocking::{Client as HttpClient, Response};
+use reqwest::header::RETRY_AFTER;
+use reqwest::StatusCode;
+use shared_types::error_dtos::CreateErrorRequest;
+
+use crate::errors::ClientError;
+use crate::options::ClientOptions;
+
+// The server rejects batches above 500 errors
+const MAX_BATCH_SIZE: usize = 500;
+const FLUSH_POLL_INTERVAL: Duration = Duration::from_millis(10);
+// A server asking for a longer pause gets the batch dropped, the sender thread
+// would stop draining the buffer otherwise
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+enum Command {
+    Send(Box<CreateErrorRequest>),
+    // Acknowledged once everything queued before it was delivered or given up on
+    Flush(SyncSender<()>),
+}
+
+/// Hands errors to a background thread that posts them in batches, so capturing
+/// never waits on the network.
+pub(crate) struct Transport {
+    sender: SyncSender<Command>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl Transport {
+    pub(crate) fn new(options: &ClientOptions) -> Result<Self, ClientError> {
+        let http = HttpClient::builder()
+            .timeout(options.request_timeout)
+            .build()?;
+        let (sender, receiver) = mpsc::sync_channel(options.buffer_size);
+        let dropped = Arc::new(AtomicU64::new(0));
+
+        let worker = Worker {
+            http,
+            url: format!("{}/sdk/error/batch", options.base_url),
+            client_id: options.client_id.to_string(),
+            client_secret: options.client_secret.clone(),
+            batch_size: options.batch_size.min(MAX_BATCH_SIZE),
+            max_retries: options.max_retries,
+            initial_backoff: options.initial_backoff,
+            max_backoff: options.max_backoff,
+            dropped: dropped.clone(),
+        };
+        thread::Builder::new()
+            .name("client-sdk-sender".to_string())
+            .spawn(move || worker.run(receiver))?;
+
+        Ok(Self { sender, dropped })
+    }
+
+    /// Queues an error, returning false when the buffer is full.
+    pub(crate) fn send(&self, request: CreateErrorRequest) -> bool {
+        match self.sender.try_send(Command::Send(Box::new(request))) {
+            Ok(()) => true,
+            Err(_) => {
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+                false
+            }
+        }
+    }
+
+    /// Blocks until every error queued so far was handled, or the timeout passes.
+    pub(crate) fn flush(&self, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        let (ack_sender, ack_receiver) = mpsc::sync_channel(1);
+
+        let mut command = Command::Flush(ack_sender);
+        loop {
+            match self.sender.try_send(command) {
+                Ok(()) => break,
+                Err(TrySendError::Full(rejected)) => {
+                    if Instant::now() >= deadline {
+                        return false;
+                    }
+                    command = rejected;
+                    thread::sleep(FLUSH_POLL_INTERVAL);
+                }
+                Err(TrySendError::Disconnected(_)) => return false,
+            }
+        }
+
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        ack_receiver.recv_timeout(remaining).is_ok()
+    }
+
+    pub(crate) fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+struct Worker {
+    http: HttpClient,
+    url: String,
+    client_id: String,
+    client_secret: String,
+    batch_size: usize,
+    max_retries: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    dropped: Arc<AtomicU64>,
+}
+
+impl Worker {
+    // Runs until every Client handle is gone, sending what is left before exiting
+    fn run(self, receiver: Receiver<Command>) {
+        let mut batch: Vec<CreateErrorRequest> = Vec::new();
+
+        loop {
+            let command = match batch.is_empty() {
+                true => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+                // Whatever arrived together goes out together
+                false => receiver.recv_timeout(Duration::ZERO),
+            };
+
+            match command {
+                Ok(Command::Send(request)) => {
+                    batch.push(*request);
+                    if batch.len() >= self.batch_size {
+                        self.deliver(&mut batch);
+                    }
+                }
+                Ok(Command::Flush(ack)) => {
+                    self.deliver(&mut batch);
+                    let _ = ack.try_send(());
+                }
+                Err(RecvTimeoutError::Timeout) => self.deliver(&mut batch),
+                Err(RecvTimeoutError::Disconnected) => {
+                    self.deliver(&mut batch);
+                    return;
+                }
+            }
+        }
+    }
+
+    fn deliver(&self, batch: &mut Vec<CreateErrorRequest>) {
+        if batch.is_empty() {
+            return;
+        }
+
+        let mut backoff = self.initial_backoff;
+        for attempt in 0..=self.max_retries {
+            match self.post(batch) {
+                Delivery::Sent => break,
+                Delivery::Rejected => {
+                    self.dropped
+                        .fetch_add(batch.len() as u64, Ordering::Relaxed);
+                    break;
+                }
+                Delivery::Retry(_) if attempt == self.max_retries => {
+                    self.dropped
+                        .fetch_add(batch.len() as u64, Ordering::Relaxed);
+                }
+                Delivery::Retry(Some(retry_after)) if retry_after > MAX_RETRY_AFTER => {
+                    self.dropped
+                        .fetch_add(batch.len() as u64, Ordering::Relaxed);
+                    break;
+                }
+                // Event IDs make a retry of a batch the server already stored harmless
+                Delivery::Retry(retry_after) => {
+                    thread::sleep(retry_after.unwrap_or(backoff).max(backoff));
+                    backoff = (backoff * 2).min(self.max_backoff);
+                }
+            }
+        }
+
+        batch.clear();
+    }
+
+    fn post(&self, batch: &[CreateErrorRequest]) -> Delivery {
+        let response = self
+            .http
+            .post(&self.url)
+            .header("client_id", &self.client_id)
+            .header("client_secret", &self.client_secret)
+            .json(batch)
+            .send();
+
+        match response {
+            Ok(response) if response.status().is_success() => Delivery::Sent,
+            Ok(response) if response.status() == StatusCode::TOO_MANY_REQUESTS => {
+                Delivery::Retry(retry_after(&response))
+            }
+            Ok(response) if response.status().is_server_error() => Delivery::Retry(None),
+            // Bad credentials or an invalid payload won't get better by resending
+            Ok(_) => Delivery::Rejected,
+            Err(_) => Delivery::Retry(None),
+        }
+    }
+}
+
+// Only the delay-seconds form, servers rarely send an HTTP date
+fn retry_after(response: &Response) -> Option<Duration> {
+    let seconds = response.headers().get(RETRY_AFTER)?.to_str().ok()?;
+    seconds.trim().parse().ok().map(Duration::from_secs)
+}
+
+enum Delivery {
+    Sent,
+    // Carries the delay the server asked for, if any
+    Retry(Option<Duration>),
+    Rejected,
+}
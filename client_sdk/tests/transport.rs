@@ -0,0 +1,240 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use client_sdk::{Client, ClientOptions};
+use serde_json::Value;
+use shared_types::error_dtos::ErrorLevel;
+use uuid::Uuid;
+
+const CLIENT_SECRET: &str = "test-secret";
+const FLUSH_TIMEOUT: Duration = Duration::from_secs(10);
+
+struct Received {
+    headers: Vec<(String, String)>,
+    body: Value,
+    at: Instant,
+}
+
+impl Received {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn batch_len(&self) -> usize {
+        self.body.as_array().map_or(0, Vec::len)
+    }
+}
+
+/// Answers requests with the scripted responses in order, then with 200 once they
+/// run out, recording every request it got.
+struct MockServer {
+    url: String,
+    received: Arc<Mutex<Vec<Received>>>,
+}
+
+impl MockServer {
+    fn start(responses: Vec<&'static str>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let responses = Arc::new(Mutex::new(responses.into_iter()));
+
+        let recorded = received.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(stream) = stream else {
+                    return;
+                };
+                let recorded = recorded.clone();
+                let responses = responses.clone();
+                thread::spawn(move || serve(stream, &recorded, &responses));
+            }
+        });
+
+        Self { url, received }
+    }
+
+    fn requests(&self) -> std::sync::MutexGuard<'_, Vec<Received>> {
+        self.received.lock().unwrap()
+    }
+}
+
+fn serve(
+    stream: TcpStream,
+    recorded: &Mutex<Vec<Received>>,
+    responses: &Mutex<std::vec::IntoIter<&'static str>>,
+) {
+    let mut writer = stream.try_clone().unwrap();
+    let mut reader = BufReader::new(stream);
+
+    // Keep-alive connections carry several requests
+    loop {
+        let mut request_line = String::new();
+        if reader.read_line(&mut request_line).unwrap_or(0) == 0 {
+            return;
+        }
+
+        let mut headers = Vec::new();
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            if let Some((key, value)) = line.split_once(':') {
+                headers.push((key.trim().to_string(), value.trim().to_string()));
+            }
+        }
+
+        let length = headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case("content-length"))
+            .and_then(|(_, value)| value.parse().ok())
+            .unwrap_or(0);
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).unwrap();
+
+        recorded.lock().unwrap().push(Received {
+            headers,
+            body: serde_json::from_slice(&body).unwrap_or(Value::Null),
+            at: Instant::now(),
+        });
+
+        let response = responses.lock().unwrap().next().unwrap_or("200 OK");
+        let (status, extra_headers) = response.split_once('\n').unwrap_or((response, ""));
+        write!(
+            writer,
+            "HTTP/1.1 {}\r\ncontent-length: 2\r\ncontent-type: application/json\r\n{}\r\n[]",
+            status, extra_headers
+        )
+        .unwrap();
+    }
+}
+
+fn client(server: &MockServer, configure: impl FnOnce(&mut ClientOptions)) -> Client {
+    let mut options = ClientOptions::new(&server.url, Uuid::new_v4(), CLIENT_SECRET);
+    options.initial_backoff = Duration::from_millis(10);
+    options.max_backoff = Duration::from_millis(40);
+    configure(&mut options);
+    Client::new(options).unwrap()
+}
+
+#[test]
+fn sends_credentials_with_every_batch() {
+    let server = MockServer::start(vec![]);
+    let client = client(&server, |_| {});
+
+    client.capture_message("boom", ErrorLevel::Error);
+    assert!(client.flush(FLUSH_TIMEOUT));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(
+        requests[0].header("client_id"),
+        Some(client.options().client_id.to_string().as_str())
+    );
+    assert_eq!(requests[0].header("client_secret"), Some(CLIENT_SECRET));
+}
+
+#[test]
+fn splits_errors_into_batches() {
+    let server = MockServer::start(vec![]);
+    let client = client(&server, |options| options.batch_size = 2);
+
+    for i in 0..5 {
+        client.capture_message(&format!("error {}", i), ErrorLevel::Error);
+    }
+    assert!(client.flush(FLUSH_TIMEOUT));
+
+    let requests = server.requests();
+    assert!(requests.iter().all(|request| request.batch_len() <= 2));
+    assert_eq!(requests.iter().map(Received::batch_len).sum::<usize>(), 5);
+
+    // Every error got its own event ID for the server to deduplicate on
+    let mut event_ids: Vec<&str> = requests
+        .iter()
+        .flat_map(|request| request.body.as_array().unwrap())
+        .map(|error| error["eventId"].as_str().unwrap())
+        .collect();
+    event_ids.sort();
+    event_ids.dedup();
+    assert_eq!(event_ids.len(), 5);
+}
+
+#[test]
+fn flush_waits_for_queued_errors() {
+    let server = MockServer::start(vec![]);
+    let client = client(&server, |_| {});
+
+    assert!(client.flush(FLUSH_TIMEOUT));
+    assert!(server.requests().is_empty());
+
+    client.capture_message("first", ErrorLevel::Error);
+    client.capture_message("second", ErrorLevel::Error);
+    assert!(client.flush(FLUSH_TIMEOUT));
+
+    let requests = server.requests();
+    assert_eq!(requests.iter().map(Received::batch_len).sum::<usize>(), 2);
+}
+
+#[test]
+fn retries_server_errors_with_backoff() {
+    let server = MockServer::start(vec!["503 Service Unavailable", "502 Bad Gateway"]);
+    let client = client(&server, |_| {});
+
+    client.capture_message("boom", ErrorLevel::Error);
+    assert!(client.flush(FLUSH_TIMEOUT));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 3);
+    // 10ms then 20ms
+    assert!(requests[1].at - requests[0].at >= Duration::from_millis(10));
+    assert!(requests[2].at - requests[1].at >= Duration::from_millis(20));
+    assert_eq!(client.dropped_count(), 0);
+}
+
+#[test]
+fn drops_the_batch_once_retries_run_out() {
+    let server = MockServer::start(vec!["500 Internal Server Error"; 3]);
+    let client = client(&server, |options| options.max_retries = 2);
+
+    client.capture_message("first", ErrorLevel::Error);
+    client.capture_message("second", ErrorLevel::Error);
+    assert!(client.flush(FLUSH_TIMEOUT));
+
+    assert_eq!(server.requests().len(), 3);
+    assert_eq!(client.dropped_count(), 2);
+}
+
+#[test]
+fn waits_for_retry_after_on_429() {
+    let server = MockServer::start(vec!["429 Too Many Requests\nretry-after: 1\r\n"]);
+    let client = client(&server, |_| {});
+
+    client.capture_message("boom", ErrorLevel::Error);
+    assert!(client.flush(FLUSH_TIMEOUT));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert!(requests[1].at - requests[0].at >= Duration::from_secs(1));
+    assert_eq!(client.dropped_count(), 0);
+}
+
+#[test]
+fn does_not_retry_rejected_batches() {
+    let server = MockServer::start(vec!["401 Unauthorized"]);
+    let client = client(&server, |_| {});
+
+    client.capture_message("boom", ErrorLevel::Error);
+    assert!(client.flush(FLUSH_TIMEOUT));
+
+    assert_eq!(server.requests().len(), 1);
+    assert_eq!(client.dropped_count(), 1);
+}
@@ -20,6 +20,25 @@ mod m20250216_024615_update_namespace_alert_with_discord_id;
 mod m20250219_225925_update_discord_id_string;
 mod m20250220_221738_add_alert_sent_col;
 mod m20250227_040239_add_phone_number_and_provider;
+mod m20261017_000001_create_issues_table;
+mod m20261017_000002_add_error_frames;
+mod m20261017_000003_add_error_breadcrumbs;
+mod m20261017_000004_create_releases_table;
+mod m20261017_000005_create_artifacts_table;
+mod m20261017_000006_add_namespace_quotas;
+mod m20261017_000007_add_error_event_ids;
+mod m20261017_000008_add_scrubbing_rules;
+mod m20261017_000009_create_inbound_filters_table;
+mod m20261017_000010_add_error_contexts;
+mod m20261017_000011_add_error_levels;
+mod m20261017_000012_create_attachments_table;
+mod m20261017_000013_add_alert_conditions;
+mod m20261017_000014_create_scheduler_leases_table;
+mod m20261017_000015_add_alert_rearming;
+mod m20261017_000016_add_alert_webhooks;
+mod m20261017_000017_add_alert_slack_webhook;
+mod m20261017_000018_create_namespace_usage_table;
+mod m20261017_000019_create_issue_users_table;
 
 pub struct Migrator;
 
@@ -47,6 +66,25 @@ impl MigratorTrait for Migrator {
             Box::new(m20250219_225925_update_discord_id_string::Migration),
             Box::new(m20250220_221738_add_alert_sent_col::Migration),
             Box::new(m20250227_040239_add_phone_number_and_provider::Migration),
+            Box::new(m20261017_000001_create_issues_table::Migration),
+            Box::new(m20261017_000002_add_error_frames::Migration),
+            Box::new(m20261017_000003_add_error_breadcrumbs::Migration),
+            Box::new(m20261017_000004_create_releases_table::Migration),
+            Box::new(m20261017_000005_create_artifacts_table::Migration),
+            Box::new(m20261017_000006_add_namespace_quotas::Migration),
+            Box::new(m20261017_000007_add_error_event_ids::Migration),
+            Box::new(m20261017_000008_add_scrubbing_rules::Migration),
+            Box::new(m20261017_000009_create_inbound_filters_table::Migration),
+            Box::new(m20261017_000010_add_error_contexts::Migration),
+            Box::new(m20261017_000011_add_error_levels::Migration),
+            Box::new(m20261017_000012_create_attachments_table::Migration),
+            Box::new(m20261017_000013_add_alert_conditions::Migration),
+            Box::new(m20261017_000014_create_scheduler_leases_table::Migration),
+            Box::new(m20261017_000015_add_alert_rearming::Migration),
+            Box::new(m20261017_000016_add_alert_webhooks::Migration),
+            Box::new(m20261017_000017_add_alert_slack_webhook::Migration),
+            Box::new(m20261017_000018_create_namespace_usage_table::Migration),
+            Box::new(m20261017_000019_create_issue_users_table::Migration),
         ]
     }
 }
@@ -0,0 +1,219 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20231013_164343_create_error_table::Errors;
+use super::m20231013_200027_create_namespace_table::Namespaces;
+
+#[derive(DeriveIden)]
+pub enum Issues {
+    Table,
+    Id,
+    NamespaceId,
+    Fingerprint,
+    ErrorType,
+    Message,
+    Path,
+    Line,
+    Resolved,
+    Occurrences,
+    UsersAffected,
+    FirstSeen,
+    LastSeen,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+pub enum ErrorsIssue {
+    IssueId,
+}
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Issues::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(Issues::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(Issues::NamespaceId).uuid().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_issue_namespace")
+                            .from(Issues::Table, Issues::NamespaceId)
+                            .to(Namespaces::Table, Namespaces::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(ColumnDef::new(Issues::Fingerprint).string().not_null())
+                    .col(ColumnDef::new(Issues::ErrorType).string().not_null())
+                    .col(ColumnDef::new(Issues::Message).string().not_null())
+                    .col(ColumnDef::new(Issues::Path).string().not_null())
+                    .col(ColumnDef::new(Issues::Line).integer().not_null())
+                    .col(
+                        ColumnDef::new(Issues::Resolved)
+                            .boolean()
+                            .default(false)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Issues::Occurrences)
+                            .big_integer()
+                            .default(0)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Issues::UsersAffected)
+                            .integer()
+                            .default(0)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Issues::FirstSeen)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Issues::LastSeen)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Issues::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Issues::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // One issue per fingerprint within a namespace
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_issues_namespace_fingerprint")
+                    .table(Issues::Table)
+                    .col(Issues::NamespaceId)
+                    .col(Issues::Fingerprint)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_issues_namespace_last_seen")
+                    .table(Issues::Table)
+                    .col(Issues::NamespaceId)
+                    .col(Issues::LastSeen)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Errors::Table)
+                    .add_column(ColumnDef::new(ErrorsIssue::IssueId).uuid().null())
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_error_issue")
+                            .from_tbl(Errors::Table)
+                            .from_col(ErrorsIssue::IssueId)
+                            .to_tbl(Issues::Table)
+                            .to_col(Issues::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_errors_issue_id")
+                    .table(Errors::Table)
+                    .col(ErrorsIssue::IssueId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Existing rows were never fingerprinted, so group them into legacy issues
+        // by namespace/message/path/line to keep them visible in the issue list. The
+        // fields are hashed with a separator so that e.g. `ab` + `c` and `a` + `bc`
+        // don't land in the same issue.
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+            INSERT INTO issues (id, namespace_id, fingerprint, error_type, message, path, line,
+                                resolved, occurrences, users_affected, first_seen, last_seen,
+                                created_at, updated_at)
+            SELECT md5(concat_ws(chr(31), namespace_id::text, message, path, line::text))::uuid,
+                   namespace_id,
+                   'legacy:' || md5(concat_ws(chr(31), message, path, line::text)),
+                   '',
+                   message,
+                   path,
+                   line,
+                   bool_and(resolved),
+                   count(*),
+                   count(DISTINCT user_affected),
+                   min(created_at),
+                   max(created_at),
+                   now(),
+                   now()
+            FROM errors
+            GROUP BY namespace_id, message, path, line
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .await?;
+
+        db.execute_unprepared(
+            r#"
+            UPDATE errors
+            SET issue_id = md5(concat_ws(chr(31), namespace_id::text, message, path, line::text))::uuid
+            WHERE issue_id IS NULL
+            "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_errors_issue_id")
+                    .table(Errors::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Errors::Table)
+                    .drop_foreign_key(Alias::new("fk_error_issue"))
+                    .drop_column(ErrorsIssue::IssueId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Issues::Table).to_owned())
+            .await
+    }
+}
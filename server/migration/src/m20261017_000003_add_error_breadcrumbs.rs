@@ -0,0 +1,41 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20231013_164343_create_error_table::Errors;
+
+#[derive(DeriveIden)]
+pub enum ErrorsBreadcrumbs {
+    Breadcrumbs,
+}
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Errors::Table)
+                    .add_column(
+                        ColumnDef::new(ErrorsBreadcrumbs::Breadcrumbs)
+                            .json_binary()
+                            .default(Expr::cust("'[]'::jsonb"))
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Errors::Table)
+                    .drop_column(ErrorsBreadcrumbs::Breadcrumbs)
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -0,0 +1,165 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20231013_164343_create_error_table::Errors;
+use super::m20231013_200027_create_namespace_table::Namespaces;
+use super::m20261017_000001_create_issues_table::Issues;
+
+#[derive(DeriveIden)]
+pub enum Releases {
+    Table,
+    Id,
+    NamespaceId,
+    Version,
+    FirstSeen,
+    LastSeen,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+pub enum ErrorsRelease {
+    Release,
+    Environment,
+}
+
+#[derive(DeriveIden)]
+pub enum IssuesRelease {
+    ResolvedInRelease,
+    Regression,
+}
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Releases::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(Releases::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(Releases::NamespaceId).uuid().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_release_namespace")
+                            .from(Releases::Table, Releases::NamespaceId)
+                            .to(Namespaces::Table, Namespaces::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(ColumnDef::new(Releases::Version).string().not_null())
+                    .col(
+                        ColumnDef::new(Releases::FirstSeen)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Releases::LastSeen)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Releases::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Releases::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // One row per version within a namespace
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_releases_namespace_version")
+                    .table(Releases::Table)
+                    .col(Releases::NamespaceId)
+                    .col(Releases::Version)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Errors::Table)
+                    .add_column(ColumnDef::new(ErrorsRelease::Release).string().null())
+                    .add_column(ColumnDef::new(ErrorsRelease::Environment).string().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_errors_namespace_release")
+                    .table(Errors::Table)
+                    .col(Errors::NamespaceId)
+                    .col(ErrorsRelease::Release)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Issues::Table)
+                    .add_column(
+                        ColumnDef::new(IssuesRelease::ResolvedInRelease)
+                            .string()
+                            .null(),
+                    )
+                    .add_column(
+                        ColumnDef::new(IssuesRelease::Regression)
+                            .boolean()
+                            .default(false)
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Issues::Table)
+                    .drop_column(IssuesRelease::ResolvedInRelease)
+                    .drop_column(IssuesRelease::Regression)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_errors_namespace_release")
+                    .table(Errors::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Errors::Table)
+                    .drop_column(ErrorsRelease::Release)
+                    .drop_column(ErrorsRelease::Environment)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Releases::Table).to_owned())
+            .await
+    }
+}
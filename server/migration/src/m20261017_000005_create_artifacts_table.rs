@@ -0,0 +1,82 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20231013_200027_create_namespace_table::Namespaces;
+
+#[derive(DeriveIden)]
+pub enum Artifacts {
+    Table,
+    Id,
+    NamespaceId,
+    Release,
+    Name,
+    StorageKey,
+    Size,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Artifacts::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Artifacts::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Artifacts::NamespaceId).uuid().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_artifact_namespace")
+                            .from(Artifacts::Table, Artifacts::NamespaceId)
+                            .to(Namespaces::Table, Namespaces::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(ColumnDef::new(Artifacts::Release).string().not_null())
+                    .col(ColumnDef::new(Artifacts::Name).string().not_null())
+                    .col(ColumnDef::new(Artifacts::StorageKey).string().not_null())
+                    .col(ColumnDef::new(Artifacts::Size).big_integer().not_null())
+                    .col(
+                        ColumnDef::new(Artifacts::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Artifacts::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Re-uploading a file for the same release replaces it
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_artifacts_namespace_release_name")
+                    .table(Artifacts::Table)
+                    .col(Artifacts::NamespaceId)
+                    .col(Artifacts::Release)
+                    .col(Artifacts::Name)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Artifacts::Table).to_owned())
+            .await
+    }
+}
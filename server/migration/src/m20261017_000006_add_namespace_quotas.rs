@@ -0,0 +1,56 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20231013_200027_create_namespace_table::Namespaces;
+
+#[derive(DeriveIden)]
+pub enum NamespacesQuota {
+    QuotaPerMinute,
+    QuotaPerDay,
+    SampleRate,
+}
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // A null quota means the namespace is not limited on that window
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Namespaces::Table)
+                    .add_column(
+                        ColumnDef::new(NamespacesQuota::QuotaPerMinute)
+                            .integer()
+                            .null(),
+                    )
+                    .add_column(
+                        ColumnDef::new(NamespacesQuota::QuotaPerDay)
+                            .integer()
+                            .null(),
+                    )
+                    .add_column(
+                        ColumnDef::new(NamespacesQuota::SampleRate)
+                            .double()
+                            .default(1.0)
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Namespaces::Table)
+                    .drop_column(NamespacesQuota::QuotaPerMinute)
+                    .drop_column(NamespacesQuota::QuotaPerDay)
+                    .drop_column(NamespacesQuota::SampleRate)
+                    .to_owned(),
+            )
+            .await
+    }
+}
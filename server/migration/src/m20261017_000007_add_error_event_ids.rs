@@ -0,0 +1,59 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20231013_164343_create_error_table::Errors;
+
+#[derive(DeriveIden)]
+pub enum ErrorsEventId {
+    EventId,
+}
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Errors::Table)
+                    .add_column(ColumnDef::new(ErrorsEventId::EventId).string_len(64).null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Errors sent without an event ID are never considered duplicates, Postgres
+        // treats every null as distinct
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_errors_namespace_event_id")
+                    .table(Errors::Table)
+                    .col(Errors::NamespaceId)
+                    .col(ErrorsEventId::EventId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_errors_namespace_event_id")
+                    .table(Errors::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Errors::Table)
+                    .drop_column(ErrorsEventId::EventId)
+                    .to_owned(),
+            )
+            .await
+    }
+}
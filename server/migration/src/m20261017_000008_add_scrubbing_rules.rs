@@ -0,0 +1,78 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20231013_164343_create_error_table::Errors;
+use super::m20231013_200027_create_namespace_table::Namespaces;
+
+#[derive(DeriveIden)]
+pub enum NamespacesScrubbing {
+    ScrubPatterns,
+    ScrubTagKeys,
+}
+
+#[derive(DeriveIden)]
+pub enum ErrorsScrubbing {
+    RedactedFields,
+}
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Namespaces::Table)
+                    .add_column(
+                        ColumnDef::new(NamespacesScrubbing::ScrubPatterns)
+                            .json_binary()
+                            .default(Expr::cust("'[]'::jsonb"))
+                            .not_null(),
+                    )
+                    .add_column(
+                        ColumnDef::new(NamespacesScrubbing::ScrubTagKeys)
+                            .json_binary()
+                            .default(Expr::cust("'[]'::jsonb"))
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Errors::Table)
+                    .add_column(
+                        ColumnDef::new(ErrorsScrubbing::RedactedFields)
+                            .json_binary()
+                            .default(Expr::cust("'[]'::jsonb"))
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Errors::Table)
+                    .drop_column(ErrorsScrubbing::RedactedFields)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Namespaces::Table)
+                    .drop_column(NamespacesScrubbing::ScrubPatterns)
+                    .drop_column(NamespacesScrubbing::ScrubTagKeys)
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -0,0 +1,98 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20231013_200027_create_namespace_table::Namespaces;
+
+#[derive(DeriveIden)]
+pub enum InboundFilters {
+    Table,
+    Id,
+    NamespaceId,
+    FilterType,
+    TagKey,
+    Pattern,
+    Active,
+    DroppedCount,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(InboundFilters::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(InboundFilters::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(InboundFilters::NamespaceId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_inbound_filter_namespace")
+                            .from(InboundFilters::Table, InboundFilters::NamespaceId)
+                            .to(Namespaces::Table, Namespaces::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(InboundFilters::FilterType)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(InboundFilters::TagKey).string().null())
+                    .col(ColumnDef::new(InboundFilters::Pattern).string().not_null())
+                    .col(
+                        ColumnDef::new(InboundFilters::Active)
+                            .boolean()
+                            .default(true)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(InboundFilters::DroppedCount)
+                            .big_integer()
+                            .default(0)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(InboundFilters::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(InboundFilters::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_inbound_filters_namespace")
+                    .table(InboundFilters::Table)
+                    .col(InboundFilters::NamespaceId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(InboundFilters::Table).to_owned())
+            .await
+    }
+}
@@ -0,0 +1,41 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20231013_164343_create_error_table::Errors;
+
+#[derive(DeriveIden)]
+pub enum ErrorsContexts {
+    Contexts,
+}
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Errors::Table)
+                    .add_column(
+                        ColumnDef::new(ErrorsContexts::Contexts)
+                            .json_binary()
+                            .default(Expr::cust("'{}'::jsonb"))
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Errors::Table)
+                    .drop_column(ErrorsContexts::Contexts)
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -0,0 +1,90 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20231013_164343_create_error_table::Errors;
+use super::m20240916_025827_create_namespace_alerts::NamespaceAlerts;
+
+#[derive(DeriveIden)]
+pub enum ErrorsLevel {
+    Level,
+}
+
+#[derive(DeriveIden)]
+pub enum NamespaceAlertsLevel {
+    MinLevel,
+}
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Errors stored before levels existed were all treated as errors
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Errors::Table)
+                    .add_column(
+                        ColumnDef::new(ErrorsLevel::Level)
+                            .string_len(16)
+                            .default("error")
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_errors_namespace_level")
+                    .table(Errors::Table)
+                    .col(Errors::NamespaceId)
+                    .col(ErrorsLevel::Level)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(NamespaceAlerts::Table)
+                    .add_column(
+                        ColumnDef::new(NamespaceAlertsLevel::MinLevel)
+                            .string_len(16)
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(NamespaceAlerts::Table)
+                    .drop_column(NamespaceAlertsLevel::MinLevel)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_errors_namespace_level")
+                    .table(Errors::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Errors::Table)
+                    .drop_column(ErrorsLevel::Level)
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -0,0 +1,41 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20240916_025827_create_namespace_alerts::NamespaceAlerts;
+
+#[derive(DeriveIden)]
+pub enum NamespaceAlertsConditions {
+    Conditions,
+}
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Alerts without conditions keep using their threshold columns
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(NamespaceAlerts::Table)
+                    .add_column(
+                        ColumnDef::new(NamespaceAlertsConditions::Conditions)
+                            .json_binary()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(NamespaceAlerts::Table)
+                    .drop_column(NamespaceAlertsConditions::Conditions)
+                    .to_owned(),
+            )
+            .await
+    }
+}
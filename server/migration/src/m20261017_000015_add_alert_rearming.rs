@@ -0,0 +1,78 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20240916_025827_create_namespace_alerts::NamespaceAlerts;
+
+#[derive(DeriveIden)]
+pub enum NamespaceAlertsRearming {
+    Cooldown,
+    RecoveryCondition,
+    NotifyRecovery,
+    TriggeredAt,
+}
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Alerts with neither a cooldown nor a recovery condition stay triggered until reset
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(NamespaceAlerts::Table)
+                    .add_column(
+                        ColumnDef::new(NamespaceAlertsRearming::Cooldown)
+                            .big_integer()
+                            .null(),
+                    )
+                    .add_column(
+                        ColumnDef::new(NamespaceAlertsRearming::RecoveryCondition)
+                            .json_binary()
+                            .null(),
+                    )
+                    .add_column(
+                        ColumnDef::new(NamespaceAlertsRearming::NotifyRecovery)
+                            .boolean()
+                            .default(false)
+                            .not_null(),
+                    )
+                    .add_column(
+                        ColumnDef::new(NamespaceAlertsRearming::TriggeredAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Alerts that already fired start their cooldown from their last update, otherwise
+        // they would never re-arm on their own
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                UPDATE namespace_alerts
+                SET triggered_at = COALESCE(updated_at, now())
+                WHERE triggered = true AND triggered_at IS NULL
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(NamespaceAlerts::Table)
+                    .drop_column(NamespaceAlertsRearming::Cooldown)
+                    .drop_column(NamespaceAlertsRearming::RecoveryCondition)
+                    .drop_column(NamespaceAlertsRearming::NotifyRecovery)
+                    .drop_column(NamespaceAlertsRearming::TriggeredAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -0,0 +1,90 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20231013_200027_create_namespace_table::Namespaces;
+
+#[derive(DeriveIden)]
+pub enum NamespaceUsage {
+    Table,
+    NamespaceId,
+    Minute,
+    MinuteCount,
+    Day,
+    DayCount,
+    Dropped,
+    SampledOut,
+    UpdatedAt,
+}
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // One row of quota counters per namespace, shared by every server instance
+        manager
+            .create_table(
+                Table::create()
+                    .table(NamespaceUsage::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(NamespaceUsage::NamespaceId)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_namespace_usage_namespace")
+                            .from(NamespaceUsage::Table, NamespaceUsage::NamespaceId)
+                            .to(Namespaces::Table, Namespaces::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(NamespaceUsage::Minute)
+                            .big_integer()
+                            .default(0)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(NamespaceUsage::MinuteCount)
+                            .big_integer()
+                            .default(0)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(NamespaceUsage::Day).date().null())
+                    .col(
+                        ColumnDef::new(NamespaceUsage::DayCount)
+                            .big_integer()
+                            .default(0)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(NamespaceUsage::Dropped)
+                            .big_integer()
+                            .default(0)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(NamespaceUsage::SampledOut)
+                            .big_integer()
+                            .default(0)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(NamespaceUsage::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(NamespaceUsage::Table).to_owned())
+            .await
+    }
+}
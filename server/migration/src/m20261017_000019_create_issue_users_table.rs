@@ -0,0 +1,64 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20261017_000001_create_issues_table::Issues;
+
+#[derive(DeriveIden)]
+pub enum IssueUsers {
+    Table,
+    IssueId,
+    UserAffected,
+}
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // The users seen per issue, the primary key decides which error counts a new user
+        manager
+            .create_table(
+                Table::create()
+                    .table(IssueUsers::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(IssueUsers::IssueId).uuid().not_null())
+                    .col(ColumnDef::new(IssueUsers::UserAffected).string().not_null())
+                    .primary_key(
+                        Index::create()
+                            .col(IssueUsers::IssueId)
+                            .col(IssueUsers::UserAffected),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_issue_users_issue")
+                            .from(IssueUsers::Table, IssueUsers::IssueId)
+                            .to(Issues::Table, Issues::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                INSERT INTO issue_users (issue_id, user_affected)
+                SELECT DISTINCT issue_id, user_affected
+                FROM errors
+                WHERE issue_id IS NOT NULL
+                ON CONFLICT DO NOTHING
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(IssueUsers::Table).to_owned())
+            .await
+    }
+}
@@ -6,6 +6,9 @@ pub struct Config {
     pub environment: String,
     pub secret_key: String,
     pub discord_secret_key: String,
+    // Keys the pseudonyms scrubbing replaces personal data with, kept apart from the
+    // JWT key so rotating one doesn't change or expose the other
+    pub pseudonym_key: String,
     pub hash_cost: String,
     pub jwt_issuer: String,
     pub jwt_audience: String,
@@ -18,6 +21,10 @@ pub struct Config {
     pub domain: String,
     pub gmail_email: String,
     pub gmail_token_pass: String,
+    // Root directory for uploaded artifacts such as source maps
+    pub artifact_storage_path: String,
+    // Root directory for files attached to errors, such as logs and minidumps
+    pub attachment_storage_path: String,
 }
 
 impl Config {
@@ -29,6 +36,7 @@ impl Config {
             secret_key: get_secret_var(&secrets, "SECRET_KEY")?,
             hash_cost: get_secret_var(&secrets, "HASH_COST")?,
             discord_secret_key: get_secret_var(&secrets, "DISCORD_SECRET_KEY")?,
+            pseudonym_key: get_secret_var(&secrets, "PSEUDONYM_KEY")?,
             jwt_issuer: get_secret_var(&secrets, "JWT_ISSUER")?,
             jwt_audience: get_secret_var(&secrets, "JWT_AUDIENCE")?,
             api_port: get_secret_var_as::<u16>(&secrets, "API_PORT")?,
@@ -40,6 +48,12 @@ impl Config {
             db_port: get_secret_var_as::<u16>(&secrets, "DB_PORT")?,
             gmail_email: get_secret_var(&secrets, "GMAIL_EMAIL")?,
             gmail_token_pass: get_secret_var(&secrets, "GMAIL_TOKEN_PASS")?,
+            artifact_storage_path: secrets
+                .get("ARTIFACT_STORAGE_PATH")
+                .unwrap_or_else(|| "artifacts".to_string()),
+            attachment_storage_path: secrets
+                .get("ATTACHMENT_STORAGE_PATH")
+                .unwrap_or_else(|| "attachments".to_string()),
         })
     }
 
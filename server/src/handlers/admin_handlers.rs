@@ -4,6 +4,7 @@ use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
 use crate::config::Config;
+use crate::managers::ingestion_manager::IngestionManager;
 use crate::services::{NamespaceService, UserService};
 use crate::shared::utils::jwt::extract_user_id_from_jwt_header;
 use crate::{
@@ -80,4 +81,23 @@ impl AdminHandler {
             Err(err) => Err(err),
         }
     }
+
+    pub async fn get_ingestion_metrics(
+        req: HttpRequest,
+        config: web::Data<Arc<Config>>,
+        user_services: web::Data<Arc<UserService>>,
+        ingestion_manager: web::Data<Arc<IngestionManager>>,
+    ) -> Result<HttpResponse, ServerError> {
+        let headers = req.headers();
+        let secret_key = config.secret_key.clone();
+        let user_id = extract_user_id_from_jwt_header(headers, &secret_key)?;
+
+        let role = user_services.get_user_role_by_user_id(user_id).await?;
+
+        if role != "admin" {
+            return Err(ServerError::QueryError(QueryError::InvalidRole));
+        }
+
+        Ok(HttpResponse::Ok().json(ingestion_manager.metrics()))
+    }
 }
@@ -0,0 +1,78 @@
+use actix_web::{web, HttpRequest, HttpResponse, Result};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::services::artifact_services::ArtifactService;
+use crate::shared::utils::errors::{RequestError, ServerError};
+use shared_types::artifact_dtos::{ArtifactQueryParams, UploadArtifactQueryParams};
+
+pub struct ArtifactHandler;
+
+impl ArtifactHandler {
+    pub async fn upload_artifact(
+        req: HttpRequest,
+        artifact_services: web::Data<Arc<ArtifactService>>,
+        query_params: web::Query<UploadArtifactQueryParams>,
+        body: web::Bytes,
+    ) -> Result<HttpResponse, ServerError> {
+        let client_id = Self::client_id(&req).map_err(ServerError::RequestError)?;
+        let query_params = query_params.into_inner();
+
+        let result = artifact_services
+            .upload_artifact(
+                client_id,
+                query_params.release,
+                query_params.name,
+                body.to_vec(),
+            )
+            .await;
+
+        match result {
+            Ok(artifact) => Ok(HttpResponse::Ok().json(artifact)),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub async fn get_artifacts(
+        req: HttpRequest,
+        artifact_services: web::Data<Arc<ArtifactService>>,
+        query_params: web::Query<ArtifactQueryParams>,
+    ) -> Result<HttpResponse, ServerError> {
+        let client_id = Self::client_id(&req).map_err(ServerError::RequestError)?;
+
+        let result = artifact_services
+            .get_artifacts(client_id, query_params.into_inner().release)
+            .await;
+
+        match result {
+            Ok(artifacts) => Ok(HttpResponse::Ok().json(artifacts)),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub async fn delete_artifact(
+        req: HttpRequest,
+        artifact_services: web::Data<Arc<ArtifactService>>,
+        artifact_id: web::Path<Uuid>,
+    ) -> Result<HttpResponse, ServerError> {
+        let client_id = Self::client_id(&req).map_err(ServerError::RequestError)?;
+
+        let result = artifact_services
+            .delete_artifact(client_id, artifact_id.into_inner())
+            .await;
+
+        match result {
+            Ok(()) => Ok(HttpResponse::Ok().finish()),
+            Err(err) => Err(err),
+        }
+    }
+
+    // The SDK auth middleware has already checked the pair, so the header is present
+    fn client_id(req: &HttpRequest) -> Result<Uuid, RequestError> {
+        req.headers()
+            .get("client_id")
+            .and_then(|header| header.to_str().ok())
+            .and_then(|client_id| Uuid::parse_str(client_id).ok())
+            .ok_or(RequestError::InvalidHeader)
+    }
+}
@@ -0,0 +1,132 @@
+use actix_web::http::header::{self, ContentDisposition, DispositionParam, DispositionType};
+use actix_web::{web, HttpRequest, HttpResponse, Result};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::config::Config;
+use crate::services::attachment_services::AttachmentService;
+use crate::services::namespace_services::NamespaceService;
+use crate::shared::utils::errors::{RequestError, ServerError};
+use crate::shared::utils::jwt::extract_user_id_from_jwt_header;
+use crate::shared::utils::role::{Permission, RoleRules};
+use shared_types::attachment_dtos::{AttachmentPathParams, UploadAttachmentQueryParams};
+
+pub struct AttachmentHandler;
+
+impl AttachmentHandler {
+    pub async fn upload_attachment(
+        req: HttpRequest,
+        attachment_services: web::Data<Arc<AttachmentService>>,
+        query_params: web::Query<UploadAttachmentQueryParams>,
+        body: web::Bytes,
+    ) -> Result<HttpResponse, ServerError> {
+        // The SDK auth middleware has already checked the pair, so the header is present
+        let client_id = req
+            .headers()
+            .get("client_id")
+            .and_then(|header| header.to_str().ok())
+            .and_then(|client_id| Uuid::parse_str(client_id).ok())
+            .ok_or(ServerError::RequestError(RequestError::InvalidHeader))?;
+        let content_type = req
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|header| header.to_str().ok());
+        let query_params = query_params.into_inner();
+
+        let result = attachment_services
+            .upload_attachment(
+                client_id,
+                query_params.error_id,
+                query_params.file_name,
+                content_type,
+                body.to_vec(),
+            )
+            .await;
+
+        match result {
+            Ok(attachment) => Ok(HttpResponse::Ok().json(attachment)),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub async fn get_attachments(
+        req: HttpRequest,
+        config: web::Data<Arc<Config>>,
+        namespace_services: web::Data<Arc<NamespaceService>>,
+        attachment_services: web::Data<Arc<AttachmentService>>,
+        role_rules: web::Data<Arc<RoleRules>>,
+        error_id: web::Path<Uuid>,
+    ) -> Result<HttpResponse, ServerError> {
+        let error_id = error_id.into_inner();
+        Self::check_perms(
+            &req,
+            &config,
+            &namespace_services,
+            &attachment_services,
+            &role_rules,
+            error_id,
+        )
+        .await?;
+
+        match attachment_services.get_attachments(error_id).await {
+            Ok(attachments) => Ok(HttpResponse::Ok().json(attachments)),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub async fn download_attachment(
+        req: HttpRequest,
+        config: web::Data<Arc<Config>>,
+        namespace_services: web::Data<Arc<NamespaceService>>,
+        attachment_services: web::Data<Arc<AttachmentService>>,
+        role_rules: web::Data<Arc<RoleRules>>,
+        path_params: web::Path<AttachmentPathParams>,
+    ) -> Result<HttpResponse, ServerError> {
+        let path_params = path_params.into_inner();
+        Self::check_perms(
+            &req,
+            &config,
+            &namespace_services,
+            &attachment_services,
+            &role_rules,
+            path_params.error_id,
+        )
+        .await?;
+
+        let (attachment, bytes) = attachment_services
+            .get_attachment(path_params.error_id, path_params.attachment_id)
+            .await?;
+
+        // Always a download, uploaded files are never rendered by the browser
+        Ok(HttpResponse::Ok()
+            .content_type(attachment.content_type)
+            .insert_header(ContentDisposition {
+                disposition: DispositionType::Attachment,
+                parameters: vec![DispositionParam::Filename(attachment.file_name)],
+            })
+            .insert_header((header::X_CONTENT_TYPE_OPTIONS, "nosniff"))
+            .body(bytes))
+    }
+
+    // Reading attachments needs the same access as viewing the error's namespace
+    async fn check_perms(
+        req: &HttpRequest,
+        config: &Config,
+        namespace_services: &NamespaceService,
+        attachment_services: &AttachmentService,
+        role_rules: &RoleRules,
+        error_id: Uuid,
+    ) -> Result<(), ServerError> {
+        let user_id = extract_user_id_from_jwt_header(req.headers(), &config.secret_key)?;
+        let namespace_id = attachment_services.error_namespace_id(error_id).await?;
+
+        match namespace_services
+            .check_user_namespace_perms(user_id, namespace_id, role_rules, Permission::View)
+            .await
+        {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(ServerError::RequestError(RequestError::PermissionDenied)),
+            Err(err) => Err(err),
+        }
+    }
+}
@@ -1,14 +1,11 @@
 use actix_web::{web, HttpRequest, HttpResponse, Result};
 use shared_types::extra_dtos::FilterRequest;
-use std::collections::HashMap;
 use std::sync::Arc;
 use uuid::Uuid;
 
-use crate::managers::notification_manager::NotificationServer;
+use crate::managers::ingestion_manager::IngestionManager;
 use crate::services::error_services::ErrorService;
-use crate::shared::utils::discord::DiscordHandler;
-use crate::shared::utils::errors::ServerError;
-use crate::{managers::namespace_manager::NamespaceServer, shared::utils::errors::RequestError};
+use crate::shared::utils::errors::{RequestError, ServerError};
 use shared_types::{
     error_dtos::{CreateErrorRequest, UpdateErrorDTO},
     extra_dtos::{ErrorMetadataQueryParams, ErrorPieChartQueryParams, TimeParams},
@@ -19,40 +16,67 @@ pub struct ErrorHandler;
 impl ErrorHandler {
     pub async fn create_error(
         req: HttpRequest,
-        discord_handler: web::Data<DiscordHandler>,
         error_services: web::Data<Arc<ErrorService>>,
-        notification_manager: web::Data<Arc<NotificationServer>>,
-        namespace_manager: web::Data<Arc<NamespaceServer>>,
-        service_mapping: web::Data<HashMap<&'static str, &'static str>>,
+        ingestion_manager: web::Data<Arc<IngestionManager>>,
         new_error: web::Json<CreateErrorRequest>,
     ) -> Result<HttpResponse, ServerError> {
         let error_dto = new_error.into_inner();
         let headers = req.headers();
-        let client_id_header = headers.get("client_id").unwrap();
-        let notification_manager = notification_manager.get_ref();
-        let discord_handler = discord_handler.get_ref();
-        let service_mapping = service_mapping.get_ref();
+        let client_id_header = headers
+            .get("client_id")
+            .ok_or(ServerError::RequestError(RequestError::MissingHeader))?;
 
         let client_id = match client_id_header.to_str() {
             Ok(client_id) => client_id,
             Err(_) => return Err(ServerError::RequestError(RequestError::InvalidHeader)),
         };
-        let client_id = Uuid::parse_str(client_id).unwrap();
+        let client_id = Uuid::parse_str(client_id)
+            .map_err(|_| ServerError::RequestError(RequestError::InvalidHeader))?;
+
+        let result = error_services.create_error(error_dto, client_id).await;
+
+        match result {
+            Ok((created, job)) => {
+                if !job.created.is_empty() {
+                    ingestion_manager.enqueue(job).await;
+                }
+                match created {
+                    Some(created) => Ok(HttpResponse::Ok().json(created)),
+                    None => Ok(HttpResponse::Ok().finish()),
+                }
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    pub async fn create_errors_batch(
+        req: HttpRequest,
+        error_services: web::Data<Arc<ErrorService>>,
+        ingestion_manager: web::Data<Arc<IngestionManager>>,
+        new_errors: web::Json<Vec<CreateErrorRequest>>,
+    ) -> Result<HttpResponse, ServerError> {
+        let headers = req.headers();
+        let client_id_header = headers
+            .get("client_id")
+            .ok_or(ServerError::RequestError(RequestError::MissingHeader))?;
+
+        let client_id = match client_id_header.to_str() {
+            Ok(client_id) => client_id,
+            Err(_) => return Err(ServerError::RequestError(RequestError::InvalidHeader)),
+        };
+        let client_id = Uuid::parse_str(client_id)
+            .map_err(|_| ServerError::RequestError(RequestError::InvalidHeader))?;
 
         let result = error_services
-            .create_error(
-                discord_handler,
-                error_dto.clone(),
-                client_id,
-                notification_manager,
-                service_mapping,
-            )
+            .create_errors_batch(new_errors.into_inner(), client_id)
             .await;
 
         match result {
-            Ok(error) => {
-                namespace_manager.broadcast_error(error).await;
-                Ok(HttpResponse::Ok().finish())
+            Ok((summary, job)) => {
+                if !job.created.is_empty() {
+                    ingestion_manager.enqueue(job).await;
+                }
+                Ok(HttpResponse::Ok().json(summary))
             }
             Err(err) => Err(err),
         }
@@ -91,7 +115,13 @@ impl ErrorHandler {
         let start_time = time_params.start_time;
         let timezone = time_params.timezone;
         match error_services
-            .get_aggregate_errors_by_date(namespace_id, start_time, time_interval_minutes, timezone)
+            .get_aggregate_errors_by_date(
+                namespace_id,
+                start_time,
+                time_interval_minutes,
+                timezone,
+                time_params.level,
+            )
             .await
         {
             Ok(errors) => Ok(HttpResponse::Ok().json(errors)),
@@ -109,6 +139,7 @@ impl ErrorHandler {
                 *namespace_id,
                 query_params.group_by.clone(),
                 query_params.group_key.clone(),
+                query_params.level,
                 query_params.offset as usize,
                 query_params.limit as usize,
             )
@@ -124,13 +155,28 @@ impl ErrorHandler {
         query_params: web::Query<ErrorPieChartQueryParams>,
     ) -> Result<HttpResponse, ServerError> {
         let result = namespace_services
-            .get_error_metrics_pie_chart(*namespace_id, query_params.group_by.clone())
+            .get_error_metrics_pie_chart(
+                *namespace_id,
+                query_params.group_by.clone(),
+                query_params.level,
+            )
             .await
             .map_err(|err| ServerError::from(err))?;
 
         Ok(HttpResponse::Ok().json(result))
     }
 
+    pub async fn get_error_counts_by_release(
+        error_services: web::Data<Arc<ErrorService>>,
+        namespace_id: web::Path<Uuid>,
+    ) -> Result<HttpResponse, ServerError> {
+        let result = error_services
+            .get_error_counts_by_release(namespace_id.into_inner())
+            .await?;
+
+        Ok(HttpResponse::Ok().json(result))
+    }
+
     pub async fn get_unique_error_meta_by_namespace(
         namespace_services: web::Data<Arc<ErrorService>>,
         namespace_id: web::Path<Uuid>,
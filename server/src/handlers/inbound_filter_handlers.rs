@@ -0,0 +1,157 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::config::Config;
+use crate::services::inbound_filter_services::InboundFilterService;
+use crate::services::namespace_services::NamespaceService;
+use crate::shared::utils::errors::{RequestError, ServerError};
+use crate::shared::utils::jwt::extract_user_id_from_jwt_header;
+use crate::shared::utils::role::{Permission, RoleRules};
+use shared_types::inbound_filter_dtos::{CreateInboundFilterDTO, UpdateInboundFilterDTO};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InboundFilterPathParams {
+    pub namespace_id: Uuid,
+    pub filter_id: Uuid,
+}
+
+pub struct InboundFilterHandler;
+
+impl InboundFilterHandler {
+    pub async fn get_filters(
+        req: HttpRequest,
+        config: web::Data<Arc<Config>>,
+        namespace_services: web::Data<Arc<NamespaceService>>,
+        inbound_filter_services: web::Data<Arc<InboundFilterService>>,
+        role_rules: web::Data<Arc<RoleRules>>,
+        namespace_id: web::Path<Uuid>,
+    ) -> Result<HttpResponse, ServerError> {
+        let namespace_id = namespace_id.into_inner();
+        Self::check_perms(
+            &req,
+            &config,
+            &namespace_services,
+            &role_rules,
+            namespace_id,
+            Permission::View,
+        )
+        .await?;
+
+        match inbound_filter_services.get_filters(namespace_id).await {
+            Ok(filters) => Ok(HttpResponse::Ok().json(filters)),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub async fn create_filter(
+        req: HttpRequest,
+        config: web::Data<Arc<Config>>,
+        namespace_services: web::Data<Arc<NamespaceService>>,
+        inbound_filter_services: web::Data<Arc<InboundFilterService>>,
+        role_rules: web::Data<Arc<RoleRules>>,
+        namespace_id: web::Path<Uuid>,
+        new_filter: web::Json<CreateInboundFilterDTO>,
+    ) -> Result<HttpResponse, ServerError> {
+        let namespace_id = namespace_id.into_inner();
+        Self::check_perms(
+            &req,
+            &config,
+            &namespace_services,
+            &role_rules,
+            namespace_id,
+            Permission::Update,
+        )
+        .await?;
+
+        match inbound_filter_services
+            .create_filter(namespace_id, new_filter.into_inner())
+            .await
+        {
+            Ok(filter) => Ok(HttpResponse::Ok().json(filter)),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub async fn update_filter(
+        req: HttpRequest,
+        config: web::Data<Arc<Config>>,
+        namespace_services: web::Data<Arc<NamespaceService>>,
+        inbound_filter_services: web::Data<Arc<InboundFilterService>>,
+        role_rules: web::Data<Arc<RoleRules>>,
+        path_params: web::Path<InboundFilterPathParams>,
+        update_filter: web::Json<UpdateInboundFilterDTO>,
+    ) -> Result<HttpResponse, ServerError> {
+        let path_params = path_params.into_inner();
+        Self::check_perms(
+            &req,
+            &config,
+            &namespace_services,
+            &role_rules,
+            path_params.namespace_id,
+            Permission::Update,
+        )
+        .await?;
+
+        match inbound_filter_services
+            .update_filter(
+                path_params.namespace_id,
+                path_params.filter_id,
+                update_filter.into_inner(),
+            )
+            .await
+        {
+            Ok(filter) => Ok(HttpResponse::Ok().json(filter)),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub async fn delete_filter(
+        req: HttpRequest,
+        config: web::Data<Arc<Config>>,
+        namespace_services: web::Data<Arc<NamespaceService>>,
+        inbound_filter_services: web::Data<Arc<InboundFilterService>>,
+        role_rules: web::Data<Arc<RoleRules>>,
+        path_params: web::Path<InboundFilterPathParams>,
+    ) -> Result<HttpResponse, ServerError> {
+        let path_params = path_params.into_inner();
+        Self::check_perms(
+            &req,
+            &config,
+            &namespace_services,
+            &role_rules,
+            path_params.namespace_id,
+            Permission::Update,
+        )
+        .await?;
+
+        match inbound_filter_services
+            .delete_filter(path_params.namespace_id, path_params.filter_id)
+            .await
+        {
+            Ok(()) => Ok(HttpResponse::Ok().finish()),
+            Err(err) => Err(err),
+        }
+    }
+
+    async fn check_perms(
+        req: &HttpRequest,
+        config: &Config,
+        namespace_services: &NamespaceService,
+        role_rules: &RoleRules,
+        namespace_id: Uuid,
+        action: Permission,
+    ) -> Result<(), ServerError> {
+        let user_id = extract_user_id_from_jwt_header(req.headers(), &config.secret_key)?;
+
+        match namespace_services
+            .check_user_namespace_perms(user_id, namespace_id, role_rules, action)
+            .await
+        {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(ServerError::RequestError(RequestError::PermissionDenied)),
+            Err(err) => Err(err),
+        }
+    }
+}
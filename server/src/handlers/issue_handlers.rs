@@ -0,0 +1,148 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::config::Config;
+use crate::services::issue_services::IssueService;
+use crate::services::namespace_services::NamespaceService;
+use crate::shared::utils::errors::{RequestError, ServerError};
+use crate::shared::utils::jwt::extract_user_id_from_jwt_header;
+use crate::shared::utils::role::{Permission, RoleRules};
+use shared_types::extra_dtos::PaginationParams;
+use shared_types::issue_dtos::UpdateIssueDTO;
+
+pub struct IssueHandler;
+
+impl IssueHandler {
+    pub async fn get_issue_by_id(
+        req: HttpRequest,
+        config: web::Data<Arc<Config>>,
+        namespace_services: web::Data<Arc<NamespaceService>>,
+        issue_services: web::Data<Arc<IssueService>>,
+        role_rules: web::Data<Arc<RoleRules>>,
+        issue_id: web::Path<Uuid>,
+    ) -> Result<HttpResponse, ServerError> {
+        let issue_id = issue_id.into_inner();
+        let namespace_id = issue_services.get_issue_namespace_id(issue_id).await?;
+        Self::check_perms(
+            &req,
+            &config,
+            &namespace_services,
+            &role_rules,
+            namespace_id,
+            Permission::View,
+        )
+        .await?;
+
+        match issue_services.get_issue_by_id(issue_id).await {
+            Ok(issue) => Ok(HttpResponse::Ok().json(issue)),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub async fn update_issue(
+        req: HttpRequest,
+        config: web::Data<Arc<Config>>,
+        namespace_services: web::Data<Arc<NamespaceService>>,
+        issue_services: web::Data<Arc<IssueService>>,
+        role_rules: web::Data<Arc<RoleRules>>,
+        update_issue: web::Json<UpdateIssueDTO>,
+    ) -> Result<HttpResponse, ServerError> {
+        let namespace_id = issue_services
+            .get_issue_namespace_id(update_issue.id)
+            .await?;
+        Self::check_perms(
+            &req,
+            &config,
+            &namespace_services,
+            &role_rules,
+            namespace_id,
+            Permission::Update,
+        )
+        .await?;
+
+        match issue_services.update_issue(update_issue.into_inner()).await {
+            Ok(issue) => Ok(HttpResponse::Ok().json(issue)),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub async fn get_issues_by_namespace(
+        req: HttpRequest,
+        config: web::Data<Arc<Config>>,
+        namespace_services: web::Data<Arc<NamespaceService>>,
+        issue_services: web::Data<Arc<IssueService>>,
+        role_rules: web::Data<Arc<RoleRules>>,
+        namespace_id: web::Path<Uuid>,
+        pagination: web::Query<PaginationParams>,
+    ) -> Result<HttpResponse, ServerError> {
+        let namespace_id = namespace_id.into_inner();
+        Self::check_perms(
+            &req,
+            &config,
+            &namespace_services,
+            &role_rules,
+            namespace_id,
+            Permission::View,
+        )
+        .await?;
+
+        match issue_services
+            .get_issues_by_namespace(namespace_id, pagination.offset, pagination.limit)
+            .await
+        {
+            Ok(issues) => Ok(HttpResponse::Ok().json(issues)),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub async fn get_errors_by_issue(
+        req: HttpRequest,
+        config: web::Data<Arc<Config>>,
+        namespace_services: web::Data<Arc<NamespaceService>>,
+        issue_services: web::Data<Arc<IssueService>>,
+        role_rules: web::Data<Arc<RoleRules>>,
+        issue_id: web::Path<Uuid>,
+        pagination: web::Query<PaginationParams>,
+    ) -> Result<HttpResponse, ServerError> {
+        let issue_id = issue_id.into_inner();
+        let namespace_id = issue_services.get_issue_namespace_id(issue_id).await?;
+        Self::check_perms(
+            &req,
+            &config,
+            &namespace_services,
+            &role_rules,
+            namespace_id,
+            Permission::View,
+        )
+        .await?;
+
+        match issue_services
+            .get_errors_by_issue(issue_id, pagination.offset, pagination.limit)
+            .await
+        {
+            Ok(errors) => Ok(HttpResponse::Ok().json(errors)),
+            Err(err) => Err(err),
+        }
+    }
+
+    async fn check_perms(
+        req: &HttpRequest,
+        config: &Config,
+        namespace_services: &NamespaceService,
+        role_rules: &RoleRules,
+        namespace_id: Uuid,
+        action: Permission,
+    ) -> Result<(), ServerError> {
+        let user_id = extract_user_id_from_jwt_header(req.headers(), &config.secret_key)?;
+
+        match namespace_services
+            .check_user_namespace_perms(user_id, namespace_id, role_rules, action)
+            .await
+        {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(ServerError::RequestError(RequestError::PermissionDenied)),
+            Err(err) => Err(err),
+        }
+    }
+}
@@ -1,11 +1,17 @@
 pub mod admin_handlers;
+pub mod artifact_handlers;
+pub mod attachment_handlers;
 pub mod auth_handlers;
 pub mod bug_report_handlers;
 pub mod error_handlers;
 pub mod feature_request_handlers;
+pub mod inbound_filter_handlers;
+pub mod issue_handlers;
 pub mod namespace_alert_handlers;
 pub mod namespace_handlers;
 pub mod notification_handlers;
+pub mod otlp_handlers;
+pub mod sentry_handlers;
 pub mod tag_handlers;
 pub mod user_handlers;
 pub mod ws_handlers;
@@ -1,9 +1,13 @@
-use actix_web::{web, HttpResponse};
+use actix_web::{web, HttpRequest, HttpResponse};
 use std::sync::Arc;
 use uuid::Uuid;
 
+use crate::config::Config;
 use crate::services::namespace_alerts_services::NamespaceAlertsService;
-use crate::shared::utils::errors::ServerError;
+use crate::services::namespace_services::NamespaceService;
+use crate::shared::utils::errors::{RequestError, ServerError};
+use crate::shared::utils::jwt::extract_user_id_from_jwt_header;
+use crate::shared::utils::role::{Permission, RoleRules};
 use shared_types::namespace_alert_dtos::{
     CreateNamespaceAlertRequestDTO, NamespaceAlertSubscriptionRequestDTO,
     UpdateNamespaceAlertRequestDTO,
@@ -93,7 +97,7 @@ impl NamespaceAlertHandler {
             .update_namespace_alert(alert_id.into_inner(), updated_alert)
             .await
         {
-            Ok(_) => Ok(HttpResponse::Ok().finish()),
+            Ok(updated) => Ok(HttpResponse::Ok().json(updated)),
             Err(err) => Err(err),
         }
     }
@@ -122,4 +126,34 @@ impl NamespaceAlertHandler {
             Err(err) => Err(err),
         }
     }
+
+    pub async fn test_webhook(
+        req: HttpRequest,
+        config: web::Data<Arc<Config>>,
+        namespace_services: web::Data<Arc<NamespaceService>>,
+        namespace_alert_services: web::Data<Arc<NamespaceAlertsService>>,
+        role_rules: web::Data<Arc<RoleRules>>,
+        alert_id: web::Path<Uuid>,
+    ) -> Result<HttpResponse, ServerError> {
+        let alert_id = alert_id.into_inner();
+        let user_id = extract_user_id_from_jwt_header(req.headers(), &config.secret_key)?;
+        let namespace_id = namespace_alert_services
+            .get_alert_namespace_id(alert_id)
+            .await?;
+
+        // Sends a request to a user-supplied URL, so it takes the same rights as editing
+        match namespace_services
+            .check_user_namespace_perms(user_id, namespace_id, &role_rules, Permission::Update)
+            .await
+        {
+            Ok(true) => {}
+            Ok(false) => return Err(ServerError::RequestError(RequestError::PermissionDenied)),
+            Err(err) => return Err(err),
+        }
+
+        match namespace_alert_services.test_webhook(alert_id).await {
+            Ok(result) => Ok(HttpResponse::Ok().json(result)),
+            Err(err) => Err(err),
+        }
+    }
 }
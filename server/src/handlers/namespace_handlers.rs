@@ -9,6 +9,7 @@ use crate::handlers::ws_handlers::namespace_error_ws_session;
 use crate::managers::namespace_manager::NamespaceServer;
 use crate::managers::notification_manager::NotificationServer;
 use crate::services::namespace_services::NamespaceService;
+use crate::shared::utils::context_filter::parse_context_filters;
 use crate::shared::utils::errors::{ExternalError, QueryError, RequestError, ServerError};
 use crate::shared::utils::jwt::extract_user_id_from_jwt_header;
 use crate::shared::utils::role::{get_weight, string_to_role, Permission, RoleRules};
@@ -141,19 +142,27 @@ impl NamespaceHandler {
         let group_by = query_params
             .group_by
             .clone()
-            .unwrap_or_else(|| "message".to_string());
+            .unwrap_or_else(|| "issue".to_string());
+        let context_filters = match &query_params.context {
+            Some(context) => parse_context_filters(context).map_err(ServerError::RequestError)?,
+            None => Vec::new(),
+        };
         let result = namespace_services
             .get_errors_by_namespace_with_pagination(
                 *namespace_id,
                 group_by,
+                context_filters,
+                query_params.level,
                 query_params.offset as usize,
                 query_params.limit as usize,
             )
             .await?;
         match result {
+            AggregatedResult::ByIssue(issue) => Ok(HttpResponse::Ok().json(issue)),
             AggregatedResult::ByTags(tag) => Ok(HttpResponse::Ok().json(tag)),
             AggregatedResult::ByLine(line) => Ok(HttpResponse::Ok().json(line)),
             AggregatedResult::ByMessage(message) => Ok(HttpResponse::Ok().json(message)),
+            AggregatedResult::ByLevel(level) => Ok(HttpResponse::Ok().json(level)),
         }
     }
 
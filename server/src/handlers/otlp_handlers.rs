@@ -0,0 +1,195 @@
+use actix_web::http::StatusCode;
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Result};
+use prost::Message;
+use sea_orm::DatabaseConnection;
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::managers::ingestion_manager::IngestionManager;
+use crate::services::error_services::{ErrorService, MAX_BATCH_SIZE};
+use crate::shared::utils::errors::{RequestError, ServerError};
+use crate::shared::utils::jwt::validate_namespace_secret_jwt;
+use crate::shared::utils::otlp::{
+    exceptions_from_logs, exceptions_from_traces, ExportLogsServiceRequest, ExportPartialSuccess,
+    ExportServiceResponse, ExportTraceServiceRequest, OtlpException,
+};
+use shared_types::error_dtos::CreateErrorRequest;
+
+const PROTOBUF_CONTENT_TYPE: &str = "application/x-protobuf";
+
+pub struct OtlpHandler;
+
+impl OtlpHandler {
+    pub async fn export_logs(
+        req: HttpRequest,
+        db_pool: web::Data<Arc<DatabaseConnection>>,
+        error_services: web::Data<Arc<ErrorService>>,
+        ingestion_manager: web::Data<Arc<IngestionManager>>,
+        body: web::Bytes,
+    ) -> Result<HttpResponse, ServerError> {
+        let is_protobuf = Self::is_protobuf(&req).ok_or_else(Self::unsupported_media_type)?;
+        let request: ExportLogsServiceRequest = match is_protobuf {
+            true => Message::decode(body.as_ref()).map_err(|_| Self::invalid_payload())?,
+            false => serde_json::from_slice(&body).map_err(|_| Self::invalid_payload())?,
+        };
+
+        let exceptions = exceptions_from_logs(
+            request,
+            Self::header_client_id(&req).map_err(ServerError::RequestError)?,
+        );
+
+        match Self::ingest(
+            &req,
+            &db_pool,
+            &error_services,
+            &ingestion_manager,
+            exceptions,
+        )
+        .await
+        {
+            Ok(rejected) => Ok(Self::export_response(is_protobuf, rejected)),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub async fn export_traces(
+        req: HttpRequest,
+        db_pool: web::Data<Arc<DatabaseConnection>>,
+        error_services: web::Data<Arc<ErrorService>>,
+        ingestion_manager: web::Data<Arc<IngestionManager>>,
+        body: web::Bytes,
+    ) -> Result<HttpResponse, ServerError> {
+        let is_protobuf = Self::is_protobuf(&req).ok_or_else(Self::unsupported_media_type)?;
+        let request: ExportTraceServiceRequest = match is_protobuf {
+            true => Message::decode(body.as_ref()).map_err(|_| Self::invalid_payload())?,
+            false => serde_json::from_slice(&body).map_err(|_| Self::invalid_payload())?,
+        };
+
+        let exceptions = exceptions_from_traces(
+            request,
+            Self::header_client_id(&req).map_err(ServerError::RequestError)?,
+        );
+
+        match Self::ingest(
+            &req,
+            &db_pool,
+            &error_services,
+            &ingestion_manager,
+            exceptions,
+        )
+        .await
+        {
+            Ok(rejected) => Ok(Self::export_response(is_protobuf, rejected)),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Stores the exceptions per namespace and returns how many were rejected. Every
+    /// namespace a request routes to must accept the `client_secret` header, and nothing
+    /// is stored unless all of them do.
+    async fn ingest(
+        req: &HttpRequest,
+        db_pool: &DatabaseConnection,
+        error_services: &ErrorService,
+        ingestion_manager: &IngestionManager,
+        exceptions: Vec<OtlpException>,
+    ) -> Result<i64, ServerError> {
+        let client_secret = req
+            .headers()
+            .get("client_secret")
+            .and_then(|header| header.to_str().ok())
+            .ok_or_else(|| Self::unauthorized("Missing client_secret header"))?;
+
+        let mut rejected: i64 = 0;
+        let mut by_namespace: HashMap<Uuid, Vec<CreateErrorRequest>> = HashMap::new();
+        for exception in exceptions {
+            match exception.client_id {
+                Some(client_id) => by_namespace
+                    .entry(client_id)
+                    .or_default()
+                    .push(exception.request),
+                None => rejected += 1,
+            }
+        }
+
+        for client_id in by_namespace.keys() {
+            match validate_namespace_secret_jwt(*client_id, client_secret.to_string(), db_pool)
+                .await
+            {
+                Ok(true) => {}
+                _ => return Err(Self::unauthorized("Invalid client_id or client_secret")),
+            }
+        }
+
+        for (client_id, requests) in by_namespace {
+            let mut requests = requests.into_iter().peekable();
+            while requests.peek().is_some() {
+                let chunk: Vec<CreateErrorRequest> =
+                    requests.by_ref().take(MAX_BATCH_SIZE).collect();
+
+                let (summary, job) = error_services.create_errors_batch(chunk, client_id).await?;
+                rejected += summary.rejected as i64;
+
+                if !job.created.is_empty() {
+                    ingestion_manager.enqueue(job).await;
+                }
+            }
+        }
+
+        Ok(rejected)
+    }
+
+    fn export_response(is_protobuf: bool, rejected: i64) -> HttpResponse {
+        let response = ExportServiceResponse {
+            partial_success: (rejected > 0).then(|| ExportPartialSuccess {
+                rejected,
+                error_message:
+                    "Exceptions without a namespace or a parsable stack trace were rejected"
+                        .to_string(),
+            }),
+        };
+
+        match is_protobuf {
+            true => HttpResponse::Ok()
+                .content_type(PROTOBUF_CONTENT_TYPE)
+                .body(response.encode_to_vec()),
+            false => HttpResponse::Ok().json(response),
+        }
+    }
+
+    fn is_protobuf(req: &HttpRequest) -> Option<bool> {
+        match req.content_type() {
+            PROTOBUF_CONTENT_TYPE => Some(true),
+            "application/json" => Some(false),
+            _ => None,
+        }
+    }
+
+    fn header_client_id(req: &HttpRequest) -> Result<Option<Uuid>, RequestError> {
+        match req.headers().get("client_id") {
+            Some(header) => header
+                .to_str()
+                .ok()
+                .and_then(|client_id| Uuid::parse_str(client_id).ok())
+                .map(Some)
+                .ok_or(RequestError::InvalidHeader),
+            None => Ok(None),
+        }
+    }
+
+    fn unsupported_media_type() -> ServerError {
+        ServerError::HttpError(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            "Expected application/json or application/x-protobuf".to_string(),
+        )
+    }
+
+    fn invalid_payload() -> ServerError {
+        ServerError::HttpError(StatusCode::BAD_REQUEST, "Invalid OTLP payload".to_string())
+    }
+
+    fn unauthorized(message: &str) -> ServerError {
+        ServerError::HttpError(StatusCode::UNAUTHORIZED, message.to_string())
+    }
+}
@@ -0,0 +1,117 @@
+use actix_web::http::StatusCode;
+use actix_web::{web, HttpRequest, HttpResponse, Result};
+use sea_orm::DatabaseConnection;
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::managers::ingestion_manager::IngestionManager;
+use crate::services::error_services::ErrorService;
+use crate::shared::utils::errors::{QueryError, RequestError, ServerError};
+use crate::shared::utils::jwt::validate_namespace_secret_jwt;
+use crate::shared::utils::sentry::{
+    auth_from_dsn, event_to_error_request, extract_sentry_auth, parse_envelope, SentryAuth,
+};
+use shared_types::sentry_dtos::{SentryEvent, SentryEventIdDTO};
+
+pub struct SentryHandler;
+
+impl SentryHandler {
+    pub async fn store(
+        req: HttpRequest,
+        query: web::Query<HashMap<String, String>>,
+        db_pool: web::Data<Arc<DatabaseConnection>>,
+        error_services: web::Data<Arc<ErrorService>>,
+        ingestion_manager: web::Data<Arc<IngestionManager>>,
+        body: web::Bytes,
+    ) -> Result<HttpResponse, ServerError> {
+        let auth = extract_sentry_auth(req.headers(), &query).ok_or_else(Self::invalid_key)?;
+        let client_id = Self::authenticate(&db_pool, auth).await?;
+
+        let event: SentryEvent = serde_json::from_slice(&body)
+            .map_err(|_| ServerError::RequestError(RequestError::InvalidSentryPayload))?;
+
+        match Self::ingest_event(&error_services, &ingestion_manager, client_id, event).await {
+            Ok(event_id) => Ok(HttpResponse::Ok().json(SentryEventIdDTO { id: event_id })),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub async fn envelope(
+        req: HttpRequest,
+        query: web::Query<HashMap<String, String>>,
+        db_pool: web::Data<Arc<DatabaseConnection>>,
+        error_services: web::Data<Arc<ErrorService>>,
+        ingestion_manager: web::Data<Arc<IngestionManager>>,
+        body: web::Bytes,
+    ) -> Result<HttpResponse, ServerError> {
+        let (header, events) = parse_envelope(&body).map_err(ServerError::RequestError)?;
+
+        let auth = extract_sentry_auth(req.headers(), &query)
+            .or_else(|| header.dsn.as_deref().and_then(auth_from_dsn))
+            .ok_or_else(Self::invalid_key)?;
+        let client_id = Self::authenticate(&db_pool, auth).await?;
+
+        let mut event_id = header.event_id;
+        for event in events {
+            let ingested_id =
+                Self::ingest_event(&error_services, &ingestion_manager, client_id, event).await?;
+            event_id.get_or_insert(ingested_id);
+        }
+
+        Ok(HttpResponse::Ok().json(SentryEventIdDTO {
+            id: event_id.unwrap_or_else(|| Uuid::new_v4().simple().to_string()),
+        }))
+    }
+
+    async fn ingest_event(
+        error_services: &ErrorService,
+        ingestion_manager: &IngestionManager,
+        client_id: Uuid,
+        event: SentryEvent,
+    ) -> Result<String, ServerError> {
+        let event_id = event
+            .event_id
+            .clone()
+            .unwrap_or_else(|| Uuid::new_v4().simple().to_string());
+
+        match error_services
+            .create_error(event_to_error_request(event), client_id)
+            .await
+        {
+            Ok((_, job)) => {
+                if !job.created.is_empty() {
+                    ingestion_manager.enqueue(job).await;
+                }
+                Ok(event_id)
+            }
+            Err(ServerError::QueryError(QueryError::NamespaceNotFound)) => Err(Self::invalid_key()),
+            Err(err) => Err(err),
+        }
+    }
+
+    // Modern SDKs only send the public key, a secret is checked whenever one is sent
+    async fn authenticate(
+        db_pool: &DatabaseConnection,
+        auth: SentryAuth,
+    ) -> Result<Uuid, ServerError> {
+        let Some(secret) = auth.secret else {
+            return Ok(auth.key);
+        };
+
+        match validate_namespace_secret_jwt(auth.key, secret, db_pool).await {
+            Ok(true) => Ok(auth.key),
+            Ok(false) | Err(ServerError::QueryError(QueryError::NamespaceNotFound)) => {
+                Err(Self::invalid_key())
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn invalid_key() -> ServerError {
+        ServerError::HttpError(
+            StatusCode::UNAUTHORIZED,
+            "Missing or invalid sentry_key or sentry_secret".to_string(),
+        )
+    }
+}
@@ -1 +1,2 @@
 pub mod oauth_client;
+pub mod object_store;
@@ -0,0 +1,73 @@
+use async_trait::async_trait;
+use std::io::{Error as IoError, ErrorKind, Result as IoResult};
+use std::path::{Component, Path, PathBuf};
+
+/// Blob storage for uploaded files (source maps, attachments). Keys are
+/// slash-separated relative paths chosen by the server.
+#[async_trait]
+pub trait ObjectStore: Send + Sync {
+    async fn put(&self, key: &str, bytes: &[u8]) -> IoResult<()>;
+
+    /// Returns `None` when nothing is stored under the key.
+    async fn get(&self, key: &str) -> IoResult<Option<Vec<u8>>>;
+
+    async fn delete(&self, key: &str) -> IoResult<()>;
+}
+
+/// Stores objects as files below a root directory on the local filesystem.
+pub struct LocalObjectStore {
+    root: PathBuf,
+}
+
+impl LocalObjectStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn path_for(&self, key: &str) -> IoResult<PathBuf> {
+        let key = Path::new(key);
+
+        // Never let a key escape the root directory
+        if !key
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+        {
+            return Err(IoError::new(
+                ErrorKind::InvalidInput,
+                "Object keys must be relative paths",
+            ));
+        }
+
+        Ok(self.root.join(key))
+    }
+}
+
+#[async_trait]
+impl ObjectStore for LocalObjectStore {
+    async fn put(&self, key: &str, bytes: &[u8]) -> IoResult<()> {
+        let path = self.path_for(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        // Write next to the target and rename so readers never see a partial file
+        let temp_path = path.with_extension("tmp");
+        tokio::fs::write(&temp_path, bytes).await?;
+        tokio::fs::rename(&temp_path, &path).await
+    }
+
+    async fn get(&self, key: &str) -> IoResult<Option<Vec<u8>>> {
+        match tokio::fs::read(self.path_for(key)?).await {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> IoResult<()> {
+        match tokio::fs::remove_file(self.path_for(key)?).await {
+            Err(err) if err.kind() != ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        }
+    }
+}
@@ -14,6 +14,8 @@ mod libs;
 use actix_web::web;
 use env_logger;
 use log::{error, info};
+use managers::alert_scheduler::AlertScheduler;
+use managers::ingestion_manager::{IngestionContext, IngestionManager};
 use managers::namespace_manager::NamespaceServer;
 use managers::notification_manager::NotificationServer;
 use shared::utils::rate_limit::DynamicStripedRateLimiter;
@@ -27,9 +29,10 @@ use crate::middlewares::{
     sdk_auth_middleware::ClientAuthMiddleware,
 };
 use crate::routes::{
-    admin_routes, auth_routes, bug_report_routes, error_routes, feature_request_routes,
-    namespace_alert_routes, namespace_routes, notification_routes, static_routes, tag_routes,
-    user_routes, ws_routes,
+    admin_routes, artifact_routes, attachment_routes, auth_routes, bug_report_routes, error_routes,
+    feature_request_routes, issue_routes, namespace_alert_routes, namespace_routes,
+    notification_routes, otlp_routes, sentry_routes, static_routes, tag_routes, user_routes,
+    ws_routes,
 };
 use crate::services::init_services;
 use crate::shared::utils::discord::DiscordHandler;
@@ -93,6 +96,9 @@ async fn main(
         .await
         .unwrap();
 
+    let artifact_service = services.artifact_service;
+    let attachment_service = Arc::new(services.attachment_service);
+    let inbound_filter_service = services.inbound_filter_service;
     let namespace_service = Arc::new(services.namespace_service);
     let namespace_alert_service = Arc::new(services.namespace_alerts_services);
     let user_service = Arc::new(services.user_service);
@@ -102,9 +108,34 @@ async fn main(
     let tag_service = Arc::new(services.tag_service);
     let notification_service = Arc::new(services.notification_service);
     let feature_request_service = Arc::new(services.feature_request_service);
+    let issue_service = Arc::new(services.issue_service);
     let namespace_manager = Arc::new(NamespaceServer::new());
     let notification_manager = Arc::new(NotificationServer::new());
 
+    // Alert delivery and live pushes run off the request path. Adjust as we scale
+    let ingestion_manager = IngestionManager::new(
+        1024,
+        4,
+        Duration::from_millis(500),
+        IngestionContext {
+            error_service: Arc::clone(&error_service),
+            discord_handler: discord_handler.clone(),
+            notification_manager: Arc::clone(&notification_manager),
+            namespace_manager: Arc::clone(&namespace_manager),
+            service_mapping: &SERVICE_MAPPING,
+        },
+    );
+
+    // Conditions like unresolved age can fire with no errors coming in
+    AlertScheduler {
+        error_service: Arc::clone(&error_service),
+        discord_handler: discord_handler.clone(),
+        notification_manager: Arc::clone(&notification_manager),
+        service_mapping: &SERVICE_MAPPING,
+        tick_interval: Duration::from_secs(60),
+    }
+    .start();
+
     let role_rules = Arc::new(initialize_role_rules());
 
     // Return a closure that configures the service
@@ -121,8 +152,13 @@ async fn main(
             .app_data(web::Data::new(tag_service.clone()))
             .app_data(web::Data::new(notification_service.clone()))
             .app_data(web::Data::new(feature_request_service.clone()))
+            .app_data(web::Data::new(issue_service.clone()))
+            .app_data(web::Data::new(artifact_service.clone()))
+            .app_data(web::Data::new(attachment_service.clone()))
+            .app_data(web::Data::new(inbound_filter_service.clone()))
             .app_data(web::Data::new(namespace_manager.clone()))
             .app_data(web::Data::new(notification_manager.clone()))
+            .app_data(web::Data::new(ingestion_manager.clone()))
             .app_data(web::Data::new(discord_handler))
             .app_data(web::Data::new(SERVICE_MAPPING.clone()))
             .configure(static_routes::configure)
@@ -132,13 +168,19 @@ async fn main(
             .configure(|cfg| auth_routes::configure_with_auth(cfg, &jwt_middleware))
             .configure(|cfg| bug_report_routes::configure(cfg, &jwt_middleware))
             .configure(|cfg| error_routes::sdk_configure(cfg, &sdk_middleware))
+            .configure(|cfg| artifact_routes::sdk_configure(cfg, &sdk_middleware))
+            .configure(|cfg| attachment_routes::sdk_configure(cfg, &sdk_middleware))
+            .configure(otlp_routes::configure)
             .configure(|cfg| feature_request_routes::configure(cfg, &jwt_middleware))
             .configure(|cfg| user_routes::configure_user_routes(cfg, &jwt_middleware))
             .configure(|cfg| namespace_routes::configure(cfg, &jwt_middleware))
             .configure(|cfg| namespace_alert_routes::configure(cfg, &jwt_middleware))
             .configure(|cfg| notification_routes::configure(cfg, &jwt_middleware))
             .configure(|cfg| error_routes::configure(cfg, &jwt_middleware))
-            .configure(|cfg| tag_routes::configure(cfg, &jwt_middleware));
+            .configure(|cfg| issue_routes::configure(cfg, &jwt_middleware))
+            .configure(|cfg| tag_routes::configure(cfg, &jwt_middleware))
+            // Matches any /api/{project_id} prefix, so it must come after the other API scopes
+            .configure(sentry_routes::configure);
     };
 
     Ok(config.into())
@@ -0,0 +1,120 @@
+use chrono::Utc;
+use log::error;
+use sea_orm::sea_query::{Expr, OnConflict, Query};
+use sea_orm::{ConnectionTrait, DatabaseConnection, DbErr};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::interval;
+use uuid::Uuid;
+
+use crate::managers::notification_manager::NotificationServer;
+use crate::models::scheduler_lease_model::{
+    Column as SchedulerLeaseColumn, Entity as SchedulerLeaseEntity,
+};
+use crate::services::error_services::ErrorService;
+use crate::shared::utils::discord::DiscordHandler;
+
+const LEASE_NAME: &str = "alert_scheduler";
+
+/// Periodically evaluates alert conditions that can fire while no errors arrive. Only
+/// the server instance holding the scheduler lease runs a tick, so scaling out doesn't
+/// multiply the queries.
+pub struct AlertScheduler {
+    pub error_service: Arc<ErrorService>,
+    pub discord_handler: DiscordHandler,
+    pub notification_manager: Arc<NotificationServer>,
+    pub service_mapping: &'static HashMap<&'static str, &'static str>,
+    pub tick_interval: Duration,
+}
+
+impl AlertScheduler {
+    pub fn start(self) {
+        let instance_id = Uuid::new_v4();
+
+        tokio::spawn(async move {
+            let mut interval = interval(self.tick_interval);
+
+            loop {
+                interval.tick().await;
+                self.tick(instance_id).await;
+            }
+        });
+    }
+
+    async fn tick(&self, instance_id: Uuid) {
+        let now = Utc::now();
+        // Outlives one interval so the holder renews it before anyone can take over
+        let lease_ttl = self.tick_interval * 2;
+
+        match try_acquire_lease(&self.error_service.db, instance_id, lease_ttl).await {
+            Ok(true) => {}
+            Ok(false) => return,
+            Err(err) => {
+                error!("Failed to acquire the alert scheduler lease: {}", err);
+                return;
+            }
+        }
+
+        if let Err(err) = self
+            .error_service
+            .evaluate_scheduled_alerts(
+                &self.discord_handler,
+                &self.notification_manager,
+                self.service_mapping,
+                now,
+            )
+            .await
+        {
+            error!("Failed to evaluate scheduled alerts: {}", err);
+        }
+    }
+}
+
+// Takes the lease when it is free or expired, or renews it for its current holder.
+// Expiry is read and set against the database clock, so instances with drifting
+// clocks agree on when a lease runs out.
+async fn try_acquire_lease(
+    db: &DatabaseConnection,
+    instance_id: Uuid,
+    ttl: Duration,
+) -> Result<bool, DbErr> {
+    let ttl_ms = i64::try_from(ttl.as_millis()).unwrap_or(i64::MAX);
+    let db_now = || Expr::cust("now()");
+
+    let insert = Query::insert()
+        .into_table(SchedulerLeaseEntity)
+        .columns([
+            SchedulerLeaseColumn::Name,
+            SchedulerLeaseColumn::Holder,
+            SchedulerLeaseColumn::ExpiresAt,
+        ])
+        .values_panic([
+            LEASE_NAME.into(),
+            instance_id.into(),
+            Expr::cust_with_values("now() + $1 * interval '1 millisecond'", [ttl_ms]),
+        ])
+        .on_conflict(
+            OnConflict::column(SchedulerLeaseColumn::Name)
+                .update_columns([
+                    SchedulerLeaseColumn::Holder,
+                    SchedulerLeaseColumn::ExpiresAt,
+                ])
+                .action_and_where(
+                    Expr::col((SchedulerLeaseEntity, SchedulerLeaseColumn::ExpiresAt))
+                        .lt(db_now())
+                        .or(
+                            Expr::col((SchedulerLeaseEntity, SchedulerLeaseColumn::Holder))
+                                .eq(instance_id),
+                        ),
+                )
+                .to_owned(),
+        )
+        .to_owned();
+
+    let result = db
+        .execute(db.get_database_backend().build(&insert))
+        .await?;
+
+    Ok(result.rows_affected() == 1)
+}
@@ -0,0 +1,184 @@
+use chrono::{DateTime, Utc};
+use log::{error, warn};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, Mutex};
+
+use crate::managers::namespace_manager::NamespaceServer;
+use crate::managers::notification_manager::NotificationServer;
+use crate::models::namespace_model::Model as NamespaceModel;
+use crate::services::error_services::{ErrorService, PreparedError};
+use crate::shared::utils::discord::DiscordHandler;
+use shared_types::error_dtos::CreateErrorDTO;
+use shared_types::extra_dtos::IngestionMetricsDTO;
+
+/// Work left over once errors have been persisted: alert evaluation, notification
+/// fan-out and the live push to dashboard sessions.
+pub struct IngestionJob {
+    pub namespace: NamespaceModel,
+    pub errors: Vec<PreparedError>,
+    pub created: Vec<CreateErrorDTO>,
+    pub received_at: DateTime<Utc>,
+}
+
+impl IngestionJob {
+    /// A job for a request that stored nothing, e.g. a sampled-out or resent error.
+    pub fn empty(namespace: NamespaceModel, received_at: DateTime<Utc>) -> Self {
+        Self {
+            namespace,
+            errors: vec![],
+            created: vec![],
+            received_at,
+        }
+    }
+}
+
+/// Everything a worker needs to deliver alerts and push errors to live sessions.
+#[derive(Clone)]
+pub struct IngestionContext {
+    pub error_service: Arc<ErrorService>,
+    pub discord_handler: DiscordHandler,
+    pub notification_manager: Arc<NotificationServer>,
+    pub namespace_manager: Arc<NamespaceServer>,
+    pub service_mapping: &'static HashMap<&'static str, &'static str>,
+}
+
+#[derive(Default)]
+struct IngestionMetrics {
+    enqueued: AtomicU64,
+    processed: AtomicU64,
+    failed: AtomicU64,
+    dropped: AtomicU64,
+}
+
+pub struct IngestionManager {
+    sender: mpsc::Sender<IngestionJob>,
+    metrics: Arc<IngestionMetrics>,
+    capacity: usize,
+    workers: usize,
+    enqueue_timeout: Duration,
+}
+
+impl IngestionManager {
+    pub fn new(
+        capacity: usize,
+        workers: usize,
+        enqueue_timeout: Duration,
+        context: IngestionContext,
+    ) -> Arc<Self> {
+        let (sender, receiver) = mpsc::channel(capacity);
+        let receiver = Arc::new(Mutex::new(receiver));
+        let metrics = Arc::new(IngestionMetrics::default());
+
+        // Start the worker pool
+        for _ in 0..workers {
+            let worker = IngestionWorker {
+                receiver: Arc::clone(&receiver),
+                metrics: Arc::clone(&metrics),
+                context: context.clone(),
+            };
+            tokio::spawn(worker.supervise());
+        }
+
+        Arc::new(IngestionManager {
+            sender,
+            metrics,
+            capacity,
+            workers,
+            enqueue_timeout,
+        })
+    }
+
+    /// Hands a job to the worker pool. When the queue stays full for longer than the
+    /// enqueue timeout the job is dropped; the errors themselves are already stored.
+    pub async fn enqueue(&self, job: IngestionJob) {
+        match self.sender.send_timeout(job, self.enqueue_timeout).await {
+            Ok(()) => {
+                self.metrics.enqueued.fetch_add(1, Ordering::Relaxed);
+            }
+            Err(err) => {
+                self.metrics.dropped.fetch_add(1, Ordering::Relaxed);
+                warn!("Dropped ingestion job, queue is saturated: {}", err);
+            }
+        }
+    }
+
+    pub fn metrics(&self) -> IngestionMetricsDTO {
+        IngestionMetricsDTO {
+            queue_depth: self.capacity - self.sender.capacity(),
+            queue_capacity: self.capacity,
+            workers: self.workers,
+            enqueued: self.metrics.enqueued.load(Ordering::Relaxed),
+            processed: self.metrics.processed.load(Ordering::Relaxed),
+            failed: self.metrics.failed.load(Ordering::Relaxed),
+            dropped: self.metrics.dropped.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[derive(Clone)]
+struct IngestionWorker {
+    receiver: Arc<Mutex<mpsc::Receiver<IngestionJob>>>,
+    metrics: Arc<IngestionMetrics>,
+    context: IngestionContext,
+}
+
+impl IngestionWorker {
+    /// Runs the worker and starts a fresh one whenever it panics, so that a single bad
+    /// job never shrinks the pool. Returns once the queue is closed.
+    async fn supervise(self) {
+        loop {
+            match tokio::spawn(self.clone().run()).await {
+                Ok(()) => break,
+                Err(err) if err.is_panic() => {
+                    self.metrics.failed.fetch_add(1, Ordering::Relaxed);
+                    error!("Ingestion worker panicked, restarting it: {}", err);
+                }
+                Err(_) => break,
+            }
+        }
+    }
+
+    async fn run(self) {
+        let context = &self.context;
+
+        loop {
+            // Only hold the lock while waiting for the next job so workers process in parallel
+            let job = self.receiver.lock().await.recv().await;
+            let Some(job) = job else {
+                break;
+            };
+
+            for created in job.created {
+                context.namespace_manager.broadcast_error(created).await;
+            }
+
+            let result = context
+                .error_service
+                .evaluate_alerts(
+                    &context.discord_handler,
+                    &job.namespace,
+                    &job.errors,
+                    &context.notification_manager,
+                    context.service_mapping,
+                    job.received_at,
+                )
+                .await;
+
+            match result {
+                Ok(()) => {
+                    self.metrics.processed.fetch_add(1, Ordering::Relaxed);
+                }
+                Err(err) => {
+                    self.metrics.failed.fetch_add(1, Ordering::Relaxed);
+                    error!(
+                        "Failed to evaluate alerts for namespace {}: {}",
+                        job.namespace.id, err
+                    );
+                }
+            }
+        }
+    }
+}
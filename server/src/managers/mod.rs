@@ -1,2 +1,4 @@
+pub mod alert_scheduler;
+pub mod ingestion_manager;
 pub mod namespace_manager;
 pub mod notification_manager;
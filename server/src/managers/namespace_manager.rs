@@ -18,6 +18,7 @@ impl NamespaceServer {
             message: String::new(),
             resolved: false,
             namespace_id: Uuid::new_v4(),
+            issue_id: None,
             user_affected: String::new(),
             stack_trace: String::new(),
         });
@@ -0,0 +1,121 @@
+use actix_service::Service;
+use actix_web::dev::{Payload, ServiceRequest, ServiceResponse, Transform};
+use actix_web::error::PayloadError;
+use actix_web::http::header::{HeaderValue, CONTENT_ENCODING, CONTENT_LENGTH};
+use actix_web::web::{self, Bytes, BytesMut};
+use actix_web::HttpMessage;
+use futures::future::{ok, Ready};
+use futures::{stream, Future, Stream, StreamExt};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use crate::shared::utils::decompress::{decompress, parse_content_encoding, ContentEncoding};
+use crate::shared::utils::errors::{ExternalError, RequestError, ServerError};
+
+/// Decodes compressed request bodies before they reach the extractors, enforcing
+/// `max_size` on the decompressed output. The body is handed on without its
+/// `Content-Encoding` so it isn't decoded a second time.
+#[derive(Clone)]
+pub struct DecompressMiddleware {
+    max_size: usize,
+}
+
+impl DecompressMiddleware {
+    pub fn new(max_size: usize) -> Self {
+        DecompressMiddleware { max_size }
+    }
+}
+
+impl<S, B, E> Transform<S, ServiceRequest> for DecompressMiddleware
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = E> + 'static,
+    S::Future: 'static,
+    B: 'static,
+    E: From<ServerError> + 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = E;
+    type InitError = ();
+    type Transform = DecompressMiddlewareService<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(DecompressMiddlewareService {
+            service: Arc::new(service),
+            max_size: self.max_size,
+        })
+    }
+}
+
+pub struct DecompressMiddlewareService<S> {
+    service: Arc<S>,
+    max_size: usize,
+}
+
+impl<S, B, E> Service<ServiceRequest> for DecompressMiddlewareService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = E> + 'static,
+    S::Future: 'static,
+    B: 'static,
+    E: From<ServerError> + 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+    type Error = E;
+
+    fn poll_ready(&self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&self, mut req: ServiceRequest) -> Self::Future {
+        let encoding = parse_content_encoding(
+            req.headers()
+                .get(CONTENT_ENCODING)
+                .and_then(|header| header.to_str().ok()),
+        );
+
+        let service = Arc::clone(&self.service);
+        let max_size = self.max_size;
+
+        Box::pin(async move {
+            let encoding = encoding.map_err(|err| E::from(ServerError::RequestError(err)))?;
+            if encoding == ContentEncoding::Identity {
+                return service.call(req).await;
+            }
+
+            // The compressed body is buffered under the same limit before decoding
+            let mut payload = req.take_payload();
+            let mut body = BytesMut::new();
+            while let Some(chunk) = payload.next().await {
+                let chunk = chunk.map_err(|err| {
+                    E::from(ServerError::ExternalError(ExternalError::Actix(err.into())))
+                })?;
+                if body.len() + chunk.len() > max_size {
+                    return Err(E::from(ServerError::RequestError(
+                        RequestError::PayloadTooLarge(max_size),
+                    )));
+                }
+                body.extend_from_slice(&chunk);
+            }
+
+            let decompressed = web::block(move || decompress(encoding, &body, max_size))
+                .await
+                .map_err(|err| {
+                    E::from(ServerError::ExternalError(ExternalError::Actix(err.into())))
+                })?
+                .map_err(|err| E::from(ServerError::RequestError(err)))?;
+
+            let headers = req.headers_mut();
+            headers.remove(CONTENT_ENCODING);
+            headers.insert(CONTENT_LENGTH, HeaderValue::from(decompressed.len()));
+
+            let decompressed = Bytes::from(decompressed);
+            let body_stream: Pin<Box<dyn Stream<Item = Result<Bytes, PayloadError>>>> =
+                Box::pin(stream::once(async move { Ok(decompressed) }));
+            req.set_payload(Payload::from(body_stream));
+
+            service.call(req).await
+        })
+    }
+}
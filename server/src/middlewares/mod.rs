@@ -1,3 +1,4 @@
 pub mod auth_middleware;
+pub mod decompress_middleware;
 pub mod rate_limit_middleware;
 pub mod sdk_auth_middleware;
@@ -0,0 +1,62 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::namespace_model::Entity as NamespaceEntity;
+use shared_types::artifact_dtos::ArtifactDTO;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "artifacts")]
+pub struct Model {
+    #[sea_orm(primary_key, column_type = "Uuid")]
+    pub id: Uuid,
+    pub namespace_id: Uuid,
+    pub release: String,
+    // File name as it appears in stack traces, e.g. `~/static/js/main.3f2a.js`
+    pub name: String,
+    pub storage_key: String,
+    pub size: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter)]
+pub enum Relation {
+    Namespace,
+}
+
+impl RelationTrait for Relation {
+    fn def(&self) -> RelationDef {
+        match self {
+            Self::Namespace => Entity::belongs_to(NamespaceEntity)
+                .from(Column::NamespaceId)
+                .to(<NamespaceEntity as EntityTrait>::Column::Id)
+                .into(),
+        }
+    }
+}
+
+impl Related<NamespaceEntity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Namespace.def()
+    }
+}
+
+#[async_trait]
+impl ActiveModelBehavior for ActiveModel {}
+
+impl From<Model> for ArtifactDTO {
+    fn from(model: Model) -> Self {
+        Self {
+            id: model.id,
+            namespace_id: model.namespace_id,
+            release: model.release,
+            name: model.name,
+            size: model.size,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::error_model::Entity as ErrorEntity;
+use crate::models::namespace_model::Entity as NamespaceEntity;
+use shared_types::attachment_dtos::AttachmentDTO;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "attachments")]
+pub struct Model {
+    #[sea_orm(primary_key, column_type = "Uuid")]
+    pub id: Uuid,
+    pub namespace_id: Uuid,
+    pub error_id: Uuid,
+    pub file_name: String,
+    pub content_type: String,
+    pub storage_key: String,
+    pub size: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter)]
+pub enum Relation {
+    Namespace,
+    Error,
+}
+
+impl RelationTrait for Relation {
+    fn def(&self) -> RelationDef {
+        match self {
+            Self::Namespace => Entity::belongs_to(NamespaceEntity)
+                .from(Column::NamespaceId)
+                .to(<NamespaceEntity as EntityTrait>::Column::Id)
+                .into(),
+            Self::Error => Entity::belongs_to(ErrorEntity)
+                .from(Column::ErrorId)
+                .to(<ErrorEntity as EntityTrait>::Column::Id)
+                .into(),
+        }
+    }
+}
+
+impl Related<NamespaceEntity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Namespace.def()
+    }
+}
+
+impl Related<ErrorEntity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Error.def()
+    }
+}
+
+#[async_trait]
+impl ActiveModelBehavior for ActiveModel {}
+
+impl From<Model> for AttachmentDTO {
+    fn from(model: Model) -> Self {
+        Self {
+            id: model.id,
+            namespace_id: model.namespace_id,
+            error_id: model.error_id,
+            file_name: model.file_name,
+            content_type: model.content_type,
+            size: model.size,
+            created_at: model.created_at,
+        }
+    }
+}
@@ -4,8 +4,9 @@ use sea_orm::{entity::prelude::*, ActiveValue};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::models::issue_model::Entity as IssueEntity;
 use crate::models::namespace_model::Entity as NamespaceEntity;
-use shared_types::error_dtos::ErrorDTO;
+use shared_types::error_dtos::{CreateErrorResponse, ErrorDTO};
 
 #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "errors")]
@@ -17,23 +18,45 @@ pub struct Model {
     pub line: i32,
     pub message: String,
     pub stack_trace: String,
+    #[sea_orm(column_type = "JsonBinary")]
+    pub frames: Json,
+    #[sea_orm(column_type = "JsonBinary")]
+    pub breadcrumbs: Json,
+    pub release: Option<String>,
+    pub environment: Option<String>,
+    // Client-generated, unique within the namespace
+    pub event_id: Option<String>,
+    // Names of the fields the scrubber redacted before the error was stored
+    #[sea_orm(column_type = "JsonBinary")]
+    pub redacted_fields: Json,
+    // Request, runtime, OS, device and extra data, see ErrorContextsDTO
+    #[sea_orm(column_type = "JsonBinary")]
+    pub contexts: Json,
+    // One of ErrorLevel's names, "error" unless the SDK said otherwise
+    pub level: String,
     pub resolved: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 
     #[sea_orm(belongs_to = NamespaceEntity, from = "Column::NamespaceId", to = NamespaceModel::Column::Id)]
     pub namespace_id: Uuid,
+    pub issue_id: Option<Uuid>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter)]
 pub enum Relation {
     NamespaceEntity,
+    Issue,
 }
 
 impl RelationTrait for Relation {
     fn def(&self) -> RelationDef {
         match self {
             Self::NamespaceEntity => Entity::belongs_to(NamespaceEntity).into(),
+            Self::Issue => Entity::belongs_to(IssueEntity)
+                .from(Column::IssueId)
+                .to(<IssueEntity as EntityTrait>::Column::Id)
+                .into(),
         }
     }
 }
@@ -44,16 +67,31 @@ impl Related<NamespaceEntity> for Entity {
     }
 }
 
+impl Related<IssueEntity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Issue.def()
+    }
+}
+
 impl ActiveModel {
     pub fn new(namespace_id: Uuid, namespace: Model) -> Self {
         Self {
             id: ActiveValue::Set(Uuid::new_v4()),
             namespace_id: ActiveValue::Set(namespace_id),
+            issue_id: ActiveValue::Set(namespace.issue_id),
             user_affected: ActiveValue::Set(namespace.user_affected),
             path: ActiveValue::Set(namespace.path),
             line: ActiveValue::Set(namespace.line),
             message: ActiveValue::Set(namespace.message),
             stack_trace: ActiveValue::Set(namespace.stack_trace),
+            frames: ActiveValue::Set(namespace.frames),
+            breadcrumbs: ActiveValue::Set(namespace.breadcrumbs),
+            release: ActiveValue::Set(namespace.release),
+            environment: ActiveValue::Set(namespace.environment),
+            event_id: ActiveValue::Set(namespace.event_id),
+            redacted_fields: ActiveValue::Set(namespace.redacted_fields),
+            contexts: ActiveValue::Set(namespace.contexts),
+            level: ActiveValue::Set(namespace.level),
             resolved: ActiveValue::Set(false),
             created_at: ActiveValue::Set(Utc::now()),
             updated_at: ActiveValue::Set(Utc::now()),
@@ -77,10 +115,29 @@ impl From<ErrorDTO> for Model {
             line: dto.line,
             message: dto.message,
             stack_trace: dto.stack_trace,
+            frames: serde_json::to_value(dto.frames).unwrap_or_default(),
+            breadcrumbs: serde_json::to_value(dto.breadcrumbs).unwrap_or_default(),
+            release: dto.release,
+            environment: dto.environment,
+            event_id: dto.event_id,
+            redacted_fields: serde_json::to_value(dto.redacted_fields).unwrap_or_default(),
+            contexts: serde_json::to_value(dto.contexts).unwrap_or_default(),
+            level: dto.level.as_str().to_string(),
             resolved: dto.resolved,
             created_at: dto.created_at,
             updated_at: dto.updated_at,
             namespace_id: dto.namespace_id,
+            issue_id: dto.issue_id,
+        }
+    }
+}
+
+impl From<Model> for CreateErrorResponse {
+    fn from(model: Model) -> Self {
+        Self {
+            id: model.id,
+            message: model.message,
+            namespace_id: model.namespace_id,
         }
     }
 }
@@ -0,0 +1,67 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::namespace_model::Entity as NamespaceEntity;
+use shared_types::inbound_filter_dtos::{InboundFilterDTO, InboundFilterType};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "inbound_filters")]
+pub struct Model {
+    #[sea_orm(primary_key, column_type = "Uuid")]
+    pub id: Uuid,
+    pub namespace_id: Uuid,
+    // One of the InboundFilterType names
+    pub filter_type: String,
+    pub tag_key: Option<String>,
+    pub pattern: String,
+    pub active: bool,
+    pub dropped_count: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter)]
+pub enum Relation {
+    Namespace,
+}
+
+impl RelationTrait for Relation {
+    fn def(&self) -> RelationDef {
+        match self {
+            Self::Namespace => Entity::belongs_to(NamespaceEntity)
+                .from(Column::NamespaceId)
+                .to(<NamespaceEntity as EntityTrait>::Column::Id)
+                .into(),
+        }
+    }
+}
+
+impl Related<NamespaceEntity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Namespace.def()
+    }
+}
+
+#[async_trait]
+impl ActiveModelBehavior for ActiveModel {}
+
+impl From<Model> for InboundFilterDTO {
+    fn from(model: Model) -> Self {
+        Self {
+            id: model.id,
+            namespace_id: model.namespace_id,
+            // Only ever written from an InboundFilterType
+            filter_type: InboundFilterType::parse(&model.filter_type)
+                .unwrap_or(InboundFilterType::Message),
+            tag_key: model.tag_key,
+            pattern: model.pattern,
+            active: model.active,
+            dropped_count: model.dropped_count,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::error_model::Entity as ErrorEntity;
+use crate::models::namespace_model::Entity as NamespaceEntity;
+use shared_types::issue_dtos::IssueDTO;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "issues")]
+pub struct Model {
+    #[sea_orm(primary_key, column_type = "Uuid")]
+    pub id: Uuid,
+    pub namespace_id: Uuid,
+    pub fingerprint: String,
+    pub error_type: String,
+    pub message: String,
+    pub path: String,
+    pub line: i32,
+    pub resolved: bool,
+    pub resolved_in_release: Option<String>,
+    pub regression: bool,
+    pub occurrences: i64,
+    pub users_affected: i32,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter)]
+pub enum Relation {
+    Namespace,
+    ErrorEntity,
+}
+
+impl RelationTrait for Relation {
+    fn def(&self) -> RelationDef {
+        match self {
+            Self::Namespace => Entity::belongs_to(NamespaceEntity)
+                .from(Column::NamespaceId)
+                .to(<NamespaceEntity as EntityTrait>::Column::Id)
+                .into(),
+            Self::ErrorEntity => Entity::has_many(ErrorEntity).into(),
+        }
+    }
+}
+
+impl Related<NamespaceEntity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Namespace.def()
+    }
+}
+
+impl Related<ErrorEntity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ErrorEntity.def()
+    }
+}
+
+#[async_trait]
+impl ActiveModelBehavior for ActiveModel {}
+
+impl From<Model> for IssueDTO {
+    fn from(model: Model) -> Self {
+        Self {
+            id: model.id,
+            namespace_id: model.namespace_id,
+            fingerprint: model.fingerprint,
+            error_type: model.error_type,
+            message: model.message,
+            path: model.path,
+            line: model.line,
+            resolved: model.resolved,
+            resolved_in_release: model.resolved_in_release,
+            regression: model.regression,
+            occurrences: model.occurrences,
+            users_affected: model.users_affected,
+            first_seen: model.first_seen,
+            last_seen: model.last_seen,
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use async_trait::async_trait;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::issue_model::Entity as IssueEntity;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "issue_users")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false, column_type = "Uuid")]
+    pub issue_id: Uuid,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub user_affected: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter)]
+pub enum Relation {
+    Issue,
+}
+
+impl RelationTrait for Relation {
+    fn def(&self) -> RelationDef {
+        match self {
+            Self::Issue => Entity::belongs_to(IssueEntity)
+                .from(Column::IssueId)
+                .to(<IssueEntity as EntityTrait>::Column::Id)
+                .into(),
+        }
+    }
+}
+
+impl Related<IssueEntity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Issue.def()
+    }
+}
+
+#[async_trait]
+impl ActiveModelBehavior for ActiveModel {}
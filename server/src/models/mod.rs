@@ -1,12 +1,20 @@
+pub mod artifact_model;
+pub mod attachment_model;
 pub mod bug_report_model;
 pub mod error_model;
 pub mod error_tag_model;
 pub mod feature_request_model;
+pub mod inbound_filter_model;
+pub mod issue_model;
+pub mod issue_user_model;
 pub mod namespace_alert_user_junction_model;
 pub mod namespace_alerts_model;
 pub mod namespace_model;
+pub mod namespace_usage_model;
 pub mod notification_model;
 pub mod refresh_token_model;
+pub mod release_model;
+pub mod scheduler_lease_model;
 pub mod user_model;
 pub mod user_namespace_junction_model;
 pub mod user_profile_model;
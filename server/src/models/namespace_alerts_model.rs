@@ -25,6 +25,23 @@ pub struct Model {
     pub unresolved_time_threshold: Option<i64>,
     pub rate_threshold: Option<i32>,
     pub rate_time_window: Option<i64>,
+    // Errors below this level never trigger the alert, None watches every level
+    pub min_level: Option<String>,
+    // An AlertConditionDTO tree, None falls back to the threshold columns
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub conditions: Option<Json>,
+    // Milliseconds after firing before the alert re-arms itself
+    pub cooldown: Option<i64>,
+    // An AlertConditionDTO tree, the alert re-arms once it stops firing
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub recovery_condition: Option<Json>,
+    pub notify_recovery: bool,
+    pub triggered_at: Option<DateTime<Utc>>,
+    pub webhook_url: Option<String>,
+    // Keys the HMAC signature sent with every webhook delivery
+    pub webhook_secret: Option<String>,
+    // Incoming webhook the slack method posts to
+    pub slack_webhook_url: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -69,6 +86,15 @@ impl ActiveModelBehavior for ActiveModel {
             unresolved_time_threshold: ActiveValue::Set(None),
             rate_threshold: ActiveValue::Set(None),
             rate_time_window: ActiveValue::Set(None),
+            min_level: ActiveValue::Set(None),
+            conditions: ActiveValue::Set(None),
+            cooldown: ActiveValue::Set(None),
+            recovery_condition: ActiveValue::Set(None),
+            notify_recovery: ActiveValue::Set(false),
+            triggered_at: ActiveValue::Set(None),
+            webhook_url: ActiveValue::Set(None),
+            webhook_secret: ActiveValue::Set(None),
+            slack_webhook_url: ActiveValue::Set(None),
             created_at: ActiveValue::Set(Utc::now()),
             updated_at: ActiveValue::Set(Utc::now()),
         }
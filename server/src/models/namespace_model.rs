@@ -7,7 +7,7 @@ use uuid::Uuid;
 use crate::models::error_model::Entity as ErrorEntity;
 use crate::models::user_namespace_junction_model::Entity as UserNamespaceJunctionEntity;
 
-#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "namespaces")]
 pub struct Model {
     #[sea_orm(primary_key, column_type = "Uuid")]
@@ -19,6 +19,17 @@ pub struct Model {
     pub client_secret: Uuid,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    // Ingestion limits, None means unlimited
+    pub quota_per_minute: Option<i32>,
+    pub quota_per_day: Option<i32>,
+    // Fraction of incoming events that are kept, between 0 and 1
+    pub sample_rate: f64,
+    // Custom regexes redacted from incoming errors, on top of the built-in detectors
+    #[sea_orm(column_type = "JsonBinary")]
+    pub scrub_patterns: Json,
+    // Tag keys whose values are never stored
+    #[sea_orm(column_type = "JsonBinary")]
+    pub scrub_tag_keys: Json,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter)]
@@ -63,6 +74,11 @@ impl ActiveModelBehavior for ActiveModel {
             client_secret: ActiveValue::Set(Uuid::new_v4()),
             created_at: ActiveValue::Set(Utc::now()),
             updated_at: ActiveValue::Set(Utc::now()),
+            quota_per_minute: ActiveValue::Set(None),
+            quota_per_day: ActiveValue::Set(None),
+            sample_rate: ActiveValue::Set(1.0),
+            scrub_patterns: ActiveValue::Set(Json::Array(vec![])),
+            scrub_tag_keys: ActiveValue::Set(Json::Array(vec![])),
         }
     }
 }
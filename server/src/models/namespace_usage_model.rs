@@ -0,0 +1,48 @@
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::namespace_model::Entity as NamespaceEntity;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "namespace_usage")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false, column_type = "Uuid")]
+    pub namespace_id: Uuid,
+    // Minutes since the Unix epoch of the window `minute_count` covers
+    pub minute: i64,
+    pub minute_count: i64,
+    // UTC day the other counters cover
+    pub day: Option<NaiveDate>,
+    pub day_count: i64,
+    pub dropped: i64,
+    pub sampled_out: i64,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter)]
+pub enum Relation {
+    Namespace,
+}
+
+impl RelationTrait for Relation {
+    fn def(&self) -> RelationDef {
+        match self {
+            Self::Namespace => Entity::belongs_to(NamespaceEntity)
+                .from(Column::NamespaceId)
+                .to(<NamespaceEntity as EntityTrait>::Column::Id)
+                .into(),
+        }
+    }
+}
+
+impl Related<NamespaceEntity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Namespace.def()
+    }
+}
+
+#[async_trait]
+impl ActiveModelBehavior for ActiveModel {}
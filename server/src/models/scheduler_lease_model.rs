@@ -0,0 +1,28 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "scheduler_leases")]
+pub struct Model {
+    // Names the background task, e.g. `alert_scheduler`
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub name: String,
+    // Server instance holding the lease
+    pub holder: Uuid,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter)]
+pub enum Relation {}
+
+impl RelationTrait for Relation {
+    fn def(&self) -> RelationDef {
+        unreachable!();
+    }
+}
+
+#[async_trait]
+impl ActiveModelBehavior for ActiveModel {}
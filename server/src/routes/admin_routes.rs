@@ -11,6 +11,10 @@ pub fn configure(cfg: &mut web::ServiceConfig, jwt_middleware: &JwtMiddleware) {
                 "/namespaces",
                 web::get().to(AdminHandler::get_all_namespaces),
             )
-            .route("/verify", web::post().to(AdminHandler::verify_admin)),
+            .route("/verify", web::post().to(AdminHandler::verify_admin))
+            .route(
+                "/ingestion",
+                web::get().to(AdminHandler::get_ingestion_metrics),
+            ),
     );
 }
@@ -0,0 +1,17 @@
+use actix_web::web;
+
+use crate::handlers::artifact_handlers::ArtifactHandler;
+use crate::middlewares::sdk_auth_middleware::ClientAuthMiddleware;
+use crate::services::artifact_services::MAX_ARTIFACT_SIZE;
+
+// Uploads come from build pipelines authenticated with the namespace's client credentials
+pub fn sdk_configure(cfg: &mut web::ServiceConfig, client_sdk_middleware: &ClientAuthMiddleware) {
+    cfg.service(
+        web::scope("/sdk/artifacts")
+            .wrap(client_sdk_middleware.clone())
+            .app_data(web::PayloadConfig::new(MAX_ARTIFACT_SIZE))
+            .route("/", web::post().to(ArtifactHandler::upload_artifact))
+            .route("/", web::get().to(ArtifactHandler::get_artifacts))
+            .route("/{id}", web::delete().to(ArtifactHandler::delete_artifact)),
+    );
+}
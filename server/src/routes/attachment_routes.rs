@@ -0,0 +1,15 @@
+use actix_web::web;
+
+use crate::handlers::attachment_handlers::AttachmentHandler;
+use crate::middlewares::sdk_auth_middleware::ClientAuthMiddleware;
+use crate::services::attachment_services::MAX_ATTACHMENT_SIZE;
+
+// SDKs upload files for an error they already reported, with the namespace's credentials
+pub fn sdk_configure(cfg: &mut web::ServiceConfig, client_sdk_middleware: &ClientAuthMiddleware) {
+    cfg.service(
+        web::scope("/sdk/attachments")
+            .wrap(client_sdk_middleware.clone())
+            .app_data(web::PayloadConfig::new(MAX_ATTACHMENT_SIZE))
+            .route("/", web::post().to(AttachmentHandler::upload_attachment)),
+    );
+}
@@ -1,13 +1,17 @@
 use actix_cors::Cors;
+use actix_web::error::JsonPayloadError;
 use actix_web::web;
 use std::time::Duration;
 use std::{sync::Arc, vec};
 
+use crate::handlers::attachment_handlers::AttachmentHandler;
 use crate::handlers::error_handlers::ErrorHandler;
 use crate::middlewares::{
-    auth_middleware::JwtMiddleware, rate_limit_middleware::RateLimiterMiddleware,
-    sdk_auth_middleware::ClientAuthMiddleware,
+    auth_middleware::JwtMiddleware, decompress_middleware::DecompressMiddleware,
+    rate_limit_middleware::RateLimiterMiddleware, sdk_auth_middleware::ClientAuthMiddleware,
 };
+use crate::shared::utils::decompress::MAX_DECOMPRESSED_SIZE;
+use crate::shared::utils::errors::{RequestError, ServerError};
 use crate::shared::utils::rate_limit::DynamicStripedRateLimiter;
 
 pub fn configure(cfg: &mut web::ServiceConfig, jwt_middleware: &JwtMiddleware) {
@@ -15,6 +19,14 @@ pub fn configure(cfg: &mut web::ServiceConfig, jwt_middleware: &JwtMiddleware) {
         web::scope("/api/error")
             .wrap(jwt_middleware.clone())
             .route("/{id}", web::get().to(ErrorHandler::get_error_by_id))
+            .route(
+                "/{id}/attachments",
+                web::get().to(AttachmentHandler::get_attachments),
+            )
+            .route(
+                "/{error_id}/attachments/{attachment_id}",
+                web::get().to(AttachmentHandler::download_attachment),
+            )
             .route("/", web::put().to(ErrorHandler::update_error))
             .route(
                 "/aggregate/namespace/{namespace_id}",
@@ -28,6 +40,10 @@ pub fn configure(cfg: &mut web::ServiceConfig, jwt_middleware: &JwtMiddleware) {
                 "/aggregate/details/namespace/{namespace_id}/pie",
                 web::get().to(ErrorHandler::get_error_metrics_pie_chart),
             )
+            .route(
+                "/aggregate/releases/namespace/{namespace_id}",
+                web::get().to(ErrorHandler::get_error_counts_by_release),
+            )
             .route(
                 "/unique/meta/namespace/{namespace_id}",
                 web::get().to(ErrorHandler::get_unique_error_meta_by_namespace),
@@ -39,9 +55,25 @@ pub fn sdk_configure(cfg: &mut web::ServiceConfig, client_sdk_middleware: &Clien
     let cors = Cors::default()
         .allow_any_origin()
         .allowed_methods(vec!["POST"])
-        .allowed_headers(vec!["Content-Type", "client_id", "client_secret"])
+        .allowed_headers(vec![
+            "Content-Type",
+            "Content-Encoding",
+            "client_id",
+            "client_secret",
+        ])
         .max_age(3600);
 
+    // Uncompressed bodies get the same limit as decompressed ones
+    let json_config = web::JsonConfig::default()
+        .limit(MAX_DECOMPRESSED_SIZE)
+        .error_handler(|err, _| match err {
+            JsonPayloadError::Overflow { .. } | JsonPayloadError::OverflowKnownLength { .. } => {
+                ServerError::RequestError(RequestError::PayloadTooLarge(MAX_DECOMPRESSED_SIZE))
+                    .into()
+            }
+            err => err.into(),
+        });
+
     // Adjust as we scale
     let stripe =
         DynamicStripedRateLimiter::new(8, Duration::from_secs(60), 60, Duration::from_secs(1800));
@@ -49,9 +81,12 @@ pub fn sdk_configure(cfg: &mut web::ServiceConfig, client_sdk_middleware: &Clien
 
     cfg.service(
         web::scope("/sdk/error")
+            .app_data(json_config)
+            .wrap(DecompressMiddleware::new(MAX_DECOMPRESSED_SIZE))
             .wrap(client_sdk_middleware.clone())
             .wrap(rate_limiter_middleware)
             .wrap(cors)
-            .route("/", web::post().to(ErrorHandler::create_error)),
+            .route("/", web::post().to(ErrorHandler::create_error))
+            .route("/batch", web::post().to(ErrorHandler::create_errors_batch)),
     );
 }
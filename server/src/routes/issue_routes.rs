@@ -0,0 +1,21 @@
+use actix_web::web;
+
+use crate::handlers::issue_handlers::IssueHandler;
+use crate::middlewares::auth_middleware::JwtMiddleware;
+
+pub fn configure(cfg: &mut web::ServiceConfig, jwt_middleware: &JwtMiddleware) {
+    cfg.service(
+        web::scope("/api/issue")
+            .wrap(jwt_middleware.clone())
+            .route("/{id}", web::get().to(IssueHandler::get_issue_by_id))
+            .route("/", web::put().to(IssueHandler::update_issue))
+            .route(
+                "/{id}/errors",
+                web::get().to(IssueHandler::get_errors_by_issue),
+            )
+            .route(
+                "/namespace/{namespace_id}",
+                web::get().to(IssueHandler::get_issues_by_namespace),
+            ),
+    );
+}
@@ -1,11 +1,16 @@
 pub mod admin_routes;
+pub mod artifact_routes;
+pub mod attachment_routes;
 pub mod auth_routes;
 pub mod bug_report_routes;
 pub mod error_routes;
 pub mod feature_request_routes;
+pub mod issue_routes;
 pub mod namespace_alert_routes;
 pub mod namespace_routes;
 pub mod notification_routes;
+pub mod otlp_routes;
+pub mod sentry_routes;
 pub mod static_routes;
 pub mod tag_routes;
 pub mod user_routes;
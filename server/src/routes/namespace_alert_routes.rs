@@ -38,6 +38,10 @@ pub fn configure(cfg: &mut web::ServiceConfig, jwt_middleware: &JwtMiddleware) {
             .route(
                 "/{id}/reset-trigger",
                 web::put().to(NamespaceAlertHandler::reset_trigger),
+            )
+            .route(
+                "/{id}/test-webhook",
+                web::post().to(NamespaceAlertHandler::test_webhook),
             ),
     );
 }
@@ -1,5 +1,6 @@
 use actix_web::web;
 
+use crate::handlers::inbound_filter_handlers::InboundFilterHandler;
 use crate::handlers::namespace_handlers::NamespaceHandler;
 use crate::middlewares::auth_middleware::JwtMiddleware;
 
@@ -44,6 +45,22 @@ pub fn configure(cfg: &mut web::ServiceConfig, jwt_middleware: &JwtMiddleware) {
             .route(
                 "/{id}/user-role",
                 web::put().to(NamespaceHandler::update_user_role_in_namespace),
+            )
+            .route(
+                "/{id}/filters",
+                web::get().to(InboundFilterHandler::get_filters),
+            )
+            .route(
+                "/{id}/filters",
+                web::post().to(InboundFilterHandler::create_filter),
+            )
+            .route(
+                "/{namespace_id}/filters/{filter_id}",
+                web::put().to(InboundFilterHandler::update_filter),
+            )
+            .route(
+                "/{namespace_id}/filters/{filter_id}",
+                web::delete().to(InboundFilterHandler::delete_filter),
             ),
     );
 }
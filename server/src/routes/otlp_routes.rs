@@ -0,0 +1,39 @@
+use actix_cors::Cors;
+use actix_web::web;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::handlers::otlp_handlers::OtlpHandler;
+use crate::middlewares::decompress_middleware::DecompressMiddleware;
+use crate::middlewares::rate_limit_middleware::RateLimiterMiddleware;
+use crate::shared::utils::decompress::MAX_DECOMPRESSED_SIZE;
+use crate::shared::utils::rate_limit::DynamicStripedRateLimiter;
+
+// Default OTLP/HTTP paths, so exporters only need the base URL and auth headers
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    let cors = Cors::default()
+        .allow_any_origin()
+        .allowed_methods(vec!["POST"])
+        .allowed_headers(vec![
+            "Content-Type",
+            "Content-Encoding",
+            "client_id",
+            "client_secret",
+        ])
+        .max_age(3600);
+
+    // Adjust as we scale
+    let stripe =
+        DynamicStripedRateLimiter::new(8, Duration::from_secs(60), 60, Duration::from_secs(1800));
+    let rate_limiter_middleware = RateLimiterMiddleware::new(Arc::clone(&stripe));
+
+    cfg.service(
+        web::scope("/v1")
+            .app_data(web::PayloadConfig::new(MAX_DECOMPRESSED_SIZE))
+            .wrap(DecompressMiddleware::new(MAX_DECOMPRESSED_SIZE))
+            .wrap(rate_limiter_middleware)
+            .wrap(cors)
+            .route("/logs", web::post().to(OtlpHandler::export_logs))
+            .route("/traces", web::post().to(OtlpHandler::export_traces)),
+    );
+}
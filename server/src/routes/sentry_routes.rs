@@ -0,0 +1,35 @@
+use actix_cors::Cors;
+use actix_web::web;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::handlers::sentry_handlers::SentryHandler;
+use crate::middlewares::decompress_middleware::DecompressMiddleware;
+use crate::middlewares::rate_limit_middleware::RateLimiterMiddleware;
+use crate::shared::utils::decompress::MAX_DECOMPRESSED_SIZE;
+use crate::shared::utils::rate_limit::DynamicStripedRateLimiter;
+
+// Sentry SDKs derive these paths from the DSN (`https://<client_id>@<host>/<project_id>`).
+// The project id only has to be numeric for the SDKs; the key identifies the namespace.
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    let cors = Cors::default()
+        .allow_any_origin()
+        .allowed_methods(vec!["POST"])
+        .allowed_headers(vec!["Content-Type", "Content-Encoding", "X-Sentry-Auth"])
+        .max_age(3600);
+
+    // Adjust as we scale
+    let stripe =
+        DynamicStripedRateLimiter::new(8, Duration::from_secs(60), 60, Duration::from_secs(1800));
+    let rate_limiter_middleware = RateLimiterMiddleware::new(Arc::clone(&stripe));
+
+    cfg.service(
+        web::scope("/api/{project_id}")
+            .app_data(web::PayloadConfig::new(MAX_DECOMPRESSED_SIZE))
+            .wrap(DecompressMiddleware::new(MAX_DECOMPRESSED_SIZE))
+            .wrap(rate_limiter_middleware)
+            .wrap(cors)
+            .route("/store/", web::post().to(SentryHandler::store))
+            .route("/envelope/", web::post().to(SentryHandler::envelope)),
+    );
+}
@@ -0,0 +1,350 @@
+use chrono::Utc;
+use log::warn;
+use sea_orm::{
+    entity::prelude::*, ActiveValue, DatabaseConnection, EntityTrait, IntoActiveModel, QueryOrder,
+};
+use sourcemap::DecodedMap;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::config::Config;
+use crate::libs::object_store::{LocalObjectStore, ObjectStore};
+use crate::models::artifact_model::{
+    Column as ArtifactColumn, Entity as ArtifactEntity, Model as ArtifactModel,
+};
+use crate::models::namespace_model::Entity as NamespaceEntity;
+use crate::shared::utils::errors::{ExternalError, QueryError, RequestError, ServerError};
+use crate::shared::utils::parse::StackTraceInfo;
+use crate::shared::utils::symbolicate::{
+    artifact_basename, is_minified_frame, normalize_artifact_name, symbolicate_frame,
+    SourceMapCache,
+};
+use shared_types::artifact_dtos::ArtifactDTO;
+
+// Largest source map accepted by the upload endpoint
+pub const MAX_ARTIFACT_SIZE: usize = 50 * 1024 * 1024;
+// Source map bytes kept decoded in memory across events
+const SOURCE_MAP_CACHE_SIZE: usize = 256 * 1024 * 1024;
+
+pub struct ArtifactService {
+    pub db: Arc<DatabaseConnection>,
+    pub configs: Arc<Config>,
+    pub store: Arc<dyn ObjectStore>,
+    source_maps: SourceMapCache,
+}
+
+impl ArtifactService {
+    pub fn new(db: Arc<DatabaseConnection>, configs: Arc<Config>) -> Result<Self, ServerError> {
+        let store = Arc::new(LocalObjectStore::new(&configs.artifact_storage_path));
+
+        Ok(Self {
+            db,
+            configs,
+            store,
+            source_maps: SourceMapCache::new(SOURCE_MAP_CACHE_SIZE),
+        })
+    }
+
+    /// Stores a source map for a release. Uploading the same name again for the same
+    /// release replaces the previous file.
+    pub async fn upload_artifact(
+        &self,
+        namespace_client_id: Uuid,
+        release: String,
+        name: String,
+        bytes: Vec<u8>,
+    ) -> Result<ArtifactDTO, ServerError> {
+        let db = &*self.db;
+        let now = Utc::now();
+
+        if release.trim().is_empty() || name.trim().is_empty() {
+            return Err(ServerError::RequestError(RequestError::InvalidArtifactName));
+        }
+
+        // Reject anything we couldn't use at ingest time
+        sourcemap::decode_slice(&bytes)
+            .map_err(|_| ServerError::RequestError(RequestError::InvalidSourceMap))?;
+
+        let namespace_id = self.find_namespace_id(namespace_client_id).await?;
+
+        let found_artifact = ArtifactEntity::find()
+            .filter(ArtifactColumn::NamespaceId.eq(namespace_id))
+            .filter(ArtifactColumn::Release.eq(&release))
+            .filter(ArtifactColumn::Name.eq(&name))
+            .one(db)
+            .await
+            .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
+
+        let artifact = match found_artifact {
+            Some(found_artifact) => {
+                self.put_object(&found_artifact.storage_key, &bytes).await?;
+                self.source_maps.invalidate(found_artifact.id);
+
+                let mut update_artifact = found_artifact.into_active_model();
+                update_artifact.size = ActiveValue::Set(bytes.len() as i64);
+                update_artifact.updated_at = ActiveValue::Set(now);
+
+                update_artifact
+                    .update(db)
+                    .await
+                    .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?
+            }
+            None => {
+                let id = Uuid::new_v4();
+                let storage_key = format!("sourcemaps/{}/{}", namespace_id, id);
+                self.put_object(&storage_key, &bytes).await?;
+
+                let new_artifact = ArtifactModel {
+                    id,
+                    namespace_id,
+                    release,
+                    name,
+                    storage_key,
+                    size: bytes.len() as i64,
+                    created_at: now,
+                    updated_at: now,
+                };
+
+                ArtifactEntity::insert(new_artifact.clone().into_active_model())
+                    .exec(db)
+                    .await
+                    .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
+
+                new_artifact
+            }
+        };
+
+        Ok(ArtifactDTO::from(artifact))
+    }
+
+    pub async fn get_artifacts(
+        &self,
+        namespace_client_id: Uuid,
+        release: String,
+    ) -> Result<Vec<ArtifactDTO>, ServerError> {
+        let namespace_id = self.find_namespace_id(namespace_client_id).await?;
+
+        let artifacts = ArtifactEntity::find()
+            .filter(ArtifactColumn::NamespaceId.eq(namespace_id))
+            .filter(ArtifactColumn::Release.eq(release))
+            .order_by_asc(ArtifactColumn::Name)
+            .all(&*self.db)
+            .await
+            .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
+
+        Ok(artifacts.into_iter().map(ArtifactDTO::from).collect())
+    }
+
+    pub async fn delete_artifact(
+        &self,
+        namespace_client_id: Uuid,
+        artifact_id: Uuid,
+    ) -> Result<(), ServerError> {
+        let db = &*self.db;
+        let namespace_id = self.find_namespace_id(namespace_client_id).await?;
+
+        let found_artifact = ArtifactEntity::find_by_id(artifact_id)
+            .filter(ArtifactColumn::NamespaceId.eq(namespace_id))
+            .one(db)
+            .await
+            .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?
+            .ok_or(ServerError::QueryError(QueryError::ArtifactNotFound))?;
+
+        let storage_key = found_artifact.storage_key.clone();
+        self.source_maps.invalidate(found_artifact.id);
+
+        found_artifact
+            .delete(db)
+            .await
+            .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
+
+        self.store
+            .delete(&storage_key)
+            .await
+            .map_err(|err| ServerError::ExternalError(ExternalError::Io(err)))
+    }
+
+    /// Maps minified JavaScript frames back to their original source using the source
+    /// maps uploaded for the release, then moves the error location to the rewritten
+    /// culprit frame. Frames without a matching source map are left as they are.
+    pub async fn symbolicate(
+        &self,
+        namespace_id: Uuid,
+        release: &str,
+        stack_trace_info: &mut StackTraceInfo,
+    ) -> Result<(), ServerError> {
+        if !stack_trace_info.frames.iter().any(is_minified_frame) {
+            return Ok(());
+        }
+
+        let artifacts = ArtifactEntity::find()
+            .filter(ArtifactColumn::NamespaceId.eq(namespace_id))
+            .filter(ArtifactColumn::Release.eq(release))
+            .all(&*self.db)
+            .await
+            .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
+
+        if artifacts.is_empty() {
+            return Ok(());
+        }
+
+        let culprit = stack_trace_info
+            .frames
+            .iter()
+            .position(|frame| {
+                frame.file_path == stack_trace_info.file_path
+                    && frame.line_number == stack_trace_info.line_number
+            })
+            .unwrap_or_default();
+
+        // Bundles usually appear in many frames, so each map is loaded at most once
+        let mut source_maps: HashMap<Uuid, Option<Arc<DecodedMap>>> = HashMap::new();
+
+        for frame in stack_trace_info.frames.iter_mut() {
+            if !is_minified_frame(frame) {
+                continue;
+            }
+
+            let Some(artifact) = Self::match_artifact(&artifacts, &frame.file_path) else {
+                continue;
+            };
+
+            if let Entry::Vacant(entry) = source_maps.entry(artifact.id) {
+                entry.insert(self.load_source_map(artifact).await);
+            }
+
+            if let Some(Some(source_map)) = source_maps.get(&artifact.id) {
+                symbolicate_frame(source_map, frame);
+            }
+        }
+
+        if let Some(frame) = stack_trace_info.frames.get(culprit) {
+            stack_trace_info.file_path = frame.file_path.clone();
+            stack_trace_info.line_number = frame.line_number;
+            stack_trace_info.function_name = frame.function_name.clone();
+            stack_trace_info.module_name = frame.module_name.clone();
+        }
+
+        Ok(())
+    }
+
+    // Prefer an artifact whose full path matches, then fall back to the file name
+    fn match_artifact<'a>(
+        artifacts: &'a [ArtifactModel],
+        file_path: &str,
+    ) -> Option<&'a ArtifactModel> {
+        let file_path = normalize_artifact_name(file_path);
+        let names: Vec<(String, &ArtifactModel)> = artifacts
+            .iter()
+            .map(|artifact| (normalize_artifact_name(&artifact.name), artifact))
+            .collect();
+
+        names
+            .iter()
+            .find(|(name, _)| *name == file_path)
+            .or_else(|| {
+                names
+                    .iter()
+                    .find(|(name, _)| artifact_basename(name) == artifact_basename(&file_path))
+            })
+            .map(|(_, artifact)| *artifact)
+    }
+
+    async fn load_source_map(&self, artifact: &ArtifactModel) -> Option<Arc<DecodedMap>> {
+        if let Some(source_map) = self.source_maps.get(artifact.id, artifact.updated_at) {
+            return Some(source_map);
+        }
+
+        let bytes = match self.store.get(&artifact.storage_key).await {
+            Ok(Some(bytes)) => bytes,
+            Ok(None) => {
+                warn!("Source map {} is missing from storage", artifact.id);
+                return None;
+            }
+            Err(err) => {
+                warn!("Failed to read source map {}: {}", artifact.id, err);
+                return None;
+            }
+        };
+
+        let source_map = sourcemap::decode_slice(&bytes)
+            .map_err(|err| warn!("Failed to decode source map {}: {}", artifact.id, err))
+            .ok()
+            .map(Arc::new)?;
+
+        self.source_maps.insert(
+            artifact.id,
+            artifact.updated_at,
+            bytes.len(),
+            source_map.clone(),
+        );
+
+        Some(source_map)
+    }
+
+    async fn put_object(&self, key: &str, bytes: &[u8]) -> Result<(), ServerError> {
+        self.store
+            .put(key, bytes)
+            .await
+            .map_err(|err| ServerError::ExternalError(ExternalError::Io(err)))
+    }
+
+    async fn find_namespace_id(&self, namespace_client_id: Uuid) -> Result<Uuid, ServerError> {
+        NamespaceEntity::find()
+            .filter(
+                <NamespaceEntity as sea_orm::EntityTrait>::Column::ClientId.eq(namespace_client_id),
+            )
+            .one(&*self.db)
+            .await
+            .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?
+            .map(|namespace| namespace.id)
+            .ok_or(ServerError::QueryError(QueryError::NamespaceNotFound))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn artifact(name: &str) -> ArtifactModel {
+        ArtifactModel {
+            id: Uuid::new_v4(),
+            namespace_id: Uuid::new_v4(),
+            release: "1.0.0".to_string(),
+            name: name.to_string(),
+            storage_key: String::new(),
+            size: 0,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn match_artifact_prefers_the_full_path() {
+        let artifacts = [
+            artifact("~/vendor/main.js.map"),
+            artifact("~/static/main.js.map"),
+        ];
+
+        let matched = ArtifactService::match_artifact(
+            &artifacts,
+            "https://cdn.example.com/static/main.js?v=2",
+        );
+        assert_eq!(matched.map(|found| found.id), Some(artifacts[1].id));
+    }
+
+    #[test]
+    fn match_artifact_falls_back_to_the_file_name() {
+        let artifacts = [artifact("vendor.js.map"), artifact("dist/main.js.map")];
+
+        let matched =
+            ArtifactService::match_artifact(&artifacts, "https://cdn.example.com/assets/main.js");
+        assert_eq!(matched.map(|found| found.id), Some(artifacts[1].id));
+
+        assert!(
+            ArtifactService::match_artifact(&artifacts, "https://cdn.example.com/app.js").is_none()
+        );
+    }
+}
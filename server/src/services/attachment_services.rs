@@ -0,0 +1,217 @@
+use chrono::Utc;
+use log::warn;
+use sea_orm::{
+    entity::prelude::*, DatabaseConnection, EntityTrait, IntoActiveModel, PaginatorTrait,
+    QueryOrder,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::config::Config;
+use crate::libs::object_store::{LocalObjectStore, ObjectStore};
+use crate::models::attachment_model::{
+    Column as AttachmentColumn, Entity as AttachmentEntity, Model as AttachmentModel,
+};
+use crate::models::error_model::Entity as ErrorEntity;
+use crate::models::namespace_model::Entity as NamespaceEntity;
+use crate::shared::utils::errors::{ExternalError, QueryError, RequestError, ServerError};
+use shared_types::attachment_dtos::AttachmentDTO;
+
+// Largest single file accepted by the upload endpoint
+pub const MAX_ATTACHMENT_SIZE: usize = 20 * 1024 * 1024;
+// Upper bound on the files attached to one error
+pub const MAX_ATTACHMENTS_PER_ERROR: u64 = 20;
+const MAX_FILE_NAME_LENGTH: usize = 255;
+const DEFAULT_CONTENT_TYPE: &str = "application/octet-stream";
+
+// Nothing a browser would render as a page, downloads are also sent with nosniff
+const ALLOWED_CONTENT_TYPES: [&str; 14] = [
+    "text/plain",
+    "text/csv",
+    "application/json",
+    "application/x-ndjson",
+    "application/octet-stream",
+    "application/x-dmp",
+    "application/x-minidump",
+    "application/gzip",
+    "application/zip",
+    "application/x-tar",
+    "image/png",
+    "image/jpeg",
+    "image/gif",
+    "image/webp",
+];
+
+pub struct AttachmentService {
+    pub db: Arc<DatabaseConnection>,
+    pub configs: Arc<Config>,
+    pub store: Arc<dyn ObjectStore>,
+}
+
+impl AttachmentService {
+    pub fn new(db: Arc<DatabaseConnection>, configs: Arc<Config>) -> Result<Self, ServerError> {
+        let store = Arc::new(LocalObjectStore::new(&configs.attachment_storage_path));
+
+        Ok(Self { db, configs, store })
+    }
+
+    /// Stores a file for an error of the namespace the SDK credentials belong to.
+    /// Without a Content-Type the file is kept as `application/octet-stream`.
+    pub async fn upload_attachment(
+        &self,
+        namespace_client_id: Uuid,
+        error_id: Uuid,
+        file_name: String,
+        content_type: Option<&str>,
+        bytes: Vec<u8>,
+    ) -> Result<AttachmentDTO, ServerError> {
+        let db = &*self.db;
+
+        if bytes.len() > MAX_ATTACHMENT_SIZE {
+            return Err(ServerError::RequestError(RequestError::PayloadTooLarge(
+                MAX_ATTACHMENT_SIZE,
+            )));
+        }
+        let file_name = validate_file_name(&file_name).map_err(ServerError::RequestError)?;
+        let content_type =
+            normalize_content_type(content_type).map_err(ServerError::RequestError)?;
+
+        let namespace_id = self.find_namespace_id(namespace_client_id).await?;
+
+        // Only errors of the uploading namespace can receive files
+        ErrorEntity::find_by_id(error_id)
+            .filter(<ErrorEntity as EntityTrait>::Column::NamespaceId.eq(namespace_id))
+            .one(db)
+            .await
+            .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?
+            .ok_or(ServerError::QueryError(QueryError::ErrorNotFound))?;
+
+        let attachment_count = AttachmentEntity::find()
+            .filter(AttachmentColumn::ErrorId.eq(error_id))
+            .count(db)
+            .await
+            .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
+        if attachment_count >= MAX_ATTACHMENTS_PER_ERROR {
+            return Err(ServerError::RequestError(
+                RequestError::AttachmentLimitReached,
+            ));
+        }
+
+        let id = Uuid::new_v4();
+        let storage_key = format!("attachments/{}/{}/{}", namespace_id, error_id, id);
+        self.store
+            .put(&storage_key, &bytes)
+            .await
+            .map_err(|err| ServerError::ExternalError(ExternalError::Io(err)))?;
+
+        let new_attachment = AttachmentModel {
+            id,
+            namespace_id,
+            error_id,
+            file_name,
+            content_type,
+            storage_key,
+            size: bytes.len() as i64,
+            created_at: Utc::now(),
+        };
+
+        if let Err(err) = AttachmentEntity::insert(new_attachment.clone().into_active_model())
+            .exec(db)
+            .await
+        {
+            // Don't leave a file behind that no row points to
+            if let Err(err) = self.store.delete(&new_attachment.storage_key).await {
+                warn!("Failed to remove orphaned attachment {}: {}", id, err);
+            }
+            return Err(ServerError::ExternalError(ExternalError::DB(err)));
+        }
+
+        Ok(AttachmentDTO::from(new_attachment))
+    }
+
+    pub async fn get_attachments(&self, error_id: Uuid) -> Result<Vec<AttachmentDTO>, ServerError> {
+        let attachments = AttachmentEntity::find()
+            .filter(AttachmentColumn::ErrorId.eq(error_id))
+            .order_by_asc(AttachmentColumn::CreatedAt)
+            .all(&*self.db)
+            .await
+            .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
+
+        Ok(attachments.into_iter().map(AttachmentDTO::from).collect())
+    }
+
+    /// Returns the attachment together with its contents.
+    pub async fn get_attachment(
+        &self,
+        error_id: Uuid,
+        attachment_id: Uuid,
+    ) -> Result<(AttachmentDTO, Vec<u8>), ServerError> {
+        let found_attachment = AttachmentEntity::find_by_id(attachment_id)
+            .filter(AttachmentColumn::ErrorId.eq(error_id))
+            .one(&*self.db)
+            .await
+            .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?
+            .ok_or(ServerError::QueryError(QueryError::AttachmentNotFound))?;
+
+        let bytes = self
+            .store
+            .get(&found_attachment.storage_key)
+            .await
+            .map_err(|err| ServerError::ExternalError(ExternalError::Io(err)))?
+            .ok_or(ServerError::QueryError(QueryError::AttachmentNotFound))?;
+
+        Ok((AttachmentDTO::from(found_attachment), bytes))
+    }
+
+    /// The namespace whose members may read the error's attachments.
+    pub async fn error_namespace_id(&self, error_id: Uuid) -> Result<Uuid, ServerError> {
+        ErrorEntity::find_by_id(error_id)
+            .one(&*self.db)
+            .await
+            .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?
+            .map(|error| error.namespace_id)
+            .ok_or(ServerError::QueryError(QueryError::ErrorNotFound))
+    }
+
+    async fn find_namespace_id(&self, namespace_client_id: Uuid) -> Result<Uuid, ServerError> {
+        NamespaceEntity::find()
+            .filter(
+                <NamespaceEntity as sea_orm::EntityTrait>::Column::ClientId.eq(namespace_client_id),
+            )
+            .one(&*self.db)
+            .await
+            .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?
+            .map(|namespace| namespace.id)
+            .ok_or(ServerError::QueryError(QueryError::NamespaceNotFound))
+    }
+}
+
+// The name ends up in a Content-Disposition header, so paths and control characters
+// are rejected rather than stripped
+fn validate_file_name(file_name: &str) -> Result<String, RequestError> {
+    let file_name = file_name.trim();
+    if file_name.is_empty()
+        || file_name.chars().count() > MAX_FILE_NAME_LENGTH
+        || file_name
+            .chars()
+            .any(|c| c == '/' || c == '\\' || c.is_control())
+    {
+        return Err(RequestError::InvalidAttachmentName);
+    }
+
+    Ok(file_name.to_string())
+}
+
+// Drops parameters such as `; charset=utf-8` before checking the type
+fn normalize_content_type(content_type: Option<&str>) -> Result<String, RequestError> {
+    let content_type = content_type
+        .and_then(|content_type| content_type.split(';').next())
+        .map(|content_type| content_type.trim().to_lowercase())
+        .filter(|content_type| !content_type.is_empty())
+        .unwrap_or_else(|| DEFAULT_CONTENT_TYPE.to_string());
+
+    match ALLOWED_CONTENT_TYPES.contains(&content_type.as_str()) {
+        true => Ok(content_type),
+        false => Err(RequestError::UnsupportedAttachmentType),
+    }
+}
@@ -1,1087 +1,795 @@
 use actix_web::Result;
 use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
 use chrono_tz::Tz;
-use sea_orm::sea_query::Query;
-use sea_orm::ActiveValue::NotSet;
-use sea_orm::Set;
+use log::{error, warn};
+use sea_orm::error::SqlErr;
+use sea_orm::sea_query::{Expr, OnConflict, Query};
 use sea_orm::{
-    entity::prelude::*, Condition, DatabaseConnection, EntityTrait, IntoActiveModel, JoinType,
-    QueryOrder, QuerySelect,
+    entity::prelude::*, Condition, ConnectionTrait, DatabaseConnection, EntityTrait,
+    IntoActiveModel, JoinType, QueryOrder, QuerySelect, TransactionTrait, TryInsertResult,
 };
-use shared_types::notification_dtos::NotificationDTO;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::config::Config;
+use crate::managers::ingestion_manager::IngestionJob;
 use crate::managers::notification_manager::NotificationServer;
 use crate::models::error_model::{Entity as ErrorEntity, Model as ErrorModel};
 use crate::models::error_tag_model::{
     ActiveModel as ActiveTagModel, Entity as TagEntity, Model as TagModel,
 };
-use crate::models::namespace_alert_user_junction_model::Entity as NamespaceAlertUserJunctionEntity;
+use crate::models::issue_model::{
+    Column as IssueColumn, Entity as IssueEntity, Model as IssueModel,
+};
+use crate::models::issue_user_model::{
+    Column as IssueUserColumn, Entity as IssueUserEntity, Model as IssueUserModel,
+};
 use crate::models::namespace_alerts_model::{
-    ActiveModel as NamespaceAlertActiveModel, Entity as NamespaceAlertEntity,
+    Entity as NamespaceAlertEntity, Model as NamespaceAlertModel,
+};
+use crate::models::namespace_model::{Entity as NamespaceEntity, Model as NamespaceModel};
+use crate::models::release_model::{
+    Column as ReleaseColumn, Entity as ReleaseEntity, Model as ReleaseModel,
 };
-use crate::models::namespace_model::Entity as NamespaceEntity;
-use crate::models::notification_model::{Entity as NotificationEntity, Model as NotificationModel};
-use crate::models::user_model::Entity as UserEntity;
-use crate::models::user_profile_model::Entity as UserProfileEntity;
+use crate::services::artifact_services::ArtifactService;
+use crate::services::inbound_filter_services::InboundFilterService;
+use crate::shared::utils::alerting::delivery::AlertDelivery;
+use crate::shared::utils::alerting::{AlertRule, ConditionContext};
+use crate::shared::utils::breadcrumbs::limit_breadcrumbs;
 use crate::shared::utils::discord::DiscordHandler;
 use crate::shared::utils::errors::{ExternalError, QueryError, RequestError, ServerError};
-use crate::shared::utils::mailing::{send_email, send_email_sms, EmailContent};
-use crate::shared::utils::parse::{parse_stack_trace, StackTraceInfo};
+use crate::shared::utils::fingerprint::compute_fingerprint;
+use crate::shared::utils::inbound_filter::InboundFilter;
+use crate::shared::utils::parse::{parse_stack_trace, stack_trace_from_frames, StackTraceInfo};
+use crate::shared::utils::quota::{QuotaDecision, QuotaLimits, QuotaTracker};
+use crate::shared::utils::scrub::{compile_rules, Scrubber};
 use shared_types::error_dtos::{
-    AggregateErrorDTO, CreateErrorDTO, CreateErrorRequest, ErrorDTO, ErrorMetaDTO,
-    GroupedAggregateErrorCountDTO, UpdateErrorDTO,
+    AggregateErrorDTO, BatchCreateErrorResultDTO, BatchItemResultDTO, BatchItemStatus,
+    CreateErrorDTO, CreateErrorRequest, CreateErrorResponse, ErrorDTO, ErrorLevel, ErrorMetaDTO,
+    GroupedAggregateErrorCountDTO, ReleaseErrorCountDTO, StackFrameDTO, UpdateErrorDTO,
 };
 use shared_types::tag_dtos::{CreateTagRequestDTO, ShortTagDTO, TagDTO};
 
+// Upper bound on how many errors a single batch request may carry
+pub const MAX_BATCH_SIZE: usize = 500;
+// Matches the width of the errors.event_id column
+pub const MAX_EVENT_ID_LENGTH: usize = 64;
+
+/// An incoming error that has been scrubbed of personal data and whose stack trace has
+/// already been parsed.
+#[derive(Clone)]
+pub struct PreparedError {
+    pub request: CreateErrorRequest,
+    pub stack_trace_info: StackTraceInfo,
+    pub redacted_fields: Vec<String>,
+}
+
 pub struct ErrorService {
     pub db: Arc<DatabaseConnection>,
     pub configs: Arc<Config>,
+    pub artifact_service: Arc<ArtifactService>,
+    pub inbound_filter_service: Arc<InboundFilterService>,
+    pub quota_tracker: Arc<QuotaTracker>,
 }
 
 impl ErrorService {
-    pub fn new(db: Arc<DatabaseConnection>, configs: Arc<Config>) -> Result<Self, ServerError> {
-        Ok(Self { db, configs })
+    pub fn new(
+        db: Arc<DatabaseConnection>,
+        configs: Arc<Config>,
+        artifact_service: Arc<ArtifactService>,
+        inbound_filter_service: Arc<InboundFilterService>,
+        quota_tracker: Arc<QuotaTracker>,
+    ) -> Result<Self, ServerError> {
+        Ok(Self {
+            db,
+            configs,
+            artifact_service,
+            inbound_filter_service,
+            quota_tracker,
+        })
     }
 
+    /// Persists a single error and returns the stored error along with the follow-up
+    /// work (alerts and live push) for the ingestion workers. An error dropped by an
+    /// inbound filter or the sample rate yields no error and a job with nothing created,
+    /// an error resent with a known event ID yields the original error.
     pub async fn create_error(
         &self,
-        discord_handler: &DiscordHandler,
         error: CreateErrorRequest,
         namespace_client_id: Uuid,
-        notification_manager: &Arc<NotificationServer>,
-        service_mapping: &HashMap<&'static str, &'static str>,
-    ) -> Result<CreateErrorDTO, ServerError> {
+    ) -> Result<(Option<CreateErrorResponse>, IngestionJob), ServerError> {
         let now = Utc::now();
-        let configs = &*self.configs;
-        let db = &*self.db;
+        let found_namespace = self
+            .find_namespace_by_client_id(namespace_client_id)
+            .await?;
+
+        let filters = self
+            .inbound_filter_service
+            .active_filters(found_namespace.id)
+            .await?;
+        if let Some(filter) =
+            Self::matching_filter(&filters, &error).map_err(ServerError::RequestError)?
+        {
+            self.record_filtered(HashMap::from([(filter.id, 1)])).await;
+            return Ok((None, IngestionJob::empty(found_namespace, now)));
+        }
+
+        let scrubber = self.scrubber_for(&found_namespace);
+        let mut prepared =
+            Self::prepare_error(error, &scrubber).map_err(ServerError::RequestError)?;
+
+        if let Some(original) = self
+            .find_error_by_event_id(found_namespace.id, &prepared.request.event_id)
+            .await?
+        {
+            return Ok((
+                Some(CreateErrorResponse::from(original)),
+                IngestionJob::empty(found_namespace, now),
+            ));
+        }
+
+        match self.check_quota(&found_namespace, 1, now).await?[0] {
+            QuotaDecision::Accept => {}
+            QuotaDecision::Sampled => {
+                return Ok((None, IngestionJob::empty(found_namespace, now)));
+            }
+            QuotaDecision::RateLimited { retry_after } => {
+                return Err(ServerError::RequestError(RequestError::QuotaExceeded(
+                    retry_after,
+                )))
+            }
+        }
+
+        self.symbolicate(found_namespace.id, &mut prepared).await;
+
+        let transaction = self
+            .db
+            .begin()
+            .await
+            .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
+
+        let created_error = match self
+            .persist_error(&transaction, found_namespace.id, prepared.clone(), now)
+            .await
+        {
+            Ok(created_error) => created_error,
+            // A retry of the same event was stored while this one was being processed
+            Err(err) if is_unique_violation(&err) => {
+                transaction
+                    .rollback()
+                    .await
+                    .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
+
+                let original = self
+                    .find_error_by_event_id(found_namespace.id, &prepared.request.event_id)
+                    .await?
+                    .ok_or(err)?;
+
+                return Ok((
+                    Some(CreateErrorResponse::from(original)),
+                    IngestionJob::empty(found_namespace, now),
+                ));
+            }
+            Err(err) => return Err(err),
+        };
+
+        transaction
+            .commit()
+            .await
+            .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
+
+        let response = CreateErrorResponse {
+            id: created_error.id,
+            message: created_error.message.clone(),
+            namespace_id: created_error.namespace_id,
+        };
+
+        let job = IngestionJob {
+            namespace: found_namespace,
+            errors: vec![prepared],
+            created: vec![created_error],
+            received_at: now,
+        };
+
+        Ok((Some(response), job))
+    }
+
+    /// Ingests several errors at once. Every accepted error is inserted in a single
+    /// transaction and the returned job evaluates alerts once for the whole batch.
+    /// Errors matching an inbound filter or over the namespace quota are dropped, and the
+    /// whole request is refused once the quota leaves none of them to store.
+    pub async fn create_errors_batch(
+        &self,
+        errors: Vec<CreateErrorRequest>,
+        namespace_client_id: Uuid,
+    ) -> Result<(BatchCreateErrorResultDTO, IngestionJob), ServerError> {
+        if errors.is_empty() || errors.len() > MAX_BATCH_SIZE {
+            return Err(ServerError::RequestError(RequestError::InvalidBatchSize));
+        }
+
+        let now = Utc::now();
+        let found_namespace = self
+            .find_namespace_by_client_id(namespace_client_id)
+            .await?;
+        let filters = self
+            .inbound_filter_service
+            .active_filters(found_namespace.id)
+            .await?;
+        let scrubber = self.scrubber_for(&found_namespace);
+
+        let mut results: Vec<BatchItemResultDTO> = Vec::with_capacity(errors.len());
+        let mut accepted: Vec<(usize, PreparedError)> = Vec::new();
+        let mut filtered: HashMap<Uuid, i64> = HashMap::new();
+
+        for (index, error) in errors.into_iter().enumerate() {
+            match Self::matching_filter(&filters, &error) {
+                Ok(None) => {}
+                Ok(Some(filter)) => {
+                    *filtered.entry(filter.id).or_default() += 1;
+                    results.push(BatchItemResultDTO {
+                        index,
+                        status: BatchItemStatus::Dropped,
+                        id: None,
+                        reason: Some("Dropped by an inbound filter".to_string()),
+                    });
+                    continue;
+                }
+                Err(err) => {
+                    results.push(BatchItemResultDTO {
+                        index,
+                        status: BatchItemStatus::Rejected,
+                        id: None,
+                        reason: Some(err.to_string()),
+                    });
+                    continue;
+                }
+            }
+
+            match Self::prepare_error(error, &scrubber) {
+                Ok(prepared) => accepted.push((index, prepared)),
+                Err(err) => results.push(BatchItemResultDTO {
+                    index,
+                    status: BatchItemStatus::Rejected,
+                    id: None,
+                    reason: Some(err.to_string()),
+                }),
+            }
+        }
+        self.record_filtered(filtered).await;
+
+        let event_ids: Vec<String> = accepted
+            .iter()
+            .filter_map(|(_, prepared)| prepared.request.event_id.clone())
+            .collect();
+        let stored = self
+            .find_errors_by_event_ids(found_namespace.id, event_ids)
+            .await?;
+
+        let mut seen_event_ids: HashSet<String> = HashSet::new();
+        for (index, prepared) in std::mem::take(&mut accepted) {
+            let Some(event_id) = prepared.request.event_id.clone() else {
+                accepted.push((index, prepared));
+                continue;
+            };
+
+            let original = stored.get(&event_id);
+            if original.is_none() && seen_event_ids.insert(event_id) {
+                accepted.push((index, prepared));
+                continue;
+            }
+
+            results.push(BatchItemResultDTO {
+                index,
+                status: BatchItemStatus::Duplicate,
+                id: original.map(|error| error.id),
+                reason: Some(QueryError::EventAlreadyReceived.to_string()),
+            });
+        }
+
+        let mut retry_after: Option<u64> = None;
+        let decisions = match accepted.is_empty() {
+            true => vec![],
+            false => self.check_quota(&found_namespace, accepted.len(), now).await?,
+        };
+        let pending = std::mem::take(&mut accepted);
+        for ((index, prepared), decision) in pending.into_iter().zip(decisions) {
+            let reason = match decision {
+                QuotaDecision::Accept => {
+                    accepted.push((index, prepared));
+                    continue;
+                }
+                QuotaDecision::Sampled => "Sampled out by the namespace sample rate".to_string(),
+                QuotaDecision::RateLimited {
+                    retry_after: seconds,
+                } => {
+                    retry_after = Some(seconds);
+                    RequestError::QuotaExceeded(seconds).to_string()
+                }
+            };
 
-        let mut stack_trace_info: StackTraceInfo = StackTraceInfo::default();
-        let error_stack_trace = error.stack_trace.clone();
+            results.push(BatchItemResultDTO {
+                index,
+                status: BatchItemStatus::Dropped,
+                id: None,
+                reason: Some(reason),
+            });
+        }
 
-        match parse_stack_trace(&error_stack_trace) {
-            Ok(info) => stack_trace_info = info,
-            Err(_) => {
-                return Err(ServerError::RequestError(
-                    RequestError::StackTraceParsingError,
-                ))
+        if accepted.is_empty() {
+            if let Some(seconds) = retry_after {
+                return Err(ServerError::RequestError(RequestError::QuotaExceeded(
+                    seconds,
+                )));
             }
         }
 
-        // Find the namespace for this error
-        let found_namespace = NamespaceEntity::find()
+        for (_, prepared) in accepted.iter_mut() {
+            self.symbolicate(found_namespace.id, prepared).await;
+        }
+
+        let transaction = self
+            .db
+            .begin()
+            .await
+            .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
+
+        let mut created: Vec<CreateErrorDTO> = Vec::with_capacity(accepted.len());
+        let mut persisted: Vec<PreparedError> = Vec::with_capacity(accepted.len());
+        for (index, prepared) in accepted {
+            // A savepoint per error, so that losing a race only undoes that error
+            let savepoint = transaction
+                .begin()
+                .await
+                .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
+
+            let created_error = match self
+                .persist_error(&savepoint, found_namespace.id, prepared.clone(), now)
+                .await
+            {
+                Ok(created_error) => created_error,
+                // A concurrent retry stored the same event while this batch was processed
+                Err(err) if is_unique_violation(&err) && prepared.request.event_id.is_some() => {
+                    savepoint
+                        .rollback()
+                        .await
+                        .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
+
+                    let Some(original) = self
+                        .find_error_by_event_id(found_namespace.id, &prepared.request.event_id)
+                        .await?
+                    else {
+                        return Err(err);
+                    };
+                    results.push(BatchItemResultDTO {
+                        index,
+                        status: BatchItemStatus::Duplicate,
+                        id: Some(original.id),
+                        reason: Some(QueryError::EventAlreadyReceived.to_string()),
+                    });
+                    continue;
+                }
+                Err(err) => return Err(err),
+            };
+
+            savepoint
+                .commit()
+                .await
+                .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
+
+            results.push(BatchItemResultDTO {
+                index,
+                status: BatchItemStatus::Accepted,
+                id: Some(created_error.id),
+                reason: None,
+            });
+            created.push(created_error);
+            persisted.push(prepared);
+        }
+
+        transaction
+            .commit()
+            .await
+            .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
+
+        results.sort_by_key(|result| result.index);
+
+        let count = |status: BatchItemStatus| {
+            results
+                .iter()
+                .filter(|result| result.status == status)
+                .count()
+        };
+        let summary = BatchCreateErrorResultDTO {
+            accepted: count(BatchItemStatus::Accepted),
+            rejected: count(BatchItemStatus::Rejected),
+            duplicate: count(BatchItemStatus::Duplicate),
+            dropped: count(BatchItemStatus::Dropped),
+            results,
+        };
+
+        let job = IngestionJob {
+            namespace: found_namespace,
+            errors: persisted,
+            created,
+            received_at: now,
+        };
+
+        Ok((summary, job))
+    }
+
+    // Scrubbing runs before parsing so that nothing derived from the stack trace, such
+    // as the issue fingerprint, still holds the redacted values
+    fn prepare_error(
+        mut error: CreateErrorRequest,
+        scrubber: &Scrubber,
+    ) -> Result<PreparedError, RequestError> {
+        if let Some(event_id) = &error.event_id {
+            if event_id.is_empty() || event_id.chars().count() > MAX_EVENT_ID_LENGTH {
+                return Err(RequestError::InvalidEventId);
+            }
+        }
+
+        let redacted_fields = scrubber.scrub_request(&mut error);
+
+        if let Some(frames) = error.frames.clone() {
+            return Ok(PreparedError {
+                stack_trace_info: stack_trace_from_frames(&error.stack_trace, frames),
+                request: error,
+                redacted_fields,
+            });
+        }
+
+        match parse_stack_trace(&error.stack_trace, error.platform.as_deref()) {
+            Ok(stack_trace_info) => Ok(PreparedError {
+                request: error,
+                stack_trace_info,
+                redacted_fields,
+            }),
+            Err(_) => Err(RequestError::StackTraceParsingError),
+        }
+    }
+
+    // Filters see the error as the SDK sent it, scrubbing could redact the very values
+    // they match on. The stack trace is only parsed here when a path filter needs it.
+    fn matching_filter<'a>(
+        filters: &'a [InboundFilter],
+        error: &CreateErrorRequest,
+    ) -> Result<Option<&'a InboundFilter>, RequestError> {
+        if filters.is_empty() {
+            return Ok(None);
+        }
+
+        let path = match filters.iter().any(InboundFilter::matches_path) {
+            true => Self::top_frame_path(error)?,
+            false => String::new(),
+        };
+
+        Ok(filters.iter().find(|filter| filter.matches(error, &path)))
+    }
+
+    fn top_frame_path(error: &CreateErrorRequest) -> Result<String, RequestError> {
+        if let Some(frames) = error.frames.clone() {
+            return Ok(stack_trace_from_frames(&error.stack_trace, frames).file_path);
+        }
+
+        parse_stack_trace(&error.stack_trace, error.platform.as_deref())
+            .map(|stack_trace_info| stack_trace_info.file_path)
+            .map_err(|_| RequestError::StackTraceParsingError)
+    }
+
+    // Counting is best effort, a failure never turns a dropped error into a failed request
+    async fn record_filtered(&self, filtered: HashMap<Uuid, i64>) {
+        if filtered.is_empty() {
+            return;
+        }
+
+        if let Err(err) = self.inbound_filter_service.record_dropped(filtered).await {
+            warn!("Failed to count errors dropped by inbound filters: {}", err);
+        }
+    }
+
+    fn scrubber_for(&self, namespace: &NamespaceModel) -> Scrubber {
+        let patterns: Vec<String> =
+            serde_json::from_value(namespace.scrub_patterns.clone()).unwrap_or_default();
+        let tag_keys: Vec<String> =
+            serde_json::from_value(namespace.scrub_tag_keys.clone()).unwrap_or_default();
+
+        // Rules are validated when saved, a bad one only disables the custom rules
+        let rules = compile_rules(&patterns).unwrap_or_else(|err| {
+            warn!(
+                "Ignoring scrubbing rules of namespace {}: {}",
+                namespace.id, err
+            );
+            vec![]
+        });
+
+        Scrubber::new(rules, &tag_keys, self.configs.pseudonym_key.as_bytes())
+    }
+
+    /// Resolves minified JavaScript frames through the release's source maps so that
+    /// grouping, alerts and the stored location use the original source. Failing to
+    /// symbolicate never rejects the error.
+    async fn symbolicate(&self, namespace_id: Uuid, prepared: &mut PreparedError) {
+        let Some(release) = prepared.request.release.as_deref() else {
+            return;
+        };
+
+        if let Err(err) = self
+            .artifact_service
+            .symbolicate(namespace_id, release, &mut prepared.stack_trace_info)
+            .await
+        {
+            warn!("Failed to symbolicate error for release {}: {}", release, err);
+        }
+    }
+
+    async fn check_quota(
+        &self,
+        namespace: &NamespaceModel,
+        count: usize,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<QuotaDecision>, ServerError> {
+        self.quota_tracker
+            .check(namespace.id, QuotaLimits::from(namespace), count, now)
+            .await
+            .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))
+    }
+
+    async fn find_error_by_event_id(
+        &self,
+        namespace_id: Uuid,
+        event_id: &Option<String>,
+    ) -> Result<Option<ErrorModel>, ServerError> {
+        let Some(event_id) = event_id else {
+            return Ok(None);
+        };
+
+        let mut stored = self
+            .find_errors_by_event_ids(namespace_id, vec![event_id.clone()])
+            .await?;
+        Ok(stored.remove(event_id))
+    }
+
+    async fn find_errors_by_event_ids(
+        &self,
+        namespace_id: Uuid,
+        event_ids: Vec<String>,
+    ) -> Result<HashMap<String, ErrorModel>, ServerError> {
+        if event_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let stored = ErrorEntity::find()
+            .filter(<ErrorEntity as sea_orm::EntityTrait>::Column::NamespaceId.eq(namespace_id))
+            .filter(<ErrorEntity as sea_orm::EntityTrait>::Column::EventId.is_in(event_ids))
+            .all(&*self.db)
+            .await
+            .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
+
+        Ok(stored
+            .into_iter()
+            .filter_map(|error| Some((error.event_id.clone()?, error)))
+            .collect())
+    }
+
+    async fn find_namespace_by_client_id(
+        &self,
+        namespace_client_id: Uuid,
+    ) -> Result<NamespaceModel, ServerError> {
+        NamespaceEntity::find()
             .filter(
                 <NamespaceEntity as sea_orm::EntityTrait>::Column::ClientId.eq(namespace_client_id),
             )
-            .one(db)
+            .one(&*self.db)
             .await
             .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?
-            .ok_or(ServerError::QueryError(QueryError::NamespaceNotFound))?;
+            .ok_or(ServerError::QueryError(QueryError::NamespaceNotFound))
+    }
+
+    /// Runs the namespace's alerts against the given errors. An alert is only evaluated
+    /// when at least one of the errors is in its scope, and is delivered once when its
+    /// conditions fire.
+    pub async fn evaluate_alerts(
+        &self,
+        discord_handler: &DiscordHandler,
+        found_namespace: &NamespaceModel,
+        errors: &[PreparedError],
+        notification_manager: &Arc<NotificationServer>,
+        service_mapping: &HashMap<&'static str, &'static str>,
+        now: DateTime<Utc>,
+    ) -> Result<(), ServerError> {
+        let db = &*self.db;
 
-        // Find alerts for this namespace
         let found_alerts = NamespaceAlertEntity::find()
             .filter(
                 <NamespaceAlertEntity as sea_orm::EntityTrait>::Column::NamespaceId
                     .eq(found_namespace.id),
             )
+            .filter(<NamespaceAlertEntity as sea_orm::EntityTrait>::Column::Triggered.eq(false))
             .all(db)
             .await
             .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
 
-        let mut alerts_sent: Vec<Uuid> = Vec::new();
+        let delivery = AlertDelivery {
+            db,
+            configs: &self.configs,
+            discord_handler,
+            notification_manager,
+            service_mapping,
+        };
 
-        // Find subscribed users for each alert
         for alert in found_alerts {
-            // // we need to filter the error based on one of these fields
-            if alert.triggered == true {
+            let Some(rule) = AlertRule::from_alert(&alert) else {
+                continue;
+            };
+            if !errors.iter().any(|prepared| rule.scope.matches(prepared)) {
                 continue;
             }
 
-            if let Some(alert_path) = alert.path.clone() {
-                if alert_path != stack_trace_info.file_path {
-                    continue;
-                }
-            }
-            if let Some(alert_line) = alert.line.clone() {
-                if alert_line != stack_trace_info.line_number {
-                    continue;
-                }
-            }
-            if let Some(alert_message) = alert.message.clone() {
-                if alert_message != error.message {
-                    continue;
-                }
-            }
+            self.fire_if_tripped(&delivery, &alert, &rule, now).await?;
+        }
 
-            let subscribed_users = NamespaceAlertUserJunctionEntity::find()
-            .filter(<NamespaceAlertUserJunctionEntity as sea_orm::EntityTrait>::Column::NamespaceAlertId.eq(alert.id))
+        Ok(())
+    }
+
+    /// Evaluates the untriggered alerts that can fire without new errors arriving, such
+    /// as an error staying unresolved too long, and re-arms fired alerts whose cooldown
+    /// passed or that recovered. Run by the alert scheduler.
+    pub async fn evaluate_scheduled_alerts(
+        &self,
+        discord_handler: &DiscordHandler,
+        notification_manager: &Arc<NotificationServer>,
+        service_mapping: &HashMap<&'static str, &'static str>,
+        now: DateTime<Utc>,
+    ) -> Result<(), ServerError> {
+        let db = &*self.db;
+
+        // Triggered alerts without a cooldown or recovery condition wait for a manual reset
+        let found_alerts = NamespaceAlertEntity::find()
+            .filter(
+                Condition::any()
+                    .add(
+                        <NamespaceAlertEntity as sea_orm::EntityTrait>::Column::Triggered.eq(false),
+                    )
+                    .add(
+                        <NamespaceAlertEntity as sea_orm::EntityTrait>::Column::Cooldown
+                            .is_not_null(),
+                    )
+                    .add(
+                        <NamespaceAlertEntity as sea_orm::EntityTrait>::Column::RecoveryCondition
+                            .is_not_null(),
+                    ),
+            )
             .all(db)
             .await
             .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
 
-            for user_alert_junction in subscribed_users {
-                // Check type of alert (discord,email, etc.) and send + notify users.
-                // TODO: Add ability to disable notifications for users
-                match alert.alert_method.as_str() {
-                    "email" => {
-                        if let Some(count_threshold) = alert.count_threshold {
-                            let time_window = alert.time_window.unwrap();
-                            let time_window_minutes = time_window / 60000;
-                            let time_window_start = now - Duration::minutes(time_window_minutes);
-
-                            // Find all errors in the time window
-                            let mut query = ErrorEntity::find()
-                                .filter(
-                                    <ErrorEntity as sea_orm::EntityTrait>::Column::NamespaceId
-                                        .eq(found_namespace.id),
-                                )
-                                .filter(
-                                    <ErrorEntity as sea_orm::EntityTrait>::Column::CreatedAt
-                                        .gt(time_window_start),
-                                );
-                            // Add filter for whichever alert field we are looking for
-
-                            if let Some(alert_path) = &alert.path {
-                                query = query.filter(
-                                    <ErrorEntity as sea_orm::EntityTrait>::Column::Path
-                                        .eq(alert_path.clone()),
-                                );
-                            }
-                            if let Some(alert_line) = &alert.line {
-                                query = query.filter(
-                                    <ErrorEntity as sea_orm::EntityTrait>::Column::Line
-                                        .eq(alert_line.clone()),
-                                );
-                            }
-                            if let Some(alert_message) = &alert.message {
-                                query = query.filter(
-                                    <ErrorEntity as sea_orm::EntityTrait>::Column::Message
-                                        .eq(alert_message.clone()),
-                                );
-                            }
-
-                            let error_count = query.count(&*self.db).await.map_err(|err| {
-                                ServerError::ExternalError(ExternalError::DB(err))
-                            })?;
-
-                            // Check if error count is equal to threshold because we don't want to spam users after it hits the threshold
-                            if error_count > count_threshold as u64 {
-                                let content = EmailContent {
-                      greeting: "Alert Notice!".to_string(), 
-                      main_message: format!("An error alert has been triggered for a namespace you are subscribed to by the ID of {}", alert.namespace_id),
-                      body: format!("Please log in to your account to view the error details and resolve the issue. {}", configs.domain),
-                      dynamic_content: Some(format!("Alert Details:\nError Count Triggered: {}.\nTime Triggered: {}", count_threshold, now)),
-                    };
+        let delivery = AlertDelivery {
+            db,
+            configs: &self.configs,
+            discord_handler,
+            notification_manager,
+            service_mapping,
+        };
 
-                                // Get user email for each user and send email
-                                let find_user = UserEntity::find()
-                                    .filter(
-                                        <UserEntity as sea_orm::EntityTrait>::Column::Id
-                                            .eq(user_alert_junction.user_id),
-                                    )
-                                    .one(db)
-                                    .await
-                                    .map_err(|err| {
-                                        ServerError::ExternalError(ExternalError::DB(err))
-                                    })?
-                                    .ok_or(ServerError::QueryError(QueryError::UserNotFound))?;
-
-                                let create_notification = NotificationDTO {
-                        id: Uuid::new_v4(),
-                        user_id: find_user.id,
-                        title: "Alert Notification".to_string(),
-                        text: format!("Alert {} has been triggered for a namespace you are subscribed to by the ID of {}", alert.id, alert.namespace_id),
-                        source: "HiGuard Alert System".to_string(),
-                        is_read: false,
-                        created_at: now,
-                    };
-                                let broadcast_notification = create_notification.clone();
-
-                                let notification_model =
-                                    NotificationModel::from(create_notification);
-                                let active_create_notification =
-                                    notification_model.into_active_model();
-
-                                NotificationEntity::insert(active_create_notification)
-                                    .exec(db)
-                                    .await
-                                    .map_err(|err| {
-                                        ServerError::ExternalError(ExternalError::DB(err))
-                                    })?;
-
-                                notification_manager
-                                    .broadcast_notification(broadcast_notification, &find_user.id)
-                                    .await;
-
-                                send_email(configs, &find_user.email, "Error Alert", &content)
-                                    .map_err(|err| ServerError::from(err))?;
-
-                                alerts_sent.push(alert.id);
-                            } else if let Some(unresolved_time_threshold) =
-                                alert.unresolved_time_threshold
-                            {
-                                let time_window = unresolved_time_threshold;
-                                let time_window_minutes = time_window / 60000;
-                                let time_window_start =
-                                    now - Duration::minutes(time_window_minutes);
-                                // Find all errors that are unresolved in the unresolved time threshold
-                                let errors = ErrorEntity::find()
-                                    .filter(
-                                        <ErrorEntity as sea_orm::EntityTrait>::Column::NamespaceId
-                                            .eq(found_namespace.id),
-                                    )
-                                    .filter(
-                                        <ErrorEntity as sea_orm::EntityTrait>::Column::Resolved
-                                            .eq(false),
-                                    )
-                                    .filter(
-                                        <ErrorEntity as sea_orm::EntityTrait>::Column::CreatedAt
-                                            .gt(time_window_start),
-                                    )
-                                    .all(&*self.db)
-                                    .await
-                                    .map_err(|err| {
-                                        ServerError::ExternalError(ExternalError::DB(err))
-                                    })?;
-
-                                let error_ids =
-                                    errors.iter().map(|error| error.id).collect::<Vec<Uuid>>();
-
-                                // Warn users if the error has been unresolved for the threshold time
-                                if errors.len() > 0 {
-                                    let content = EmailContent {
-                          greeting: "Alert Notice!".to_string(), 
-                          main_message: format!("An error alert has been triggered for a namespace you are subscribed to by the ID of {}", alert.namespace_id),
-                          body: format!("Please log in to your account to view the error details and resolve the issue. {}", configs.domain),
-                          dynamic_content: Some(format!("Alert Details:\nErrors unresolved within time: {} Errors. \nError IDs: {:?}", errors.len(), error_ids)),
-                          };
-
-                                    let find_user = UserEntity::find()
-                                        .filter(
-                                            <UserEntity as sea_orm::EntityTrait>::Column::Id
-                                                .eq(user_alert_junction.user_id),
-                                        )
-                                        .one(&*self.db)
-                                        .await
-                                        .map_err(|err| {
-                                            ServerError::ExternalError(ExternalError::DB(err))
-                                        })?
-                                        .ok_or(ServerError::QueryError(QueryError::UserNotFound))?;
-
-                                    let create_notification = NotificationDTO {
-                              id: Uuid::new_v4(),
-                              user_id: find_user.id,
-                              title: "Alert Notification".to_string(),
-                              text: format!("Alert {} has been triggered for a namespace you are subscribed to by the ID of {}", alert.id, alert.namespace_id),
-                              source: "HiGuard Alert System".to_string(),
-                              is_read: false,
-                              created_at: now,
-                          };
-                                    let broadcast_notification = create_notification.clone();
-
-                                    let notification_model =
-                                        NotificationModel::from(create_notification);
-                                    let active_create_notification =
-                                        notification_model.into_active_model();
-
-                                    NotificationEntity::insert(active_create_notification)
-                                        .exec(&*self.db)
-                                        .await
-                                        .map_err(|err| {
-                                            ServerError::ExternalError(ExternalError::DB(err))
-                                        })?;
-
-                                    notification_manager
-                                        .broadcast_notification(
-                                            broadcast_notification,
-                                            &find_user.id,
-                                        )
-                                        .await;
-
-                                    send_email(configs, &find_user.email, "Error Alert", &content)
-                                        .map_err(|err| ServerError::from(err))?;
-
-                                    alerts_sent.push(alert.id);
-                                }
-                            } else if let Some(rate_threshold) = alert.rate_threshold {
-                                let time_window = alert.rate_time_window.unwrap();
-                                let time_window_minutes = time_window / 60000;
-                                let time_window_start: DateTime<Utc> =
-                                    now - Duration::minutes(time_window_minutes);
-
-                                // Find all errors in the time window
-                                let mut error_query = ErrorEntity::find()
-                                    .filter(
-                                        <ErrorEntity as sea_orm::EntityTrait>::Column::NamespaceId
-                                            .eq(found_namespace.id),
-                                    )
-                                    .filter(
-                                        <ErrorEntity as sea_orm::EntityTrait>::Column::CreatedAt
-                                            .gt(time_window_start),
-                                    );
-
-                                if let Some(alert_path) = &alert.path {
-                                    error_query = error_query.filter(
-                                        <ErrorEntity as sea_orm::EntityTrait>::Column::Path
-                                            .eq(alert_path.clone()),
-                                    );
-                                }
-                                if let Some(alert_line) = &alert.line {
-                                    error_query = error_query.filter(
-                                        <ErrorEntity as sea_orm::EntityTrait>::Column::Line
-                                            .eq(alert_line.clone()),
-                                    );
-                                }
-                                if let Some(alert_message) = &alert.message {
-                                    error_query = error_query.filter(
-                                        <ErrorEntity as sea_orm::EntityTrait>::Column::Message
-                                            .eq(alert_message.clone()),
-                                    );
-                                }
-
-                                let error_count =
-                                    error_query.count(&*self.db).await.map_err(|err| {
-                                        ServerError::ExternalError(ExternalError::DB(err))
-                                    })?;
-
-                                // Check if the rate at which errors are being created is greater than the threshold
-                                // Calculate rate by dividing the number of errors by the time window
-                                // Time is all calculated in milliseconds so we need to convert the time window to milliseconds
-                                let rate = error_count as f64 / (time_window as f64 * 60.0);
-
-                                if rate > rate_threshold as f64 {
-                                    let content = EmailContent {
-                          greeting: "Alert Notice!".to_string(), 
-                          main_message: format!("An error alert has been triggered for a namespace you are subscribed to by the ID of {}", alert.namespace_id),
-                          body: format!("Please log in to your account to view the error details and resolve the issue. {}", configs.domain),
-                          dynamic_content: Some(format!("Alert Details:\nRate: {} errors per minute. \nThreshold: {} errors per minute. \nAlert ID: {}", rate, alert.rate_threshold.unwrap(), alert.id)),
-                          };
-
-                                    let find_user = UserEntity::find()
-                                        .filter(
-                                            <UserEntity as sea_orm::EntityTrait>::Column::Id
-                                                .eq(user_alert_junction.user_id),
-                                        )
-                                        .one(&*self.db)
-                                        .await
-                                        .map_err(|err| {
-                                            ServerError::ExternalError(ExternalError::DB(err))
-                                        })?
-                                        .ok_or(ServerError::QueryError(QueryError::UserNotFound))?;
-
-                                    let create_notification = NotificationDTO {
-                              id: Uuid::new_v4(),
-                              user_id: find_user.id,
-                              title: "Alert Notification".to_string(),
-                              text: format!("Alert {} has been triggered for a namespace you are subscribed to by the ID of {}", alert.id, alert.namespace_id),
-                              source: "HiGuard Alert System".to_string(),
-                              is_read: false,
-                              created_at: now,
-                          };
-                                    let broadcast_notification = create_notification.clone();
-
-                                    let notification_model =
-                                        NotificationModel::from(create_notification);
-                                    let active_create_notification =
-                                        notification_model.into_active_model();
-
-                                    NotificationEntity::insert(active_create_notification)
-                                        .exec(&*self.db)
-                                        .await
-                                        .map_err(|err| {
-                                            ServerError::ExternalError(ExternalError::DB(err))
-                                        })?;
-
-                                    notification_manager
-                                        .broadcast_notification(
-                                            broadcast_notification,
-                                            &find_user.id,
-                                        )
-                                        .await;
-
-                                    send_email(configs, &find_user.email, "Error Alert", &content)
-                                        .map_err(|err| ServerError::from(err))?;
-
-                                    alerts_sent.push(alert.id);
-                                }
-                            } else {
-                                continue;
-                            }
-                        }
-                    }
-                    "discord" => {
-                        if let Some(count_threshold) = alert.count_threshold {
-                            let time_window = alert.time_window.unwrap();
-                            let time_window_minutes = time_window / 60000;
-                            let time_window_start = now - Duration::minutes(time_window_minutes);
-
-                            // Find all errors in the time window
-                            let mut query = ErrorEntity::find()
-                                .filter(
-                                    <ErrorEntity as sea_orm::EntityTrait>::Column::NamespaceId
-                                        .eq(found_namespace.id),
-                                )
-                                .filter(
-                                    <ErrorEntity as sea_orm::EntityTrait>::Column::CreatedAt
-                                        .gt(time_window_start),
-                                );
-                            // Add filter for whichever alert field we are looking for
-
-                            if let Some(alert_path) = &alert.path {
-                                query = query.filter(
-                                    <ErrorEntity as sea_orm::EntityTrait>::Column::Path
-                                        .eq(alert_path.clone()),
-                                );
-                            }
-                            if let Some(alert_line) = &alert.line {
-                                query = query.filter(
-                                    <ErrorEntity as sea_orm::EntityTrait>::Column::Line
-                                        .eq(alert_line.clone()),
-                                );
-                            }
-                            if let Some(alert_message) = &alert.message {
-                                query = query.filter(
-                                    <ErrorEntity as sea_orm::EntityTrait>::Column::Message
-                                        .eq(alert_message.clone()),
-                                );
-                            }
-
-                            let error_count = query.count(&*self.db).await.map_err(|err| {
-                                ServerError::ExternalError(ExternalError::DB(err))
-                            })?;
-
-                            // Check if error count is equal to threshold because we don't want to spam users after it hits the threshold
-                            if error_count > count_threshold as u64 {
-                                // Get user email for each user and send email
-                                let find_user = UserEntity::find()
-                                    .filter(
-                                        <UserEntity as sea_orm::EntityTrait>::Column::Id
-                                            .eq(user_alert_junction.user_id),
-                                    )
-                                    .one(db)
-                                    .await
-                                    .map_err(|err| {
-                                        ServerError::ExternalError(ExternalError::DB(err))
-                                    })?
-                                    .ok_or(ServerError::QueryError(QueryError::UserNotFound))?;
-
-                                let create_notification = NotificationDTO {
-                        id: Uuid::new_v4(),
-                        user_id: find_user.id,
-                        title: "Alert Notification".to_string(),
-                        text: format!("Alert {} has been triggered for a namespace you are subscribed to by the ID of {}", alert.id, alert.namespace_id),
-                        source: "HiGuard Alert System".to_string(),
-                        is_read: false,
-                        created_at: now,
-                    };
-                                let broadcast_notification = create_notification.clone();
-
-                                let notification_model =
-                                    NotificationModel::from(create_notification);
-                                let active_create_notification =
-                                    notification_model.into_active_model();
-
-                                NotificationEntity::insert(active_create_notification)
-                                    .exec(db)
-                                    .await
-                                    .map_err(|err| {
-                                        ServerError::ExternalError(ExternalError::DB(err))
-                                    })?;
-
-                                notification_manager
-                                    .broadcast_notification(broadcast_notification, &find_user.id)
-                                    .await;
-
-                                let content = format!("Alert {} has been triggered for a namespace you are subscribed to by the ID of {} at {}.", alert.id, alert.namespace_id, alert.created_at);
-                                let channel_id = alert.discord_channel_id.as_ref().ok_or(
-                                    ServerError::QueryError(QueryError::DiscordChannelNotFound),
-                                )?;
-
-                                let channel_id: u64 = channel_id.parse().map_err(|_| {
-                                    ServerError::QueryError(QueryError::DiscordChannelNotFound)
-                                })?;
-
-                                discord_handler
-                                    .send_discord_alert(channel_id, &content)
-                                    .await
-                                    .map_err(|err| ServerError::from(err))?;
-
-                                alerts_sent.push(alert.id);
-                            } else if let Some(unresolved_time_threshold) =
-                                alert.unresolved_time_threshold
-                            {
-                                let time_window = unresolved_time_threshold;
-                                let time_window_minutes = time_window / 60000;
-                                let time_window_start =
-                                    now - Duration::minutes(time_window_minutes);
-                                // Find all errors that are unresolved in the unresolved time threshold
-                                let errors = ErrorEntity::find()
-                                    .filter(
-                                        <ErrorEntity as sea_orm::EntityTrait>::Column::NamespaceId
-                                            .eq(found_namespace.id),
-                                    )
-                                    .filter(
-                                        <ErrorEntity as sea_orm::EntityTrait>::Column::Resolved
-                                            .eq(false),
-                                    )
-                                    .filter(
-                                        <ErrorEntity as sea_orm::EntityTrait>::Column::CreatedAt
-                                            .gt(time_window_start),
-                                    )
-                                    .all(&*self.db)
-                                    .await
-                                    .map_err(|err| {
-                                        ServerError::ExternalError(ExternalError::DB(err))
-                                    })?;
-
-                                let error_ids =
-                                    errors.iter().map(|error| error.id).collect::<Vec<Uuid>>();
-
-                                // Warn users if the error has been unresolved for the threshold time
-                                if errors.len() > 0 {
-                                    let find_user = UserEntity::find()
-                                        .filter(
-                                            <UserEntity as sea_orm::EntityTrait>::Column::Id
-                                                .eq(user_alert_junction.user_id),
-                                        )
-                                        .one(&*self.db)
-                                        .await
-                                        .map_err(|err| {
-                                            ServerError::ExternalError(ExternalError::DB(err))
-                                        })?
-                                        .ok_or(ServerError::QueryError(QueryError::UserNotFound))?;
-
-                                    let create_notification = NotificationDTO {
-                              id: Uuid::new_v4(),
-                              user_id: find_user.id,
-                              title: "Alert Notification".to_string(),
-                              text: format!("Alert {} has been triggered for a namespace you are subscribed to by the ID of {}", alert.id, alert.namespace_id),
-                              source: "HiGuard Alert System".to_string(),
-                              is_read: false,
-                              created_at: now,
-                          };
-                                    let broadcast_notification = create_notification.clone();
-
-                                    let notification_model =
-                                        NotificationModel::from(create_notification);
-                                    let active_create_notification =
-                                        notification_model.into_active_model();
-
-                                    NotificationEntity::insert(active_create_notification)
-                                        .exec(&*self.db)
-                                        .await
-                                        .map_err(|err| {
-                                            ServerError::ExternalError(ExternalError::DB(err))
-                                        })?;
-
-                                    notification_manager
-                                        .broadcast_notification(
-                                            broadcast_notification,
-                                            &find_user.id,
-                                        )
-                                        .await;
-
-                                    let content = format!("Alert {} has been triggered for a namespace you are subscribed to by the ID of {} at {}.", alert.id, alert.namespace_id, alert.created_at);
-                                    let channel_id = alert.discord_channel_id.as_ref().ok_or(
-                                        ServerError::QueryError(QueryError::DiscordChannelNotFound),
-                                    )?;
-
-                                    let channel_id: u64 = channel_id.parse().map_err(|_| {
-                                        ServerError::QueryError(QueryError::DiscordChannelNotFound)
-                                    })?;
-
-                                    discord_handler
-                                        .send_discord_alert(channel_id, &content)
-                                        .await
-                                        .map_err(|err| ServerError::from(err))?;
-
-                                    alerts_sent.push(alert.id);
-                                }
-                            } else if let Some(rate_threshold) = alert.rate_threshold {
-                                let time_window = alert.rate_time_window.unwrap();
-                                let time_window_minutes = time_window / 60000;
-                                let time_window_start: DateTime<Utc> =
-                                    now - Duration::minutes(time_window_minutes);
-
-                                // Find all errors in the time window
-                                let mut error_query = ErrorEntity::find()
-                                    .filter(
-                                        <ErrorEntity as sea_orm::EntityTrait>::Column::NamespaceId
-                                            .eq(found_namespace.id),
-                                    )
-                                    .filter(
-                                        <ErrorEntity as sea_orm::EntityTrait>::Column::CreatedAt
-                                            .gt(time_window_start),
-                                    );
-
-                                if let Some(alert_path) = &alert.path {
-                                    error_query = error_query.filter(
-                                        <ErrorEntity as sea_orm::EntityTrait>::Column::Path
-                                            .eq(alert_path.clone()),
-                                    );
-                                }
-                                if let Some(alert_line) = &alert.line {
-                                    error_query = error_query.filter(
-                                        <ErrorEntity as sea_orm::EntityTrait>::Column::Line
-                                            .eq(alert_line.clone()),
-                                    );
-                                }
-                                if let Some(alert_message) = &alert.message {
-                                    error_query = error_query.filter(
-                                        <ErrorEntity as sea_orm::EntityTrait>::Column::Message
-                                            .eq(alert_message.clone()),
-                                    );
-                                }
-
-                                let error_count =
-                                    error_query.count(&*self.db).await.map_err(|err| {
-                                        ServerError::ExternalError(ExternalError::DB(err))
-                                    })?;
-
-                                // Check if the rate at which errors are being created is greater than the threshold
-                                // Calculate rate by dividing the number of errors by the time window
-                                // Time is all calculated in milliseconds so we need to convert the time window to milliseconds
-                                let rate = error_count as f64 / (time_window as f64 * 60.0);
-
-                                if rate > rate_threshold as f64 {
-                                    let find_user = UserEntity::find()
-                                        .filter(
-                                            <UserEntity as sea_orm::EntityTrait>::Column::Id
-                                                .eq(user_alert_junction.user_id),
-                                        )
-                                        .one(&*self.db)
-                                        .await
-                                        .map_err(|err| {
-                                            ServerError::ExternalError(ExternalError::DB(err))
-                                        })?
-                                        .ok_or(ServerError::QueryError(QueryError::UserNotFound))?;
-
-                                    let create_notification = NotificationDTO {
-                              id: Uuid::new_v4(),
-                              user_id: find_user.id,
-                              title: "Alert Notification".to_string(),
-                              text: format!("Alert {} has been triggered for a namespace you are subscribed to by the ID of {}", alert.id, alert.namespace_id),
-                              source: "HiGuard Alert System".to_string(),
-                              is_read: false,
-                              created_at: now,
-                          };
-                                    let broadcast_notification = create_notification.clone();
-
-                                    let notification_model =
-                                        NotificationModel::from(create_notification);
-                                    let active_create_notification =
-                                        notification_model.into_active_model();
-
-                                    NotificationEntity::insert(active_create_notification)
-                                        .exec(&*self.db)
-                                        .await
-                                        .map_err(|err| {
-                                            ServerError::ExternalError(ExternalError::DB(err))
-                                        })?;
-
-                                    notification_manager
-                                        .broadcast_notification(
-                                            broadcast_notification,
-                                            &find_user.id,
-                                        )
-                                        .await;
-                                    let content = format!("Alert {} has been triggered for a namespace you are subscribed to by the ID of {} at {}.", alert.id, alert.namespace_id, alert.created_at);
-                                    let channel_id = alert.discord_channel_id.as_ref().ok_or(
-                                        ServerError::QueryError(QueryError::DiscordChannelNotFound),
-                                    )?;
-
-                                    let channel_id: u64 = channel_id.parse().map_err(|_| {
-                                        ServerError::QueryError(QueryError::DiscordChannelNotFound)
-                                    })?;
-
-                                    discord_handler
-                                        .send_discord_alert(channel_id, &content)
-                                        .await
-                                        .map_err(|err| ServerError::from(err))?;
-
-                                    alerts_sent.push(alert.id);
-                                }
-                            } else {
-                                continue;
-                            }
-                        }
-                    }
-                    "text" => {
-                        if let Some(count_threshold) = alert.count_threshold {
-                            let time_window = alert.time_window.unwrap();
-                            let time_window_minutes = time_window / 60000;
-                            let time_window_start = now - Duration::minutes(time_window_minutes);
-
-                            // Find all errors in the time window
-                            let mut query = ErrorEntity::find()
-                                .filter(
-                                    <ErrorEntity as sea_orm::EntityTrait>::Column::NamespaceId
-                                        .eq(found_namespace.id),
-                                )
-                                .filter(
-                                    <ErrorEntity as sea_orm::EntityTrait>::Column::CreatedAt
-                                        .gt(time_window_start),
-                                );
-                            // Add filter for whichever alert field we are looking for
-
-                            if let Some(alert_path) = &alert.path {
-                                query = query.filter(
-                                    <ErrorEntity as sea_orm::EntityTrait>::Column::Path
-                                        .eq(alert_path.clone()),
-                                );
-                            }
-                            if let Some(alert_line) = &alert.line {
-                                query = query.filter(
-                                    <ErrorEntity as sea_orm::EntityTrait>::Column::Line
-                                        .eq(alert_line.clone()),
-                                );
-                            }
-                            if let Some(alert_message) = &alert.message {
-                                query = query.filter(
-                                    <ErrorEntity as sea_orm::EntityTrait>::Column::Message
-                                        .eq(alert_message.clone()),
-                                );
-                            }
-
-                            let error_count = query.count(&*self.db).await.map_err(|err| {
-                                ServerError::ExternalError(ExternalError::DB(err))
-                            })?;
-
-                            // Check if error count is equal to threshold because we don't want to spam users after it hits the threshold
-                            if error_count > count_threshold as u64 {
-                                let content = format!("\nAlert {} has been triggered for a namespace you are subscribed to by the ID of {}.\n\nPlease log in to your account to view the error details and resolve the issue. {}", alert.id, alert.namespace_id, configs.domain);
-
-                                // Get user email for each user and send email
-                                let (user, user_profile) = UserEntity::find()
-                                    .filter(
-                                        <UserEntity as sea_orm::EntityTrait>::Column::Id
-                                            .eq(user_alert_junction.user_id),
-                                    )
-                                    .find_also_related(UserProfileEntity)
-                                    .one(db)
-                                    .await
-                                    .map_err(|err| {
-                                        ServerError::ExternalError(ExternalError::DB(err))
-                                    })?
-                                    .ok_or(ServerError::QueryError(QueryError::UserNotFound))?;
-
-                                let user_profile = user_profile.ok_or(ServerError::QueryError(
-                                    QueryError::UserProfileNotFound,
-                                ))?;
-                                
-                                if user_profile.phone_number.is_none() {
-                                    continue;
-                                };
-
-                                let create_notification = NotificationDTO {
-                                    id: Uuid::new_v4(),
-                                    user_id: user.id,
-                                    title: "Higuard Alert Notification".to_string(),
-                                    text: format!("Your Higuard alert {} has been triggered for a namespace you are subscribed to by the ID of {}", alert.id, alert.namespace_id),
-                                    source: "HiGuard Alert System".to_string(),
-                                    is_read: false,
-                                    created_at: now,
-                                };
-
-                                let broadcast_notification = create_notification.clone();
-
-                                let notification_model =
-                                    NotificationModel::from(create_notification);
-                                let active_create_notification =
-                                    notification_model.into_active_model();
-
-                                NotificationEntity::insert(active_create_notification)
-                                    .exec(db)
-                                    .await
-                                    .map_err(|err| {
-                                        ServerError::ExternalError(ExternalError::DB(err))
-                                    })?;
-
-                                notification_manager
-                                    .broadcast_notification(broadcast_notification, &user.id)
-                                    .await;
-
-                                // check if phone number and phone provider are in the user profile
-                                // if they are, send the sms
-                                // if not, continue to check the next alert
-                                // as_deref() converts Option<String> to &str
-                                if user_profile.phone_number.is_some()
-                                    && user_profile.phone_provider.is_some()
-                                {
-                                    if let (Some(phone_number), Some(phone_provider)) = (
-                                        user_profile.phone_number.as_deref(),
-                                        user_profile.phone_provider.as_deref(),
-                                    ) {
-                                        send_email_sms(
-                                            configs,
-                                            service_mapping,
-                                            phone_number,
-                                            phone_provider,
-                                            &content,
-                                        )
-                                        .map_err(|err| ServerError::from(err))?;
-                                    }
-                                    alerts_sent.push(alert.id);
-                                }
-                            } else if let Some(unresolved_time_threshold) =
-                                alert.unresolved_time_threshold
-                            {
-                                let time_window = unresolved_time_threshold;
-                                let time_window_minutes = time_window / 60000;
-                                let time_window_start =
-                                    now - Duration::minutes(time_window_minutes);
-                                // Find all errors that are unresolved in the unresolved time threshold
-                                let errors = ErrorEntity::find()
-                                    .filter(
-                                        <ErrorEntity as sea_orm::EntityTrait>::Column::NamespaceId
-                                            .eq(found_namespace.id),
-                                    )
-                                    .filter(
-                                        <ErrorEntity as sea_orm::EntityTrait>::Column::Resolved
-                                            .eq(false),
-                                    )
-                                    .filter(
-                                        <ErrorEntity as sea_orm::EntityTrait>::Column::CreatedAt
-                                            .gt(time_window_start),
-                                    )
-                                    .all(&*self.db)
-                                    .await
-                                    .map_err(|err| {
-                                        ServerError::ExternalError(ExternalError::DB(err))
-                                    })?;
-
-                                let error_ids =
-                                    errors.iter().map(|error| error.id).collect::<Vec<Uuid>>();
-
-                                // Warn users if the error has been unresolved for the threshold time
-                                if errors.len() > 0 {
-                                    let content = format!("\nAlert {} has been triggered for a namespace you are subscribed to by the ID of {}.\n\nPlease log in to your account to view the error details and resolve the issue. {}", alert.id, alert.namespace_id, configs.domain);
-
-                                    let (user, user_profile) = UserEntity::find()
-                                        .filter(
-                                            <UserEntity as sea_orm::EntityTrait>::Column::Id
-                                                .eq(user_alert_junction.user_id),
-                                        )
-                                        .find_also_related(UserProfileEntity)
-                                        .one(db)
-                                        .await
-                                        .map_err(|err| {
-                                            ServerError::ExternalError(ExternalError::DB(err))
-                                        })?
-                                        .ok_or(ServerError::QueryError(QueryError::UserNotFound))?;
-
-                                    let user_profile = user_profile.ok_or(
-                                        ServerError::QueryError(QueryError::UserProfileNotFound),
-                                    )?;
-
-                                    if user_profile.phone_number.is_none() {
-                                        continue;
-                                    };
-
-                                    let create_notification = NotificationDTO {
-                              id: Uuid::new_v4(),
-                              user_id: user.id,
-                              title: "Alert Notification".to_string(),
-                              text: format!("Alert {} has been triggered for a namespace you are subscribed to by the ID of {}", alert.id, alert.namespace_id),
-                              source: "HiGuard Alert System".to_string(),
-                              is_read: false,
-                              created_at: now,
-                          };
-                                    let broadcast_notification = create_notification.clone();
-
-                                    let notification_model =
-                                        NotificationModel::from(create_notification);
-                                    let active_create_notification =
-                                        notification_model.into_active_model();
-
-                                    NotificationEntity::insert(active_create_notification)
-                                        .exec(&*self.db)
-                                        .await
-                                        .map_err(|err| {
-                                            ServerError::ExternalError(ExternalError::DB(err))
-                                        })?;
-
-                                    notification_manager
-                                        .broadcast_notification(broadcast_notification, &user.id)
-                                        .await;
-
-                                    if user_profile.phone_number.is_some()
-                                        && user_profile.phone_provider.is_some()
-                                    {
-                                        if let (Some(phone_number), Some(phone_provider)) = (
-                                            user_profile.phone_number.as_deref(),
-                                            user_profile.phone_provider.as_deref(),
-                                        ) {
-                                            send_email_sms(
-                                                configs,
-                                                service_mapping,
-                                                phone_number,
-                                                phone_provider,
-                                                &content,
-                                            )
-                                            .map_err(|err| ServerError::from(err))?;
-                                        }
-                                        alerts_sent.push(alert.id);
-                                    }
-                                }
-                            } else if let Some(rate_threshold) = alert.rate_threshold {
-                                let time_window = alert.rate_time_window.unwrap();
-                                let time_window_minutes = time_window / 60000;
-                                let time_window_start: DateTime<Utc> =
-                                    now - Duration::minutes(time_window_minutes);
-
-                                // Find all errors in the time window
-                                let mut error_query = ErrorEntity::find()
-                                    .filter(
-                                        <ErrorEntity as sea_orm::EntityTrait>::Column::NamespaceId
-                                            .eq(found_namespace.id),
-                                    )
-                                    .filter(
-                                        <ErrorEntity as sea_orm::EntityTrait>::Column::CreatedAt
-                                            .gt(time_window_start),
-                                    );
-
-                                if let Some(alert_path) = &alert.path {
-                                    error_query = error_query.filter(
-                                        <ErrorEntity as sea_orm::EntityTrait>::Column::Path
-                                            .eq(alert_path.clone()),
-                                    );
-                                }
-                                if let Some(alert_line) = &alert.line {
-                                    error_query = error_query.filter(
-                                        <ErrorEntity as sea_orm::EntityTrait>::Column::Line
-                                            .eq(alert_line.clone()),
-                                    );
-                                }
-                                if let Some(alert_message) = &alert.message {
-                                    error_query = error_query.filter(
-                                        <ErrorEntity as sea_orm::EntityTrait>::Column::Message
-                                            .eq(alert_message.clone()),
-                                    );
-                                }
-
-                                let error_count =
-                                    error_query.count(&*self.db).await.map_err(|err| {
-                                        ServerError::ExternalError(ExternalError::DB(err))
-                                    })?;
-
-                                // Check if the rate at which errors are being created is greater than the threshold
-                                // Calculate rate by dividing the number of errors by the time window
-                                // Time is all calculated in milliseconds so we need to convert the time window to milliseconds
-                                let rate = error_count as f64 / (time_window as f64 * 60.0);
-
-                                if rate > rate_threshold as f64 {
-                                    let content = format!("\nAlert {} has been triggered for a namespace you are subscribed to by the ID of {}.\n\nPlease log in to your account to view the error details and resolve the issue. {}", alert.id, alert.namespace_id, configs.domain);
-
-                                    let (user, user_profile) = UserEntity::find()
-                                        .filter(
-                                            <UserEntity as sea_orm::EntityTrait>::Column::Id
-                                                .eq(user_alert_junction.user_id),
-                                        )
-                                        .find_also_related(UserProfileEntity)
-                                        .one(db)
-                                        .await
-                                        .map_err(|err| {
-                                            ServerError::ExternalError(ExternalError::DB(err))
-                                        })?
-                                        .ok_or(ServerError::QueryError(QueryError::UserNotFound))?;
-
-                                    let user_profile = user_profile.ok_or(
-                                        ServerError::QueryError(QueryError::UserProfileNotFound),
-                                    )?;
-
-                                    if user_profile.phone_number.is_none() {
-                                        continue;
-                                    };
-
-                                    let create_notification = NotificationDTO {
-                                        id: Uuid::new_v4(),
-                                        user_id: user.id,
-                                        title: "Alert Notification".to_string(),
-                                        text: format!("Alert {} has been triggered for a namespace you are subscribed to by the ID of {}", alert.id, alert.namespace_id),
-                                        source: "HiGuard Alert System".to_string(),
-                                        is_read: false,
-                                        created_at: now,
-                                    };
-                                    let broadcast_notification = create_notification.clone();
-
-                                    let notification_model =
-                                        NotificationModel::from(create_notification);
-                                    let active_create_notification =
-                                        notification_model.into_active_model();
-
-                                    NotificationEntity::insert(active_create_notification)
-                                        .exec(&*self.db)
-                                        .await
-                                        .map_err(|err| {
-                                            ServerError::ExternalError(ExternalError::DB(err))
-                                        })?;
-
-                                    notification_manager
-                                        .broadcast_notification(broadcast_notification, &user.id)
-                                        .await;
-
-                                    if user_profile.phone_number.is_some()
-                                        && user_profile.phone_provider.is_some()
-                                    {
-                                        if let (Some(phone_number), Some(phone_provider)) = (
-                                            user_profile.phone_number.as_deref(),
-                                            user_profile.phone_provider.as_deref(),
-                                        ) {
-                                            send_email_sms(
-                                                configs,
-                                                service_mapping,
-                                                phone_number,
-                                                phone_provider,
-                                                &content,
-                                            )
-                                            .map_err(|err| ServerError::from(err))?;
-                                        }
-                                        alerts_sent.push(alert.id);
-                                    }
-                                }
-                            } else {
-                                continue;
-                            }
-                        }
-                    }
-                    _ => {
-                        return Err(ServerError::QueryError(QueryError::AlertTypeNotFound));
-                    }
-                }
+        for alert in found_alerts {
+            let Some(rule) = AlertRule::from_alert(&alert) else {
+                continue;
+            };
+
+            if alert.triggered {
+                self.rearm_if_recovered(&delivery, &alert, &rule, now)
+                    .await?;
+            } else if rule.condition.is_time_based() {
+                self.fire_if_tripped(&delivery, &alert, &rule, now).await?;
             }
         }
 
-        // Batch update alerts triggered
-        let update_model = NamespaceAlertActiveModel {
-            triggered: Set(true),
-            id: NotSet,
-            namespace_id: NotSet,
-            alert_method: NotSet,
-            discord_channel_id: NotSet,
-            path: NotSet,
-            line: NotSet,
-            message: NotSet,
-            stack_trace: NotSet,
-            count_threshold: NotSet,
-            time_window: NotSet,
-            unresolved_time_threshold: NotSet,
-            rate_threshold: NotSet,
-            rate_time_window: NotSet,
-            created_at: NotSet,
-            updated_at: NotSet,
-        };
+        Ok(())
+    }
 
-        // Update alerts triggered
-        NamespaceAlertEntity::update_many()
-            .set(update_model)
-            .filter(<NamespaceAlertEntity as sea_orm::EntityTrait>::Column::Id.is_in(alerts_sent))
-            .exec(db)
+    async fn fire_if_tripped(
+        &self,
+        delivery: &AlertDelivery<'_>,
+        alert: &NamespaceAlertModel,
+        rule: &AlertRule,
+        now: DateTime<Utc>,
+    ) -> Result<(), ServerError> {
+        let context = ConditionContext {
+            db: &self.db,
+            namespace_id: alert.namespace_id,
+            scope: &rule.scope,
+            now,
+        };
+        let Some(details) = rule
+            .condition
+            .evaluate(&context)
             .await
-            .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
+            .map_err(ExternalError::from)?
+        else {
+            return Ok(());
+        };
+
+        // One alert failing to send shouldn't keep the others from going out
+        if let Err(err) = delivery.fire(alert, &details, now).await {
+            error!("Failed to deliver alert {}: {}", alert.id, err);
+        }
+
+        Ok(())
+    }
+
+    // Re-arms once the cooldown passed and the recovery condition stopped firing,
+    // whichever of the two the alert has
+    async fn rearm_if_recovered(
+        &self,
+        delivery: &AlertDelivery<'_>,
+        alert: &NamespaceAlertModel,
+        rule: &AlertRule,
+        now: DateTime<Utc>,
+    ) -> Result<(), ServerError> {
+        if let (Some(cooldown), Some(triggered_at)) = (alert.cooldown, alert.triggered_at) {
+            if now < triggered_at + Duration::milliseconds(cooldown) {
+                return Ok(());
+            }
+        }
+
+        if let Some(recovery) = &rule.recovery {
+            let context = ConditionContext {
+                db: &self.db,
+                namespace_id: alert.namespace_id,
+                scope: &rule.scope,
+                now,
+            };
+            if recovery
+                .evaluate(&context)
+                .await
+                .map_err(ExternalError::from)?
+                .is_some()
+            {
+                return Ok(());
+            }
+        }
+
+        // A cooldown alone re-arms while the issue may still be ongoing, that's no recovery
+        let notify = alert.notify_recovery && rule.recovery.is_some();
+        if let Err(err) = delivery.rearm(alert, notify, now).await {
+            error!("Failed to re-arm alert {}: {}", alert.id, err);
+        }
+
+        Ok(())
+    }
+
+    async fn persist_error<C: ConnectionTrait>(
+        &self,
+        db: &C,
+        namespace_id: Uuid,
+        prepared: PreparedError,
+        now: DateTime<Utc>,
+    ) -> Result<CreateErrorDTO, ServerError> {
+        let PreparedError {
+            request: error,
+            stack_trace_info,
+            redacted_fields,
+        } = prepared;
+
+        if let Some(release) = &error.release {
+            self.upsert_release(db, namespace_id, release, now).await?;
+        }
+
+        let issue_id = self
+            .upsert_issue(
+                db,
+                namespace_id,
+                &stack_trace_info,
+                &error.message,
+                &error.user_affected,
+                now,
+            )
+            .await?;
+
+        self.reopen_if_regressed(db, namespace_id, issue_id, error.release.as_deref(), now)
+            .await?;
 
         let create_error = ErrorModel {
             id: Uuid::new_v4(),
@@ -1089,9 +797,28 @@ impl ErrorService {
             path: stack_trace_info.file_path,
             line: stack_trace_info.line_number,
             message: error.message,
-            stack_trace: error_stack_trace,
+            stack_trace: error.stack_trace,
+            frames: serde_json::to_value(
+                stack_trace_info
+                    .frames
+                    .iter()
+                    .map(StackFrameDTO::from)
+                    .collect::<Vec<StackFrameDTO>>(),
+            )
+            .unwrap_or_default(),
+            breadcrumbs: serde_json::to_value(limit_breadcrumbs(
+                error.breadcrumbs.unwrap_or_default(),
+            ))
+            .unwrap_or_default(),
+            release: error.release,
+            environment: error.environment,
+            event_id: error.event_id,
+            redacted_fields: Json::from(redacted_fields),
+            contexts: serde_json::to_value(error.contexts.unwrap_or_default()).unwrap_or_default(),
+            level: error.level.unwrap_or_default().as_str().to_string(),
             resolved: false,
-            namespace_id: found_namespace.id,
+            namespace_id,
+            issue_id: Some(issue_id),
             created_at: now,
             updated_at: now,
         };
@@ -1126,11 +853,193 @@ impl ErrorService {
             message: create_error.message,
             resolved: create_error.resolved,
             namespace_id: create_error.namespace_id,
+            issue_id: create_error.issue_id,
             stack_trace: create_error.stack_trace,
             user_affected: create_error.user_affected,
         })
     }
 
+    /// Groups an incoming error into its issue, creating the issue on first sight and
+    /// bumping its occurrence, last seen and unique user counters otherwise.
+    async fn upsert_issue<C: ConnectionTrait>(
+        &self,
+        db: &C,
+        namespace_id: Uuid,
+        stack_trace_info: &StackTraceInfo,
+        message: &str,
+        user_affected: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Uuid, ServerError> {
+        let new_issue = IssueModel {
+            id: Uuid::new_v4(),
+            namespace_id,
+            fingerprint: compute_fingerprint(stack_trace_info, message),
+            error_type: stack_trace_info.error_type.clone(),
+            message: message.to_string(),
+            path: stack_trace_info.file_path.clone(),
+            line: stack_trace_info.line_number,
+            resolved: false,
+            resolved_in_release: None,
+            regression: false,
+            occurrences: 1,
+            users_affected: 0,
+            first_seen: now,
+            last_seen: now,
+            created_at: now,
+            updated_at: now,
+        };
+
+        // Errors sharing a fingerprint can arrive concurrently, so the counters are
+        // bumped by the conflicting insert itself rather than by a read-then-write
+        let issue_id = IssueEntity::insert(new_issue.into_active_model())
+            .on_conflict(
+                OnConflict::columns([IssueColumn::NamespaceId, IssueColumn::Fingerprint])
+                    .value(
+                        IssueColumn::Occurrences,
+                        Expr::col((IssueEntity, IssueColumn::Occurrences)).add(1),
+                    )
+                    .value(IssueColumn::LastSeen, Expr::value(now))
+                    .value(IssueColumn::UpdatedAt, Expr::value(now))
+                    .to_owned(),
+            )
+            .exec(db)
+            .await
+            .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?
+            .last_insert_id;
+
+        // Only the error whose insert wins the primary key counts a new user, concurrent
+        // errors from the same user wait on it and then conflict
+        let new_user = IssueUserEntity::insert(
+            IssueUserModel {
+                issue_id,
+                user_affected: user_affected.to_string(),
+            }
+            .into_active_model(),
+        )
+        .on_conflict(
+            OnConflict::columns([IssueUserColumn::IssueId, IssueUserColumn::UserAffected])
+                .do_nothing()
+                .to_owned(),
+        )
+        .do_nothing()
+        .exec(db)
+        .await
+        .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
+
+        if matches!(new_user, TryInsertResult::Inserted(_)) {
+            IssueEntity::update_many()
+                .col_expr(
+                    IssueColumn::UsersAffected,
+                    Expr::col(IssueColumn::UsersAffected).add(1),
+                )
+                .filter(IssueColumn::Id.eq(issue_id))
+                .exec(db)
+                .await
+                .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
+        }
+
+        Ok(issue_id)
+    }
+
+    /// Records that a release has been seen, creating it the first time it reports.
+    async fn upsert_release<C: ConnectionTrait>(
+        &self,
+        db: &C,
+        namespace_id: Uuid,
+        version: &str,
+        now: DateTime<Utc>,
+    ) -> Result<(), ServerError> {
+        let new_release = ReleaseModel {
+            id: Uuid::new_v4(),
+            namespace_id,
+            version: version.to_string(),
+            first_seen: now,
+            last_seen: now,
+            created_at: now,
+            updated_at: now,
+        };
+
+        ReleaseEntity::insert(new_release.into_active_model())
+            .on_conflict(
+                OnConflict::columns([ReleaseColumn::NamespaceId, ReleaseColumn::Version])
+                    .value(ReleaseColumn::LastSeen, Expr::value(now))
+                    .value(ReleaseColumn::UpdatedAt, Expr::value(now))
+                    .to_owned(),
+            )
+            .exec(db)
+            .await
+            .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
+
+        Ok(())
+    }
+
+    /// Reopens a resolved issue as a regression when it shows up again. An issue resolved
+    /// in a release only reopens for releases first seen after that one, so errors from
+    /// older builds that are still deployed leave it resolved.
+    async fn reopen_if_regressed<C: ConnectionTrait>(
+        &self,
+        db: &C,
+        namespace_id: Uuid,
+        issue_id: Uuid,
+        release: Option<&str>,
+        now: DateTime<Utc>,
+    ) -> Result<(), ServerError> {
+        let issue = IssueEntity::find_by_id(issue_id)
+            .one(db)
+            .await
+            .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
+
+        let resolved_in_release = match issue {
+            Some(IssueModel {
+                resolved: true,
+                resolved_in_release,
+                ..
+            }) => resolved_in_release,
+            _ => return Ok(()),
+        };
+
+        let is_newer = match (release, resolved_in_release) {
+            (Some(release), Some(resolved_in_release)) if release == resolved_in_release => false,
+            (Some(release), Some(resolved_in_release)) => {
+                let releases = ReleaseEntity::find()
+                    .filter(ReleaseColumn::NamespaceId.eq(namespace_id))
+                    .filter(ReleaseColumn::Version.is_in([release, resolved_in_release.as_str()]))
+                    .all(db)
+                    .await
+                    .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
+
+                let first_seen = |version: &str| {
+                    releases
+                        .iter()
+                        .find(|found| found.version == version)
+                        .map(|found| found.first_seen)
+                };
+
+                match (first_seen(release), first_seen(&resolved_in_release)) {
+                    (Some(current), Some(resolved)) => current > resolved,
+                    _ => false,
+                }
+            }
+            // Without both releases there is nothing to compare, and the occurrence came
+            // in after the issue was resolved
+            _ => true,
+        };
+
+        if is_newer {
+            IssueEntity::update_many()
+                .col_expr(IssueColumn::Resolved, Expr::value(false))
+                .col_expr(IssueColumn::Regression, Expr::value(true))
+                .col_expr(IssueColumn::UpdatedAt, Expr::value(now))
+                .filter(IssueColumn::Id.eq(issue_id))
+                .filter(IssueColumn::Resolved.eq(true))
+                .exec(db)
+                .await
+                .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
+        }
+
+        Ok(())
+    }
+
     pub async fn get_error_by_id(&self, id: Uuid) -> Result<ErrorDTO, ServerError> {
         let found_error = ErrorEntity::find()
             .filter(<ErrorEntity as sea_orm::EntityTrait>::Column::Id.eq(id))
@@ -1165,9 +1074,19 @@ impl ErrorService {
             line: found_error.line,
             stack_trace: found_error.stack_trace,
             namespace_id: found_error.namespace_id,
+            issue_id: found_error.issue_id,
             resolved: found_error.resolved,
             created_at: found_error.created_at,
             updated_at: found_error.updated_at,
+            frames: serde_json::from_value(found_error.frames).unwrap_or_default(),
+            breadcrumbs: serde_json::from_value(found_error.breadcrumbs).unwrap_or_default(),
+            release: found_error.release,
+            environment: found_error.environment,
+            event_id: found_error.event_id,
+            redacted_fields: serde_json::from_value(found_error.redacted_fields)
+                .unwrap_or_default(),
+            contexts: serde_json::from_value(found_error.contexts).unwrap_or_default(),
+            level: ErrorLevel::parse(&found_error.level).unwrap_or_default(),
             tags,
         })
     }
@@ -1175,9 +1094,15 @@ impl ErrorService {
     pub async fn update_error(&self, error: UpdateErrorDTO) -> Result<UpdateErrorDTO, ServerError> {
         let now = Utc::now();
 
+        let transaction = self
+            .db
+            .begin()
+            .await
+            .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
+
         let found_error = ErrorEntity::find()
             .filter(<ErrorEntity as sea_orm::EntityTrait>::Column::Id.eq(error.id))
-            .one(&*self.db)
+            .one(&transaction)
             .await
             .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?
             .ok_or(ServerError::QueryError(QueryError::ErrorNotFound))?;
@@ -1205,7 +1130,7 @@ impl ErrorService {
                 tag_list.push(tag);
 
                 TagEntity::insert(tag_model)
-                    .exec(&*self.db)
+                    .exec(&transaction)
                     .await
                     .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
             }
@@ -1214,7 +1139,20 @@ impl ErrorService {
         update_error.updated_at = now;
 
         ErrorEntity::update(update_error.clone().into_active_model())
-            .exec(&*self.db)
+            .exec(&transaction)
+            .await
+            .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
+
+        // Resolving the issue resolves every error in it, so the issue follows its errors
+        // the other way round too
+        if let Some(issue_id) = update_error.issue_id {
+            if update_error.resolved != found_error.resolved {
+                self.sync_issue_resolution(&transaction, issue_id, now).await?;
+            }
+        }
+
+        transaction
+            .commit()
             .await
             .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
 
@@ -1225,12 +1163,39 @@ impl ErrorService {
         })
     }
 
+    /// Marks the issue resolved once none of its errors are open, and reopens it as soon as
+    /// one is. The regression flag and resolving release only describe a resolution made
+    /// through the issue itself, so both are cleared whenever the state flips here.
+    async fn sync_issue_resolution<C: ConnectionTrait>(
+        &self,
+        db: &C,
+        issue_id: Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<(), ServerError> {
+        const NO_OPEN_ERRORS: &str = "NOT EXISTS (SELECT 1 FROM errors \
+            WHERE errors.issue_id = issues.id AND errors.resolved = false)";
+
+        IssueEntity::update_many()
+            .col_expr(IssueColumn::Resolved, Expr::cust(NO_OPEN_ERRORS))
+            .col_expr(IssueColumn::Regression, Expr::value(false))
+            .col_expr(IssueColumn::ResolvedInRelease, Expr::value(Option::<String>::None))
+            .col_expr(IssueColumn::UpdatedAt, Expr::value(now))
+            .filter(IssueColumn::Id.eq(issue_id))
+            .filter(Expr::cust(format!("issues.resolved <> {}", NO_OPEN_ERRORS)))
+            .exec(db)
+            .await
+            .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
+
+        Ok(())
+    }
+
     pub async fn get_aggregate_errors_by_date(
         &self,
         namespace_id: Uuid,
         selected_date: NaiveDate,
         time_interval: i64,
         timezone: String,
+        level: Option<ErrorLevel>,
     ) -> Result<Vec<AggregateErrorDTO>, ServerError> {
         let user_tz: Tz = timezone
             .parse()
@@ -1253,6 +1218,7 @@ impl ErrorService {
             .filter(<ErrorEntity as sea_orm::EntityTrait>::Column::NamespaceId.eq(namespace_id))
             .filter(<ErrorEntity as sea_orm::EntityTrait>::Column::CreatedAt.gt(start_time_utc))
             .filter(<ErrorEntity as sea_orm::EntityTrait>::Column::CreatedAt.lt(end_time_utc))
+            .filter(level_condition(level))
             .all(&*self.db)
             .await
             .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
@@ -1285,6 +1251,7 @@ impl ErrorService {
         namespace_id: Uuid,
         group_by: String,
         group_key: String,
+        level: Option<ErrorLevel>,
         offset: usize,
         limit: usize,
     ) -> Result<Vec<ErrorMetaDTO>, ServerError> {
@@ -1314,6 +1281,7 @@ impl ErrorService {
                         ),
                     ),
                 )
+                .filter(level_condition(level))
                 .order_by_desc(<ErrorEntity as EntityTrait>::Column::CreatedAt)
                 .offset(offset as u64)
                 .limit(limit as u64)
@@ -1325,8 +1293,14 @@ impl ErrorService {
                 .filter(<ErrorEntity as EntityTrait>::Column::NamespaceId.eq(namespace_id))
                 .filter(match group_by.as_str() {
                     "message" => <ErrorEntity as EntityTrait>::Column::Message.eq(group_key),
+                    "release" => <ErrorEntity as EntityTrait>::Column::Release.eq(group_key),
+                    "environment" => {
+                        <ErrorEntity as EntityTrait>::Column::Environment.eq(group_key)
+                    }
+                    "level" => <ErrorEntity as EntityTrait>::Column::Level.eq(group_key),
                     _ => <ErrorEntity as EntityTrait>::Column::Message.eq(group_key),
                 })
+                .filter(level_condition(level))
                 .order_by_desc(<ErrorEntity as EntityTrait>::Column::CreatedAt)
                 .offset(offset as u64)
                 .limit(limit as u64)
@@ -1354,6 +1328,7 @@ impl ErrorService {
         &self,
         namespace_id: Uuid,
         group_by: String,
+        level: Option<ErrorLevel>,
     ) -> Result<Vec<GroupedAggregateErrorCountDTO>, ServerError> {
         let db: &DatabaseConnection = &*self.db;
 
@@ -1367,6 +1342,7 @@ impl ErrorService {
                             .and_where(
                                 <ErrorEntity as EntityTrait>::Column::NamespaceId.eq(namespace_id),
                             )
+                            .cond_where(level_condition(level))
                             .to_owned(),
                     ),
                 )
@@ -1391,6 +1367,7 @@ impl ErrorService {
         } else {
             let errors = <ErrorEntity as EntityTrait>::find()
                 .filter(<ErrorEntity as EntityTrait>::Column::NamespaceId.eq(namespace_id))
+                .filter(level_condition(level))
                 .all(db)
                 .await
                 .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
@@ -1400,6 +1377,9 @@ impl ErrorService {
             for error in errors {
                 let group_key = match group_by.as_str() {
                     "message" => error.message.clone(),
+                    "release" => error.release.clone().unwrap_or_default(),
+                    "environment" => error.environment.clone().unwrap_or_default(),
+                    "level" => error.level.clone(),
                     _ => error.message.clone(),
                 };
                 *error_map.entry(group_key).or_insert(0) += 1;
@@ -1414,6 +1394,42 @@ impl ErrorService {
         Ok(aggregated_errors)
     }
 
+    /// Counts errors per release, newest release first. Errors reported without a
+    /// release are left out.
+    pub async fn get_error_counts_by_release(
+        &self,
+        namespace_id: Uuid,
+    ) -> Result<Vec<ReleaseErrorCountDTO>, ServerError> {
+        let db: &DatabaseConnection = &self.db;
+
+        let rows = ErrorEntity::find()
+            .select_only()
+            .column(<ErrorEntity as EntityTrait>::Column::Release)
+            .column_as(<ErrorEntity as EntityTrait>::Column::Id.count(), "count")
+            .column_as(<ErrorEntity as EntityTrait>::Column::CreatedAt.min(), "first_seen")
+            .column_as(<ErrorEntity as EntityTrait>::Column::CreatedAt.max(), "last_seen")
+            .filter(<ErrorEntity as EntityTrait>::Column::NamespaceId.eq(namespace_id))
+            .filter(<ErrorEntity as EntityTrait>::Column::Release.is_not_null())
+            .group_by(<ErrorEntity as EntityTrait>::Column::Release)
+            .order_by_desc(Expr::cust("first_seen"))
+            .into_tuple::<(String, i64, DateTime<Utc>, DateTime<Utc>)>()
+            .all(db)
+            .await
+            .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
+
+        let release_counts = rows
+            .into_iter()
+            .map(|(release, count, first_seen, last_seen)| ReleaseErrorCountDTO {
+                release,
+                count,
+                first_seen,
+                last_seen,
+            })
+            .collect();
+
+        Ok(release_counts)
+    }
+
     pub async fn get_unique_error_meta_by_namespace(
         &self,
         namespace_id: Uuid,
@@ -1433,6 +1449,9 @@ impl ErrorService {
             let meta = match filter.as_str() {
                 "message" => error.message,
                 "path" => error.path,
+                "release" => error.release.unwrap_or_default(),
+                "environment" => error.environment.unwrap_or_default(),
+                "level" => error.level,
                 "line" => error.line.to_string(),
                 "stackTrace" => error.stack_trace,
                 _ => error.message,
@@ -1444,3 +1463,20 @@ impl ErrorService {
         Ok(unique_meta)
     }
 }
+
+// Matches everything when no level is given
+fn level_condition(level: Option<ErrorLevel>) -> Condition {
+    Condition::all().add_option(
+        level.map(|level| <ErrorEntity as EntityTrait>::Column::Level.eq(level.as_str())),
+    )
+}
+
+// The only unique constraint an error insert can hit is the namespace's event ID
+fn is_unique_violation(err: &ServerError) -> bool {
+    match err {
+        ServerError::ExternalError(ExternalError::DB(err)) => {
+            matches!(err.sql_err(), Some(SqlErr::UniqueConstraintViolation(_)))
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,249 @@
+use chrono::Utc;
+use log::warn;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    entity::prelude::*, ActiveValue, DatabaseConnection, EntityTrait, IntoActiveModel, QueryOrder,
+};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+use crate::config::Config;
+use crate::models::inbound_filter_model::{
+    Column as InboundFilterColumn, Entity as InboundFilterEntity, Model as InboundFilterModel,
+};
+use crate::shared::utils::errors::{ExternalError, QueryError, RequestError, ServerError};
+use crate::shared::utils::inbound_filter::{InboundFilter, MAX_FILTER_PATTERN_LENGTH};
+use shared_types::inbound_filter_dtos::{
+    CreateInboundFilterDTO, InboundFilterDTO, InboundFilterType, UpdateInboundFilterDTO,
+};
+
+// Upper bound on the filters of a single namespace, every one runs on each error
+pub const MAX_INBOUND_FILTERS: u64 = 100;
+
+// Changes made through another server instance are picked up after this long
+const FILTER_CACHE_TTL: Duration = Duration::from_secs(60);
+
+// Compiled active filters per namespace along with when they were loaded
+type FilterCache = HashMap<Uuid, (Instant, Arc<Vec<InboundFilter>>)>;
+
+pub struct InboundFilterService {
+    pub db: Arc<DatabaseConnection>,
+    pub configs: Arc<Config>,
+    // Dropped for a namespace whenever one of its filters changes
+    cache: RwLock<FilterCache>,
+}
+
+impl InboundFilterService {
+    pub fn new(db: Arc<DatabaseConnection>, configs: Arc<Config>) -> Self {
+        Self {
+            db,
+            configs,
+            cache: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub async fn get_filters(
+        &self,
+        namespace_id: Uuid,
+    ) -> Result<Vec<InboundFilterDTO>, ServerError> {
+        let filters = InboundFilterEntity::find()
+            .filter(InboundFilterColumn::NamespaceId.eq(namespace_id))
+            .order_by_asc(InboundFilterColumn::CreatedAt)
+            .all(&*self.db)
+            .await
+            .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
+
+        Ok(filters.into_iter().map(InboundFilterDTO::from).collect())
+    }
+
+    pub async fn create_filter(
+        &self,
+        namespace_id: Uuid,
+        filter: CreateInboundFilterDTO,
+    ) -> Result<InboundFilterDTO, ServerError> {
+        let db = &*self.db;
+        let now = Utc::now();
+
+        validate_filter(filter.filter_type, &filter.tag_key, &filter.pattern)
+            .map_err(ServerError::RequestError)?;
+
+        let filter_count = InboundFilterEntity::find()
+            .filter(InboundFilterColumn::NamespaceId.eq(namespace_id))
+            .count(db)
+            .await
+            .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
+        if filter_count >= MAX_INBOUND_FILTERS {
+            return Err(ServerError::RequestError(
+                RequestError::InboundFilterLimitReached,
+            ));
+        }
+
+        let new_filter = InboundFilterModel {
+            id: Uuid::new_v4(),
+            namespace_id,
+            filter_type: filter.filter_type.as_str().to_string(),
+            tag_key: filter.tag_key,
+            pattern: filter.pattern,
+            active: true,
+            dropped_count: 0,
+            created_at: now,
+            updated_at: now,
+        };
+
+        InboundFilterEntity::insert(new_filter.clone().into_active_model())
+            .exec(db)
+            .await
+            .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
+        self.invalidate(namespace_id);
+
+        Ok(InboundFilterDTO::from(new_filter))
+    }
+
+    pub async fn update_filter(
+        &self,
+        namespace_id: Uuid,
+        filter_id: Uuid,
+        update: UpdateInboundFilterDTO,
+    ) -> Result<InboundFilterDTO, ServerError> {
+        let db = &*self.db;
+        let found_filter = self.find_filter(namespace_id, filter_id).await?;
+
+        let filter_type = InboundFilterType::parse(&found_filter.filter_type)
+            .unwrap_or(InboundFilterType::Message);
+        let tag_key = match update.tag_key {
+            Some(tag_key) => tag_key,
+            None => found_filter.tag_key.clone(),
+        };
+        let pattern = update
+            .pattern
+            .unwrap_or_else(|| found_filter.pattern.clone());
+        validate_filter(filter_type, &tag_key, &pattern).map_err(ServerError::RequestError)?;
+
+        let mut update_filter = found_filter.into_active_model();
+        update_filter.tag_key = ActiveValue::Set(tag_key);
+        update_filter.pattern = ActiveValue::Set(pattern);
+        if let Some(active) = update.active {
+            update_filter.active = ActiveValue::Set(active);
+        }
+        update_filter.updated_at = ActiveValue::Set(Utc::now());
+
+        let updated_filter = update_filter
+            .update(db)
+            .await
+            .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
+        self.invalidate(namespace_id);
+
+        Ok(InboundFilterDTO::from(updated_filter))
+    }
+
+    pub async fn delete_filter(
+        &self,
+        namespace_id: Uuid,
+        filter_id: Uuid,
+    ) -> Result<(), ServerError> {
+        let found_filter = self.find_filter(namespace_id, filter_id).await?;
+
+        InboundFilterEntity::delete_by_id(found_filter.id)
+            .exec(&*self.db)
+            .await
+            .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
+        self.invalidate(namespace_id);
+
+        Ok(())
+    }
+
+    /// Returns the compiled active filters of a namespace for the ingestion path,
+    /// loading them from the database only when they aren't cached.
+    pub async fn active_filters(
+        &self,
+        namespace_id: Uuid,
+    ) -> Result<Arc<Vec<InboundFilter>>, ServerError> {
+        if let Some((loaded_at, filters)) = self.cache.read().unwrap().get(&namespace_id) {
+            if loaded_at.elapsed() < FILTER_CACHE_TTL {
+                return Ok(filters.clone());
+            }
+        }
+
+        let filters = InboundFilterEntity::find()
+            .filter(InboundFilterColumn::NamespaceId.eq(namespace_id))
+            .filter(InboundFilterColumn::Active.eq(true))
+            .all(&*self.db)
+            .await
+            .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
+
+        let filters: Arc<Vec<InboundFilter>> = Arc::new(
+            filters
+                .into_iter()
+                .filter_map(|filter| {
+                    let filter_type = InboundFilterType::parse(&filter.filter_type)?;
+                    // Patterns are validated when saved, a bad one only disables its filter
+                    InboundFilter::compile(filter.id, filter_type, filter.tag_key, &filter.pattern)
+                        .map_err(|err| warn!("Ignoring inbound filter {}: {}", filter.id, err))
+                        .ok()
+                })
+                .collect(),
+        );
+
+        self.cache
+            .write()
+            .unwrap()
+            .insert(namespace_id, (Instant::now(), filters.clone()));
+
+        Ok(filters)
+    }
+
+    fn invalidate(&self, namespace_id: Uuid) {
+        self.cache.write().unwrap().remove(&namespace_id);
+    }
+
+    /// Adds the number of events each filter dropped to its counter.
+    pub async fn record_dropped(&self, dropped: HashMap<Uuid, i64>) -> Result<(), ServerError> {
+        for (filter_id, count) in dropped {
+            InboundFilterEntity::update_many()
+                .col_expr(
+                    InboundFilterColumn::DroppedCount,
+                    Expr::col(InboundFilterColumn::DroppedCount).add(count),
+                )
+                .filter(InboundFilterColumn::Id.eq(filter_id))
+                .exec(&*self.db)
+                .await
+                .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
+        }
+
+        Ok(())
+    }
+
+    async fn find_filter(
+        &self,
+        namespace_id: Uuid,
+        filter_id: Uuid,
+    ) -> Result<InboundFilterModel, ServerError> {
+        InboundFilterEntity::find()
+            .filter(InboundFilterColumn::Id.eq(filter_id))
+            .filter(InboundFilterColumn::NamespaceId.eq(namespace_id))
+            .one(&*self.db)
+            .await
+            .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?
+            .ok_or(ServerError::QueryError(QueryError::InboundFilterNotFound))
+    }
+}
+
+fn validate_filter(
+    filter_type: InboundFilterType,
+    tag_key: &Option<String>,
+    pattern: &str,
+) -> Result<(), RequestError> {
+    let missing_tag_key = filter_type == InboundFilterType::Tag
+        && tag_key.as_deref().is_none_or(|key| key.trim().is_empty());
+    if missing_tag_key
+        || pattern.is_empty()
+        || pattern.chars().count() > MAX_FILTER_PATTERN_LENGTH
+        || InboundFilter::compile(Uuid::nil(), filter_type, tag_key.clone(), pattern).is_err()
+    {
+        return Err(RequestError::InvalidInboundFilter);
+    }
+
+    Ok(())
+}
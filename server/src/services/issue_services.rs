@@ -0,0 +1,167 @@
+use chrono::Utc;
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::{
+    entity::prelude::*, DatabaseConnection, EntityTrait, IntoActiveModel, QueryOrder, QuerySelect,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::config::Config;
+use crate::models::error_model::Entity as ErrorEntity;
+use crate::models::issue_model::Entity as IssueEntity;
+use crate::models::release_model::{
+    Column as ReleaseColumn, Entity as ReleaseEntity, Model as ReleaseModel,
+};
+use crate::shared::utils::errors::{ExternalError, QueryError, ServerError};
+use shared_types::error_dtos::ErrorMetaDTO;
+use shared_types::issue_dtos::{IssueDTO, UpdateIssueDTO};
+
+pub struct IssueService {
+    pub db: Arc<DatabaseConnection>,
+    pub configs: Arc<Config>,
+}
+
+impl IssueService {
+    pub fn new(db: Arc<DatabaseConnection>, configs: Arc<Config>) -> Result<Self, ServerError> {
+        Ok(Self { db, configs })
+    }
+
+    pub async fn get_issues_by_namespace(
+        &self,
+        namespace_id: Uuid,
+        offset: u64,
+        limit: u64,
+    ) -> Result<Vec<IssueDTO>, ServerError> {
+        let db = &*self.db;
+
+        let issues = IssueEntity::find()
+            .filter(<IssueEntity as EntityTrait>::Column::NamespaceId.eq(namespace_id))
+            .order_by_desc(<IssueEntity as EntityTrait>::Column::LastSeen)
+            .offset(offset)
+            .limit(limit)
+            .all(db)
+            .await
+            .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
+
+        Ok(issues.into_iter().map(IssueDTO::from).collect())
+    }
+
+    pub async fn get_issue_by_id(&self, issue_id: Uuid) -> Result<IssueDTO, ServerError> {
+        let db = &*self.db;
+
+        let issue = IssueEntity::find_by_id(issue_id)
+            .one(db)
+            .await
+            .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?
+            .ok_or(ServerError::QueryError(QueryError::IssueNotFound))?;
+
+        Ok(IssueDTO::from(issue))
+    }
+
+    /// Looks up which namespace an issue belongs to, for permission checks.
+    pub async fn get_issue_namespace_id(&self, issue_id: Uuid) -> Result<Uuid, ServerError> {
+        IssueEntity::find_by_id(issue_id)
+            .select_only()
+            .column(<IssueEntity as EntityTrait>::Column::NamespaceId)
+            .into_tuple()
+            .one(&*self.db)
+            .await
+            .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?
+            .ok_or(ServerError::QueryError(QueryError::IssueNotFound))
+    }
+
+    pub async fn update_issue(&self, issue: UpdateIssueDTO) -> Result<IssueDTO, ServerError> {
+        let db = &*self.db;
+        let now = Utc::now();
+
+        let found_issue = IssueEntity::find_by_id(issue.id)
+            .one(db)
+            .await
+            .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?
+            .ok_or(ServerError::QueryError(QueryError::IssueNotFound))?;
+
+        let namespace_id = found_issue.namespace_id;
+        let mut update_issue = found_issue.into_active_model();
+
+        if let Some(resolved) = issue.resolved {
+            update_issue.resolved = sea_orm::Set(resolved);
+            update_issue.regression = sea_orm::Set(false);
+
+            // Remember which release shipped the fix so later releases can reopen it
+            let resolved_in_release = issue.resolved_in_release.filter(|_| resolved);
+            if let Some(release) = &resolved_in_release {
+                let new_release = ReleaseModel {
+                    id: Uuid::new_v4(),
+                    namespace_id,
+                    version: release.clone(),
+                    first_seen: now,
+                    last_seen: now,
+                    created_at: now,
+                    updated_at: now,
+                };
+
+                ReleaseEntity::insert(new_release.into_active_model())
+                    .on_conflict(
+                        OnConflict::columns([ReleaseColumn::NamespaceId, ReleaseColumn::Version])
+                            .update_column(ReleaseColumn::UpdatedAt)
+                            .to_owned(),
+                    )
+                    .exec(db)
+                    .await
+                    .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
+            }
+            update_issue.resolved_in_release = sea_orm::Set(resolved_in_release);
+
+            // Resolving an issue resolves every occurrence grouped under it
+            ErrorEntity::update_many()
+                .col_expr(
+                    <ErrorEntity as EntityTrait>::Column::Resolved,
+                    Expr::value(resolved),
+                )
+                .col_expr(
+                    <ErrorEntity as EntityTrait>::Column::UpdatedAt,
+                    Expr::value(now),
+                )
+                .filter(<ErrorEntity as EntityTrait>::Column::IssueId.eq(issue.id))
+                .exec(db)
+                .await
+                .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
+        }
+
+        update_issue.updated_at = sea_orm::Set(now);
+
+        let updated_issue = update_issue
+            .update(db)
+            .await
+            .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
+
+        Ok(IssueDTO::from(updated_issue))
+    }
+
+    pub async fn get_errors_by_issue(
+        &self,
+        issue_id: Uuid,
+        offset: u64,
+        limit: u64,
+    ) -> Result<Vec<ErrorMetaDTO>, ServerError> {
+        let db = &*self.db;
+
+        let errors = ErrorEntity::find()
+            .filter(<ErrorEntity as EntityTrait>::Column::IssueId.eq(issue_id))
+            .order_by_desc(<ErrorEntity as EntityTrait>::Column::CreatedAt)
+            .offset(offset)
+            .limit(limit)
+            .all(db)
+            .await
+            .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
+
+        Ok(errors
+            .into_iter()
+            .map(|error| ErrorMetaDTO {
+                id: error.id,
+                resolved: error.resolved,
+                created_at: error.created_at,
+            })
+            .collect())
+    }
+}
@@ -8,15 +8,27 @@ pub use user_services::*;
 pub mod auth_services;
 pub use auth_services::*;
 
+pub mod artifact_services;
+pub use artifact_services::*;
+
+pub mod attachment_services;
+pub use attachment_services::*;
+
 pub mod bug_report_services;
 pub use bug_report_services::*;
 
 pub mod feature_request_services;
 pub use feature_request_services::*;
 
+pub mod inbound_filter_services;
+pub use inbound_filter_services::*;
+
 pub mod tag_services;
 pub use tag_services::*;
 
+pub mod issue_services;
+pub use issue_services::*;
+
 pub mod namespace_alerts_services;
 pub use namespace_alerts_services::*;
 
@@ -31,12 +43,19 @@ pub use notification_services::*;
 
 use crate::config::Config;
 use crate::shared::utils::errors::ServerError;
+use crate::shared::utils::quota::QuotaTracker;
 
 pub struct Services {
+    // Shared with the error service, which symbolicates stack traces at ingest
+    pub artifact_service: Arc<artifact_services::ArtifactService>,
+    pub attachment_service: attachment_services::AttachmentService,
     pub auth_service: auth_services::AuthService,
     pub bug_report_service: bug_report_services::BugReportService,
     pub error_service: error_services::ErrorService,
     pub feature_request_service: feature_request_services::FeatureRequestService,
+    // Shared with the error service, which applies the filters at ingest
+    pub inbound_filter_service: Arc<inbound_filter_services::InboundFilterService>,
+    pub issue_service: issue_services::IssueService,
     pub namespace_service: namespace_services::NamespaceService,
     pub namespace_alerts_services: namespace_alerts_services::NamespaceAlertsService,
     pub notification_service: notification_services::NotificationService,
@@ -48,11 +67,17 @@ pub fn init_services(
     db_pool: Arc<DatabaseConnection>,
     config: Arc<Config>,
 ) -> Result<Services, Box<dyn Error>> {
-    let namespace_service =
-        namespace_services::NamespaceService::new(Arc::clone(&db_pool), Arc::clone(&config))
-            .map_err(|_| {
-                ServerError::ServiceInitError("Namespace service failed to initialize".to_string())
-            })?;
+    // Ingestion counters, read by the namespace service to report usage
+    let quota_tracker = QuotaTracker::new(Arc::clone(&db_pool));
+
+    let namespace_service = namespace_services::NamespaceService::new(
+        Arc::clone(&db_pool),
+        Arc::clone(&config),
+        Arc::clone(&quota_tracker),
+    )
+    .map_err(|_| {
+        ServerError::ServiceInitError("Namespace service failed to initialize".to_string())
+    })?;
 
     let namespace_alerts_services = namespace_alerts_services::NamespaceAlertsService::new(
         Arc::clone(&db_pool),
@@ -80,10 +105,34 @@ pub fn init_services(
                 )
             })?;
 
-    let error_service =
-        error_services::ErrorService::new(Arc::clone(&db_pool), Arc::clone(&config)).map_err(
-            |_| ServerError::ServiceInitError("Error services failed to initialize".to_string()),
-        )?;
+    let artifact_service =
+        artifact_services::ArtifactService::new(Arc::clone(&db_pool), Arc::clone(&config))
+            .map_err(|_| {
+                ServerError::ServiceInitError("Artifact services failed to initialize".to_string())
+            })?;
+    let artifact_service = Arc::new(artifact_service);
+
+    let attachment_service =
+        attachment_services::AttachmentService::new(Arc::clone(&db_pool), Arc::clone(&config))
+            .map_err(|_| {
+                ServerError::ServiceInitError(
+                    "Attachment services failed to initialize".to_string(),
+                )
+            })?;
+
+    let inbound_filter_service = Arc::new(inbound_filter_services::InboundFilterService::new(
+        Arc::clone(&db_pool),
+        Arc::clone(&config),
+    ));
+
+    let error_service = error_services::ErrorService::new(
+        Arc::clone(&db_pool),
+        Arc::clone(&config),
+        Arc::clone(&artifact_service),
+        Arc::clone(&inbound_filter_service),
+        Arc::clone(&quota_tracker),
+    )
+    .map_err(|_| ServerError::ServiceInitError("Error services failed to initialize".to_string()))?;
 
     let tag_service = tag_services::TagService::new(Arc::clone(&db_pool), Arc::clone(&config))
         .map_err(|_| {
@@ -106,7 +155,14 @@ pub fn init_services(
         ServerError::ServiceInitError("Feature request services failed to initialize".to_string())
     })?;
 
+    let issue_service = issue_services::IssueService::new(Arc::clone(&db_pool), Arc::clone(&config))
+        .map_err(|_| {
+            ServerError::ServiceInitError("Issue services failed to initialize".to_string())
+        })?;
+
     Ok(Services {
+        artifact_service,
+        attachment_service,
         namespace_service,
         namespace_alerts_services,
         user_service,
@@ -116,5 +172,7 @@ pub fn init_services(
         tag_service,
         notification_service,
         feature_request_service,
+        inbound_filter_service,
+        issue_service,
     })
 }
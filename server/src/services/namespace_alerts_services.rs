@@ -15,10 +15,18 @@ use crate::models::namespace_alerts_model::{
 use crate::models::user_model::Entity as UserEntity;
 use crate::models::user_namespace_junction_model::Entity as UserNamespaceJunctionEntity;
 use crate::models::user_profile_model::Entity as UserProfileEntity;
-use crate::shared::utils::errors::{ExternalError, QueryError, ServerError};
+use crate::shared::utils::alerting::delivery::AlertChannel;
+use crate::shared::utils::alerting::validate_condition;
+use crate::shared::utils::alerting::webhook::{
+    generate_webhook_secret, send_webhook, validate_webhook_url, webhook_payload, webhook_target,
+};
+use crate::shared::utils::errors::{ExternalError, QueryError, RequestError, ServerError};
+use crate::shared::utils::slack::{redact_slack_webhook_url, validate_slack_webhook_url};
+use shared_types::error_dtos::ErrorLevel;
 use shared_types::namespace_alert_dtos::{
-    CreateNamespaceAlertRequestDTO, NamespaceAlertSubscriptionRequestDTO, ShortNamespaceAlertDTO,
-    UpdateNamespaceAlertRequestDTO,
+    AlertConditionDTO, AlertWebhookEvent, CreateNamespaceAlertRequestDTO,
+    CreatedNamespaceAlertDTO, NamespaceAlertSubscriptionRequestDTO, ShortNamespaceAlertDTO,
+    UpdateNamespaceAlertRequestDTO, UpdatedNamespaceAlertDTO, WebhookTestResultDTO,
 };
 use shared_types::user_dtos::{MemberListDTO, ShortUserProfileDTO};
 
@@ -32,19 +40,94 @@ impl NamespaceAlertsService {
         Ok(Self { db, configs })
     }
 
+    // Unknown methods are refused here so alert evaluation never meets one
+    fn validate_alert(
+        alert_method: Option<&String>,
+        conditions: Option<&AlertConditionDTO>,
+        cooldown: Option<i64>,
+        recovery_condition: Option<&AlertConditionDTO>,
+    ) -> Result<(), ServerError> {
+        if let Some(alert_method) = alert_method {
+            if AlertChannel::parse(alert_method).is_none() {
+                return Err(ServerError::QueryError(QueryError::AlertTypeNotFound));
+            }
+        }
+        for condition in [conditions, recovery_condition].into_iter().flatten() {
+            validate_condition(condition).map_err(ServerError::RequestError)?;
+        }
+        if cooldown.is_some_and(|cooldown| cooldown <= 0) {
+            return Err(ServerError::RequestError(
+                RequestError::InvalidAlertCooldown,
+            ));
+        }
+        Ok(())
+    }
+
+    // Checked against the alert as it will be stored, the method and URL can change separately
+    async fn validate_webhook(
+        alert_method: &str,
+        webhook_url: Option<&str>,
+    ) -> Result<(), ServerError> {
+        if AlertChannel::parse(alert_method) != Some(AlertChannel::Webhook) {
+            return Ok(());
+        }
+
+        let webhook_url = webhook_url.ok_or(RequestError::InvalidWebhookUrl)?;
+        validate_webhook_url(webhook_url)
+            .await
+            .map_err(ServerError::RequestError)
+    }
+
+    fn validate_slack(
+        alert_method: &str,
+        slack_webhook_url: Option<&str>,
+    ) -> Result<(), ServerError> {
+        // A URL set on another method is still checked, it's used if the method changes
+        match (AlertChannel::parse(alert_method), slack_webhook_url) {
+            (_, Some(slack_webhook_url)) => {
+                validate_slack_webhook_url(slack_webhook_url).map_err(ServerError::RequestError)
+            }
+            (Some(AlertChannel::Slack), None) => Err(ServerError::RequestError(
+                RequestError::InvalidSlackWebhookUrl,
+            )),
+            _ => Ok(()),
+        }
+    }
+
     pub async fn create_namespace_alert(
         &self,
         new_namespace_alert: CreateNamespaceAlertRequestDTO,
-    ) -> Result<Uuid, ServerError> {
+    ) -> Result<CreatedNamespaceAlertDTO, ServerError> {
         let db = &*self.db;
 
+        Self::validate_alert(
+            Some(&new_namespace_alert.alert_method),
+            new_namespace_alert.conditions.as_ref(),
+            new_namespace_alert.cooldown,
+            new_namespace_alert.recovery_condition.as_ref(),
+        )?;
+        Self::validate_webhook(
+            &new_namespace_alert.alert_method,
+            new_namespace_alert.webhook_url.as_deref(),
+        )
+        .await?;
+        Self::validate_slack(
+            &new_namespace_alert.alert_method,
+            new_namespace_alert.slack_webhook_url.as_deref(),
+        )?;
+
         let uid = Uuid::new_v4();
+        let webhook_secret = new_namespace_alert
+            .webhook_url
+            .as_ref()
+            .map(|_| generate_webhook_secret());
         let now = chrono::Utc::now();
 
         let namespace_alert = NamespaceAlertModel {
             id: uid,
             namespace_id: new_namespace_alert.namespace_id,
             discord_channel_id: new_namespace_alert.discord_channel_id,
+            slack_webhook_url: new_namespace_alert.slack_webhook_url,
             alert_method: new_namespace_alert.alert_method,
             triggered: false,
             path: new_namespace_alert.path,
@@ -56,6 +139,24 @@ impl NamespaceAlertsService {
             unresolved_time_threshold: new_namespace_alert.unresolved_time_threshold,
             rate_threshold: new_namespace_alert.rate_threshold,
             rate_time_window: new_namespace_alert.rate_time_window,
+            min_level: new_namespace_alert
+                .min_level
+                .map(|level| level.as_str().to_string()),
+            conditions: new_namespace_alert
+                .conditions
+                .map(serde_json::to_value)
+                .transpose()
+                .map_err(ExternalError::from)?,
+            cooldown: new_namespace_alert.cooldown,
+            recovery_condition: new_namespace_alert
+                .recovery_condition
+                .map(serde_json::to_value)
+                .transpose()
+                .map_err(ExternalError::from)?,
+            notify_recovery: new_namespace_alert.notify_recovery.unwrap_or(false),
+            triggered_at: None,
+            webhook_url: new_namespace_alert.webhook_url,
+            webhook_secret: webhook_secret.clone(),
             created_at: now,
             updated_at: now,
         }
@@ -65,7 +166,10 @@ impl NamespaceAlertsService {
             return Err(ServerError::ExternalError(ExternalError::DB(err)));
         }
 
-        Ok(uid)
+        Ok(CreatedNamespaceAlertDTO {
+            id: uid,
+            webhook_secret,
+        })
     }
 
     pub async fn delete_namespace_alert(&self, alert_id: Uuid) -> Result<(), ServerError> {
@@ -110,6 +214,10 @@ impl NamespaceAlertsService {
                 namespace_id: alert.namespace_id,
                 alert_method: alert.alert_method.clone(),
                 discord_channel_id: alert.discord_channel_id.clone(),
+                slack_webhook_url: alert
+                    .slack_webhook_url
+                    .as_deref()
+                    .map(redact_slack_webhook_url),
                 triggered: alert.triggered,
                 path: alert.path.clone(),
                 line: alert.line.clone(),
@@ -120,6 +228,19 @@ impl NamespaceAlertsService {
                 unresolved_time_threshold: alert.unresolved_time_threshold,
                 rate_threshold: alert.rate_threshold,
                 rate_time_window: alert.rate_time_window,
+                min_level: alert.min_level.as_deref().and_then(ErrorLevel::parse),
+                conditions: alert
+                    .conditions
+                    .clone()
+                    .and_then(|conditions| serde_json::from_value(conditions).ok()),
+                cooldown: alert.cooldown,
+                recovery_condition: alert
+                    .recovery_condition
+                    .clone()
+                    .and_then(|recovery| serde_json::from_value(recovery).ok()),
+                notify_recovery: alert.notify_recovery,
+                triggered_at: alert.triggered_at,
+                webhook_url: alert.webhook_url.clone(),
             });
         });
 
@@ -157,6 +278,10 @@ impl NamespaceAlertsService {
                 id: alert.id,
                 namespace_id: alert.namespace_id,
                 discord_channel_id: alert.discord_channel_id.clone(),
+                slack_webhook_url: alert
+                    .slack_webhook_url
+                    .as_deref()
+                    .map(redact_slack_webhook_url),
                 alert_method: alert.alert_method.clone(),
                 triggered: alert.triggered,
                 path: alert.path.clone(),
@@ -168,6 +293,19 @@ impl NamespaceAlertsService {
                 unresolved_time_threshold: alert.unresolved_time_threshold,
                 rate_threshold: alert.rate_threshold,
                 rate_time_window: alert.rate_time_window,
+                min_level: alert.min_level.as_deref().and_then(ErrorLevel::parse),
+                conditions: alert
+                    .conditions
+                    .clone()
+                    .and_then(|conditions| serde_json::from_value(conditions).ok()),
+                cooldown: alert.cooldown,
+                recovery_condition: alert
+                    .recovery_condition
+                    .clone()
+                    .and_then(|recovery| serde_json::from_value(recovery).ok()),
+                notify_recovery: alert.notify_recovery,
+                triggered_at: alert.triggered_at,
+                webhook_url: alert.webhook_url.clone(),
             });
         });
 
@@ -238,26 +376,77 @@ impl NamespaceAlertsService {
     pub async fn update_namespace_alert(
         &self,
         alert_id: Uuid,
-        updated_namespace_alert: UpdateNamespaceAlertRequestDTO,
-    ) -> Result<(), ServerError> {
+        mut updated_namespace_alert: UpdateNamespaceAlertRequestDTO,
+    ) -> Result<UpdatedNamespaceAlertDTO, ServerError> {
         let db = &*self.db;
         let now = chrono::Utc::now();
 
+        Self::validate_alert(
+            updated_namespace_alert.alert_method.as_ref(),
+            updated_namespace_alert.conditions.as_ref(),
+            updated_namespace_alert.cooldown.flatten(),
+            updated_namespace_alert.recovery_condition.as_ref().and_then(Option::as_ref),
+        )?;
+
         let namespace_alert = NamespaceAlertEntity::find()
             .filter(<NamespaceAlertEntity as EntityTrait>::Column::Id.eq(alert_id))
             .one(db)
             .await;
 
-        let mut updated_alert = match namespace_alert {
-            Ok(Some(alert)) => alert.into_active_model(),
+        let found_alert = match namespace_alert {
+            Ok(Some(alert)) => alert,
             Ok(None) => return Err(ServerError::QueryError(QueryError::NamespaceAlertNotFound)),
             Err(err) => return Err(ServerError::ExternalError(ExternalError::DB(err))),
         };
 
+        Self::validate_webhook(
+            updated_namespace_alert
+                .alert_method
+                .as_deref()
+                .unwrap_or(&found_alert.alert_method),
+            updated_namespace_alert
+                .webhook_url
+                .as_deref()
+                .or(found_alert.webhook_url.as_deref()),
+        )
+        .await?;
+        // Alerts are listed with the Slack URL redacted, sending that back keeps the stored one
+        if updated_namespace_alert.slack_webhook_url.is_some()
+            && updated_namespace_alert.slack_webhook_url
+                == found_alert
+                    .slack_webhook_url
+                    .as_deref()
+                    .map(redact_slack_webhook_url)
+        {
+            updated_namespace_alert.slack_webhook_url = None;
+        }
+        Self::validate_slack(
+            updated_namespace_alert
+                .alert_method
+                .as_deref()
+                .unwrap_or(&found_alert.alert_method),
+            updated_namespace_alert
+                .slack_webhook_url
+                .as_deref()
+                .or(found_alert.slack_webhook_url.as_deref()),
+        )?;
+
+        // Alerts switched to a webhook get a secret, existing ones only on request
+        let rotate_webhook_secret = updated_namespace_alert.rotate_webhook_secret == Some(true)
+            || (found_alert.webhook_secret.is_none()
+                && (updated_namespace_alert.webhook_url.is_some()
+                    || found_alert.webhook_url.is_some()));
+
+        let mut updated_alert = found_alert.into_active_model();
+
         if let Some(alert_method) = updated_namespace_alert.alert_method {
             updated_alert.alert_method = ActiveValue::Set(alert_method);
         }
 
+        if let Some(slack_webhook_url) = updated_namespace_alert.slack_webhook_url {
+            updated_alert.slack_webhook_url = ActiveValue::Set(Some(slack_webhook_url));
+        }
+
         if let Some(path) = updated_namespace_alert.path {
             updated_alert.path = ActiveValue::Set(Some(path));
         }
@@ -295,13 +484,47 @@ impl NamespaceAlertsService {
             updated_alert.rate_time_window = ActiveValue::Set(Some(rate_time_window));
         }
 
+        if let Some(min_level) = updated_namespace_alert.min_level {
+            updated_alert.min_level = ActiveValue::Set(Some(min_level.as_str().to_string()));
+        }
+
+        if let Some(conditions) = updated_namespace_alert.conditions {
+            let conditions = serde_json::to_value(conditions).map_err(ExternalError::from)?;
+            updated_alert.conditions = ActiveValue::Set(Some(conditions));
+        }
+
+        if let Some(cooldown) = updated_namespace_alert.cooldown {
+            updated_alert.cooldown = ActiveValue::Set(cooldown);
+        }
+
+        if let Some(recovery_condition) = updated_namespace_alert.recovery_condition {
+            let recovery_condition = recovery_condition
+                .map(serde_json::to_value)
+                .transpose()
+                .map_err(ExternalError::from)?;
+            updated_alert.recovery_condition = ActiveValue::Set(recovery_condition);
+        }
+
+        if let Some(notify_recovery) = updated_namespace_alert.notify_recovery {
+            updated_alert.notify_recovery = ActiveValue::Set(notify_recovery);
+        }
+
+        if let Some(webhook_url) = updated_namespace_alert.webhook_url {
+            updated_alert.webhook_url = ActiveValue::Set(Some(webhook_url));
+        }
+
+        let webhook_secret = rotate_webhook_secret.then(generate_webhook_secret);
+        if let Some(webhook_secret) = &webhook_secret {
+            updated_alert.webhook_secret = ActiveValue::Set(Some(webhook_secret.clone()));
+        }
+
         updated_alert.updated_at = ActiveValue::Set(now);
 
         if let Err(err) = updated_alert.update(db).await {
             return Err(ServerError::ExternalError(ExternalError::DB(err)));
         }
 
-        Ok(())
+        Ok(UpdatedNamespaceAlertDTO { webhook_secret })
     }
 
     pub async fn get_subscribed_users_by_namespace_alert_id(
@@ -377,4 +600,47 @@ impl NamespaceAlertsService {
 
         Ok(())
     }
+
+    /// The namespace an alert belongs to, for permission checks on the alert.
+    pub async fn get_alert_namespace_id(&self, alert_id: Uuid) -> Result<Uuid, ServerError> {
+        let found_alert = NamespaceAlertEntity::find()
+            .filter(<NamespaceAlertEntity as EntityTrait>::Column::Id.eq(alert_id))
+            .one(&*self.db)
+            .await
+            .map_err(ExternalError::from)?
+            .ok_or(ServerError::QueryError(QueryError::NamespaceAlertNotFound))?;
+
+        Ok(found_alert.namespace_id)
+    }
+
+    /// Sends a signed test payload to the alert's webhook and returns what the target
+    /// answered, whatever its status.
+    pub async fn test_webhook(&self, alert_id: Uuid) -> Result<WebhookTestResultDTO, ServerError> {
+        let db = &*self.db;
+
+        let found_alert = NamespaceAlertEntity::find()
+            .filter(<NamespaceAlertEntity as EntityTrait>::Column::Id.eq(alert_id))
+            .one(db)
+            .await
+            .map_err(ExternalError::from)?
+            .ok_or(ServerError::QueryError(QueryError::NamespaceAlertNotFound))?;
+
+        let (webhook_url, secret) = webhook_target(&found_alert)?;
+        let payload = webhook_payload(
+            db,
+            &found_alert,
+            AlertWebhookEvent::Test,
+            None,
+            chrono::Utc::now(),
+        )
+        .await?;
+
+        let response = send_webhook(webhook_url, secret, &payload).await?;
+
+        Ok(WebhookTestResultDTO {
+            status: response.status,
+            attempts: response.attempts,
+            body: response.body,
+        })
+    }
 }
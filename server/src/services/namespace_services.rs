@@ -2,9 +2,10 @@ use bcrypt::{hash, DEFAULT_COST};
 use chrono::Utc;
 use futures::stream::{FuturesUnordered, TryStreamExt};
 use log::info;
+use sea_orm::sea_query::Query;
 use sea_orm::{
-    entity::prelude::*, ActiveValue, DatabaseConnection, EntityTrait, IntoActiveModel, QuerySelect,
-    TransactionTrait,
+    entity::prelude::*, ActiveValue, Condition, DatabaseConnection, EntityTrait, IntoActiveModel,
+    QueryOrder, QuerySelect, TransactionTrait,
 };
 use shared_types::user_dtos::MemberListDTO;
 use std::collections::{HashMap, HashSet};
@@ -15,18 +16,24 @@ use crate::config::Config;
 use crate::managers::notification_manager::{self, NotificationServer};
 use crate::models::error_model::Entity as ErrorEntity;
 use crate::models::error_tag_model::Entity as TagEntity;
+use crate::models::issue_model::Entity as IssueEntity;
 use crate::models::namespace_model::{Entity as NamespaceEntity, Model as NamespaceModel};
 use crate::models::notification_model::{Entity as NotificationEntity, Model as NotificationModel};
 use crate::models::user_model::Entity as UserEntity;
 use crate::models::user_namespace_junction_model::{
     Entity as UserNamespaceJunctionEntity, Model as UserNamespaceJunctionModel,
 };
+use crate::shared::utils::context_filter::ContextFilter;
 use crate::shared::utils::errors::{ExternalError, QueryError, RequestError, ServerError};
+use crate::shared::utils::quota::QuotaTracker;
 use crate::shared::utils::role::{get_perms, string_to_role, Permission, Role, RoleRules};
+use crate::shared::utils::scrub::{compile_rules, MAX_SCRUB_PATTERN_LENGTH, MAX_SCRUB_RULES};
 use shared_types::error_dtos::{
-    AggregateIndividualErrorDTO, AggregatedResult, GetAggregatedLineErrorDTO,
-    GetAggregatedMessageErrorDTO, GetAggregatedStatusErrorDTO, TagAggregatedErrorDTO,
+    AggregateIndividualErrorDTO, AggregatedResult, ErrorLevel, GetAggregatedLevelErrorDTO,
+    GetAggregatedLineErrorDTO, GetAggregatedMessageErrorDTO, GetAggregatedStatusErrorDTO,
+    TagAggregatedErrorDTO,
 };
+use shared_types::issue_dtos::IssueDTO;
 use shared_types::namespace_dtos::{
     GetNamespaceResponseDTO, GetNamespacesByUserResponseDTO, InviteUserRequestDTO,
     ShortNamespaceDTO, UpdateNamespaceDTO,
@@ -37,11 +44,20 @@ use shared_types::tag_dtos::ShortTagNoIdDTO;
 pub struct NamespaceService {
     pub db: Arc<DatabaseConnection>,
     pub configs: Arc<Config>,
+    pub quota_tracker: Arc<QuotaTracker>,
 }
 
 impl NamespaceService {
-    pub fn new(db: Arc<DatabaseConnection>, configs: Arc<Config>) -> Result<Self, ServerError> {
-        Ok(Self { db, configs })
+    pub fn new(
+        db: Arc<DatabaseConnection>,
+        configs: Arc<Config>,
+        quota_tracker: Arc<QuotaTracker>,
+    ) -> Result<Self, ServerError> {
+        Ok(Self {
+            db,
+            configs,
+            quota_tracker,
+        })
     }
 
     pub async fn get_all_namespaces(&self) -> Result<Vec<ShortNamespaceDTO>, ServerError> {
@@ -104,6 +120,11 @@ impl NamespaceService {
             client_secret: Uuid::new_v4(),
             created_at: now,
             updated_at: now,
+            quota_per_minute: None,
+            quota_per_day: None,
+            sample_rate: 1.0,
+            scrub_patterns: Json::Array(vec![]),
+            scrub_tag_keys: Json::Array(vec![]),
         }
         .into_active_model();
 
@@ -167,6 +188,12 @@ impl NamespaceService {
 
         match result {
             Ok(Some(namespace)) => {
+                let usage = self
+                    .quota_tracker
+                    .usage(namespace.id, Utc::now())
+                    .await
+                    .map_err(ExternalError::from)?;
+
                 let hash_secret = hash(namespace.client_secret.to_string(), hash_cost).unwrap();
                 let namespace_dto = GetNamespaceResponseDTO {
                     id: namespace.id,
@@ -177,6 +204,14 @@ impl NamespaceService {
                     client_secret: hash_secret,
                     created_at: namespace.created_at,
                     updated_at: namespace.updated_at,
+                    quota_per_minute: namespace.quota_per_minute,
+                    quota_per_day: namespace.quota_per_day,
+                    sample_rate: namespace.sample_rate,
+                    usage,
+                    scrub_patterns: serde_json::from_value(namespace.scrub_patterns)
+                        .unwrap_or_default(),
+                    scrub_tag_keys: serde_json::from_value(namespace.scrub_tag_keys)
+                        .unwrap_or_default(),
                 };
                 Ok(namespace_dto)
             }
@@ -248,6 +283,31 @@ impl NamespaceService {
         let db: &DatabaseConnection = &*self.db;
         let config = &*self.configs;
 
+        let negative_quota = [
+            update_namespace_object.quota_per_minute,
+            update_namespace_object.quota_per_day,
+        ]
+        .into_iter()
+        .flatten()
+        .any(|quota| quota < 0);
+        let invalid_sample_rate = update_namespace_object
+            .sample_rate
+            .is_some_and(|rate| !(0.0..=1.0).contains(&rate));
+        if negative_quota || invalid_sample_rate {
+            return Err(ServerError::RequestError(RequestError::InvalidQuota));
+        }
+
+        if let Some(patterns) = &update_namespace_object.scrub_patterns {
+            let too_long = patterns
+                .iter()
+                .any(|pattern| pattern.chars().count() > MAX_SCRUB_PATTERN_LENGTH);
+            if patterns.len() > MAX_SCRUB_RULES || too_long || compile_rules(patterns).is_err() {
+                return Err(ServerError::RequestError(
+                    RequestError::InvalidScrubbingRule,
+                ));
+            }
+        }
+
         let transaction = db.begin().await.map_err(ExternalError::from)?;
         let now = Utc::now();
 
@@ -338,6 +398,27 @@ impl NamespaceService {
             };
         };
 
+        // A quota of 0 lifts the limit
+        if let Some(quota) = update_namespace_object.quota_per_minute {
+            namespace.quota_per_minute = ActiveValue::Set((quota > 0).then_some(quota));
+        };
+
+        if let Some(quota) = update_namespace_object.quota_per_day {
+            namespace.quota_per_day = ActiveValue::Set((quota > 0).then_some(quota));
+        };
+
+        if let Some(sample_rate) = update_namespace_object.sample_rate {
+            namespace.sample_rate = ActiveValue::Set(sample_rate);
+        };
+
+        if let Some(patterns) = update_namespace_object.scrub_patterns {
+            namespace.scrub_patterns = ActiveValue::Set(Json::from(patterns));
+        };
+
+        if let Some(tag_keys) = update_namespace_object.scrub_tag_keys {
+            namespace.scrub_tag_keys = ActiveValue::Set(Json::from(tag_keys));
+        };
+
         namespace.updated_at = ActiveValue::Set(now);
 
         match namespace.update(&transaction).await {
@@ -450,10 +531,12 @@ impl NamespaceService {
     pub async fn get_errors_by_namespace_id(
         &self,
         namespace_id: Uuid,
+        filters: Condition,
     ) -> Result<Vec<AggregateIndividualErrorDTO>, ServerError> {
         let db: &DatabaseConnection = &*self.db;
         let errors = ErrorEntity::find()
             .filter(<ErrorEntity as EntityTrait>::Column::NamespaceId.eq(namespace_id))
+            .filter(filters)
             .all(db)
             .await
             .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
@@ -491,6 +574,7 @@ impl NamespaceService {
                         resolved: error.resolved,
                         created_at: error.created_at,
                         tags,
+                        level: ErrorLevel::parse(&error.level).unwrap_or_default(),
                         updated_at: error.updated_at,
                     }) as Result<AggregateIndividualErrorDTO, ServerError>
                 }
@@ -634,14 +718,88 @@ impl NamespaceService {
         }
     }
 
+    fn aggregate_by_level(
+        &self,
+        errors: &[AggregateIndividualErrorDTO],
+    ) -> Vec<GetAggregatedLevelErrorDTO> {
+        let mut grouped_errors_by_level: HashMap<ErrorLevel, GetAggregatedLevelErrorDTO> =
+            HashMap::new();
+        let mut unique_users: HashMap<ErrorLevel, HashSet<String>> = HashMap::new();
+
+        for error in errors {
+            let entry = grouped_errors_by_level
+                .entry(error.level)
+                .or_insert_with(|| GetAggregatedLevelErrorDTO {
+                    level: error.level,
+                    user_affected_count: 0,
+                    error_count: 0,
+                    aggregated_tags: vec![],
+                });
+
+            entry.error_count += 1;
+
+            let user_set = unique_users.entry(error.level).or_default();
+            user_set.insert(error.user_affected.clone());
+            entry.user_affected_count = user_set.len() as i32;
+
+            if let Some(tags) = &error.tags {
+                let tag_set: HashSet<ShortTagNoIdDTO> =
+                    entry.aggregated_tags.iter().cloned().collect();
+                let new_tags: HashSet<ShortTagNoIdDTO> = tags.iter().cloned().collect();
+                entry.aggregated_tags = tag_set.union(&new_tags).cloned().collect();
+            }
+        }
+
+        grouped_errors_by_level.into_values().collect()
+    }
+
     pub async fn get_errors_by_namespace_with_pagination(
         &self,
         namespace_id: Uuid,
         group_by: String,
+        context_filters: Vec<ContextFilter>,
+        level: Option<ErrorLevel>,
         offset: usize,
         limit: usize,
     ) -> Result<AggregatedResult, ServerError> {
-        let errors = self.get_errors_by_namespace_id(namespace_id).await?;
+        let filters = error_condition(&context_filters, level);
+
+        // Issues are already grouped and counted at ingest, so page them in the database
+        if group_by == "issue" {
+            let mut issues = IssueEntity::find()
+                .filter(<IssueEntity as EntityTrait>::Column::NamespaceId.eq(namespace_id));
+            // Keep the issues with at least one error matching the filters
+            if !filters.is_empty() {
+                issues = issues.filter(
+                    <IssueEntity as EntityTrait>::Column::Id.in_subquery(
+                        Query::select()
+                            .column(<ErrorEntity as EntityTrait>::Column::IssueId)
+                            .from(ErrorEntity)
+                            .and_where(
+                                <ErrorEntity as EntityTrait>::Column::NamespaceId.eq(namespace_id),
+                            )
+                            .cond_where(filters)
+                            .to_owned(),
+                    ),
+                );
+            }
+
+            let issues = issues
+                .order_by_desc(<IssueEntity as EntityTrait>::Column::LastSeen)
+                .offset(offset as u64)
+                .limit(limit as u64)
+                .all(&*self.db)
+                .await
+                .map_err(|err| ServerError::ExternalError(ExternalError::DB(err)))?;
+
+            return Ok(AggregatedResult::ByIssue(
+                issues.into_iter().map(IssueDTO::from).collect(),
+            ));
+        }
+
+        let errors = self
+            .get_errors_by_namespace_id(namespace_id, filters)
+            .await?;
         let (
             grouped_errors_by_message,
             // grouped_errors_by_status,
@@ -651,6 +809,13 @@ impl NamespaceService {
         ) = self.aggregate_errors(&errors, &group_by).await;
 
         match group_by.as_str() {
+            "level" => {
+                let mut level_results = self.aggregate_by_level(&errors);
+                level_results.sort_by_key(|level| std::cmp::Reverse(level.error_count));
+                let paginated_results =
+                    level_results.into_iter().skip(offset).take(limit).collect();
+                Ok(AggregatedResult::ByLevel(paginated_results))
+            }
             "tags" => {
                 let mut tag_results: Vec<TagAggregatedErrorDTO> =
                     tag_aggregations.into_iter().map(|(_, v)| v).collect();
@@ -967,3 +1132,15 @@ impl NamespaceService {
         Ok(())
     }
 }
+
+// Every filter has to match, no filters match everything
+fn error_condition(context_filters: &[ContextFilter], level: Option<ErrorLevel>) -> Condition {
+    context_filters
+        .iter()
+        .fold(Condition::all(), |condition, filter| {
+            condition.add(filter.condition())
+        })
+        .add_option(
+            level.map(|level| <ErrorEntity as EntityTrait>::Column::Level.eq(level.as_str())),
+        )
+}
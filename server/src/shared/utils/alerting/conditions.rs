@@ -0,0 +1,147 @@
+use async_trait::async_trait;
+use chrono::Duration;
+use sea_orm::{ColumnTrait, DbErr, EntityTrait, PaginatorTrait, QueryFilter};
+
+use super::{AlertCondition, ConditionContext};
+use crate::models::error_model::Entity as ErrorEntity;
+
+/// Fires when more than `threshold` errors in scope arrived within the window.
+pub struct CountInWindow {
+    pub threshold: i32,
+    // Milliseconds
+    pub time_window: i64,
+}
+
+#[async_trait]
+impl AlertCondition for CountInWindow {
+    async fn evaluate(&self, ctx: &ConditionContext<'_>) -> Result<Option<String>, DbErr> {
+        let count = count_since(ctx, self.time_window).await?;
+
+        Ok((count > self.threshold as u64).then(|| {
+            format!(
+                "{} errors in the last {} minutes, threshold {}",
+                count,
+                self.time_window / 60000,
+                self.threshold
+            )
+        }))
+    }
+}
+
+/// Fires when more than `threshold` errors in scope arrived per minute, averaged over
+/// the window.
+pub struct RateInWindow {
+    pub threshold: i32,
+    // Milliseconds
+    pub time_window: i64,
+}
+
+#[async_trait]
+impl AlertCondition for RateInWindow {
+    async fn evaluate(&self, ctx: &ConditionContext<'_>) -> Result<Option<String>, DbErr> {
+        let count = count_since(ctx, self.time_window).await?;
+        let minutes = self.time_window as f64 / 60000.0;
+        let rate = count as f64 / minutes;
+
+        Ok((rate > self.threshold as f64).then(|| {
+            format!(
+                "{:.2} errors per minute over the last {} minutes, threshold {}",
+                rate,
+                self.time_window / 60000,
+                self.threshold
+            )
+        }))
+    }
+}
+
+/// Fires when an error in scope has stayed unresolved for longer than `threshold`.
+pub struct UnresolvedAge {
+    // Milliseconds
+    pub threshold: i64,
+}
+
+#[async_trait]
+impl AlertCondition for UnresolvedAge {
+    async fn evaluate(&self, ctx: &ConditionContext<'_>) -> Result<Option<String>, DbErr> {
+        let cutoff = ctx.now - Duration::milliseconds(self.threshold);
+        let query = ctx
+            .scope
+            .filter(ctx.namespace_id, ErrorEntity::find())
+            .filter(<ErrorEntity as EntityTrait>::Column::Resolved.eq(false))
+            .filter(<ErrorEntity as EntityTrait>::Column::CreatedAt.lte(cutoff));
+        let count = query.count(ctx.db).await?;
+
+        Ok((count > 0).then(|| {
+            format!(
+                "{} errors unresolved for more than {} minutes",
+                count,
+                self.threshold / 60000
+            )
+        }))
+    }
+
+    fn is_time_based(&self) -> bool {
+        true
+    }
+}
+
+/// Fires when every condition fires (AND).
+pub struct AllOf {
+    pub conditions: Vec<Box<dyn AlertCondition>>,
+}
+
+#[async_trait]
+impl AlertCondition for AllOf {
+    async fn evaluate(&self, ctx: &ConditionContext<'_>) -> Result<Option<String>, DbErr> {
+        let mut details = Vec::with_capacity(self.conditions.len());
+        for condition in &self.conditions {
+            match condition.evaluate(ctx).await? {
+                Some(detail) => details.push(detail),
+                // Skip the remaining queries, the group can no longer fire
+                None => return Ok(None),
+            }
+        }
+
+        Ok(Some(details.join(" and ")))
+    }
+
+    fn is_time_based(&self) -> bool {
+        self.conditions
+            .iter()
+            .any(|condition| condition.is_time_based())
+    }
+}
+
+/// Fires when at least one condition fires (OR).
+pub struct AnyOf {
+    pub conditions: Vec<Box<dyn AlertCondition>>,
+}
+
+#[async_trait]
+impl AlertCondition for AnyOf {
+    async fn evaluate(&self, ctx: &ConditionContext<'_>) -> Result<Option<String>, DbErr> {
+        for condition in &self.conditions {
+            if let Some(detail) = condition.evaluate(ctx).await? {
+                return Ok(Some(detail));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn is_time_based(&self) -> bool {
+        self.conditions
+            .iter()
+            .any(|condition| condition.is_time_based())
+    }
+}
+
+async fn count_since(ctx: &ConditionContext<'_>, time_window: i64) -> Result<u64, DbErr> {
+    let window_start = ctx.now - Duration::milliseconds(time_window);
+
+    ctx.scope
+        .filter(ctx.namespace_id, ErrorEntity::find())
+        .filter(<ErrorEntity as EntityTrait>::Column::CreatedAt.gt(window_start))
+        .count(ctx.db)
+        .await
+}
@@ -0,0 +1,404 @@
+use chrono::{DateTime, Utc};
+use log::{error, warn};
+use sea_orm::sea_query::Expr;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, IntoActiveModel, QueryFilter};
+use shared_types::namespace_alert_dtos::AlertWebhookEvent;
+use shared_types::notification_dtos::NotificationDTO;
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::config::Config;
+use crate::managers::notification_manager::NotificationServer;
+use crate::models::namespace_alert_user_junction_model::Entity as NamespaceAlertUserJunctionEntity;
+use crate::models::namespace_alerts_model::{
+    Entity as NamespaceAlertEntity, Model as NamespaceAlertModel,
+};
+use crate::models::notification_model::{Entity as NotificationEntity, Model as NotificationModel};
+use crate::models::user_model::{Entity as UserEntity, Model as UserModel};
+use crate::models::user_profile_model::{Entity as UserProfileEntity, Model as UserProfileModel};
+use crate::shared::utils::discord::DiscordHandler;
+use crate::shared::utils::errors::{ExternalError, QueryError, RequestError, ServerError};
+use crate::shared::utils::mailing::{send_email, send_email_sms, EmailContent};
+use crate::shared::utils::slack::{send_slack_alert, SlackAlert};
+
+use super::webhook::{send_webhook, webhook_payload, webhook_target};
+
+/// Where an alert is sent on top of the in-app notification every subscriber gets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertChannel {
+    Email,
+    Discord,
+    Sms,
+    Slack,
+    Webhook,
+}
+
+impl AlertChannel {
+    pub fn parse(alert_method: &str) -> Option<Self> {
+        match alert_method {
+            "email" => Some(AlertChannel::Email),
+            "discord" => Some(AlertChannel::Discord),
+            "sms" => Some(AlertChannel::Sms),
+            "slack" => Some(AlertChannel::Slack),
+            "webhook" => Some(AlertChannel::Webhook),
+            _ => None,
+        }
+    }
+}
+
+/// What happened to an alert, deciding the message its subscribers get.
+#[derive(Debug, Clone, Copy)]
+pub enum AlertEvent<'a> {
+    // Carries what tripped the alert
+    Fired(&'a str),
+    Recovered,
+}
+
+/// Sends fired and recovered alerts to their subscribers and channel.
+pub struct AlertDelivery<'a> {
+    pub db: &'a DatabaseConnection,
+    pub configs: &'a Config,
+    pub discord_handler: &'a DiscordHandler,
+    pub notification_manager: &'a Arc<NotificationServer>,
+    pub service_mapping: &'a HashMap<&'static str, &'static str>,
+}
+
+impl AlertDelivery<'_> {
+    /// Marks the alert triggered and delivers it. The alert is only claimed while it is
+    /// still untriggered, so ingestion workers and the scheduler on any instance never
+    /// send it twice. The claim is only released again when the alert reached nobody,
+    /// so a later evaluation can retry it. Returns whether this call delivered it.
+    pub async fn fire(
+        &self,
+        alert: &NamespaceAlertModel,
+        details: &str,
+        now: DateTime<Utc>,
+    ) -> Result<bool, ServerError> {
+        if !self.set_triggered(alert.id, true, now).await? {
+            return Ok(false);
+        }
+
+        // Logged here rather than returned, the error can't be held across the release
+        let delivered = match self.deliver(alert, AlertEvent::Fired(details), now).await {
+            Ok(delivered) => delivered,
+            Err(err) => {
+                error!("Failed to deliver alert {}: {}", alert.id, err);
+                false
+            }
+        };
+        if !delivered {
+            self.set_triggered(alert.id, false, now).await?;
+        }
+
+        Ok(delivered)
+    }
+
+    /// Re-arms a triggered alert, sending a recovered message on its channel when
+    /// `notify` is set. Returns false when another worker re-armed it first.
+    pub async fn rearm(
+        &self,
+        alert: &NamespaceAlertModel,
+        notify: bool,
+        now: DateTime<Utc>,
+    ) -> Result<bool, ServerError> {
+        if !self.set_triggered(alert.id, false, now).await? {
+            return Ok(false);
+        }
+
+        // The alert stays armed if the message fails, it can still fire again
+        if notify {
+            self.deliver(alert, AlertEvent::Recovered, now).await?;
+        }
+
+        Ok(true)
+    }
+
+    /// Notifies the alert's subscribers and sends the event over the alert's channel.
+    /// Returns whether the alert reached anyone. Once a subscriber was notified in-app a
+    /// failing channel is only logged, the alert did go out.
+    pub async fn deliver(
+        &self,
+        alert: &NamespaceAlertModel,
+        event: AlertEvent<'_>,
+        now: DateTime<Utc>,
+    ) -> Result<bool, ServerError> {
+        let subscribers = self.subscribers(alert.id).await?;
+
+        let mut notified = false;
+        for (user, _) in &subscribers {
+            match self.notify(alert, event, user, now).await {
+                Ok(()) => notified = true,
+                Err(err) => error!(
+                    "Failed to notify user {} of alert {}: {}",
+                    user.id, alert.id, err
+                ),
+            }
+        }
+
+        match self.send(alert, event, &subscribers, now).await {
+            Ok(sent) => Ok(notified || sent),
+            Err(err) if notified => {
+                error!(
+                    "Alert {} notified its subscribers in-app but failed to send over {}: {}",
+                    alert.id, alert.alert_method, err
+                );
+                Ok(true)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    // Sends the event over the alert's channel, returning whether it reached anyone there
+    async fn send(
+        &self,
+        alert: &NamespaceAlertModel,
+        event: AlertEvent<'_>,
+        subscribers: &[(UserModel, Option<UserProfileModel>)],
+        now: DateTime<Utc>,
+    ) -> Result<bool, ServerError> {
+        match AlertChannel::parse(&alert.alert_method) {
+            Some(AlertChannel::Email) => {
+                let content = match event {
+                    AlertEvent::Fired(details) => EmailContent {
+                        greeting: "Alert Notice!".to_string(),
+                        main_message: format!("An error alert has been triggered for a namespace you are subscribed to by the ID of {}", alert.namespace_id),
+                        body: format!("Please log in to your account to view the error details and resolve the issue. {}", self.configs.domain),
+                        dynamic_content: Some(format!("Alert Details:\n{}.\nTime Triggered: {}", details, now)),
+                    },
+                    AlertEvent::Recovered => EmailContent {
+                        greeting: "Alert Recovered".to_string(),
+                        main_message: format!("An error alert has recovered for a namespace you are subscribed to by the ID of {}", alert.namespace_id),
+                        body: format!("The alert is armed again and will notify you if the issue comes back. {}", self.configs.domain),
+                        dynamic_content: Some(format!("Time Recovered: {}", now)),
+                    },
+                };
+                let subject = match event {
+                    AlertEvent::Fired(_) => "Error Alert",
+                    AlertEvent::Recovered => "Error Alert Recovered",
+                };
+                for (user, _) in subscribers {
+                    send_email(self.configs, &user.email, subject, &content)?;
+                }
+            }
+            Some(AlertChannel::Sms) => {
+                let content = match event {
+                    AlertEvent::Fired(details) => format!("\nAlert {} has been triggered for a namespace you are subscribed to by the ID of {}.\n{}.\n\nPlease log in to your account to view the error details and resolve the issue. {}", alert.id, alert.namespace_id, details, self.configs.domain),
+                    AlertEvent::Recovered => format!("\nAlert {} has recovered for a namespace you are subscribed to by the ID of {} and is armed again. {}", alert.id, alert.namespace_id, self.configs.domain),
+                };
+                for (_, profile) in subscribers {
+                    let Some(profile) = profile else {
+                        continue;
+                    };
+                    if let (Some(phone_number), Some(phone_provider)) = (
+                        profile.phone_number.as_deref(),
+                        profile.phone_provider.as_deref(),
+                    ) {
+                        // Unknown providers have no email-to-SMS gateway
+                        if !self.service_mapping.contains_key(phone_provider) {
+                            continue;
+                        }
+                        send_email_sms(
+                            self.configs,
+                            self.service_mapping,
+                            phone_number,
+                            phone_provider,
+                            &content,
+                        )?;
+                    }
+                }
+            }
+            Some(AlertChannel::Discord) => {
+                // Posted once to the channel, however many users are subscribed
+                if subscribers.is_empty() {
+                    return Ok(false);
+                }
+
+                let channel_id: u64 = alert
+                    .discord_channel_id
+                    .as_deref()
+                    .and_then(|channel_id| channel_id.parse().ok())
+                    .ok_or(ServerError::QueryError(QueryError::DiscordChannelNotFound))?;
+                let content = match event {
+                    AlertEvent::Fired(details) => format!("Alert {} has been triggered for a namespace you are subscribed to by the ID of {}: {}.", alert.id, alert.namespace_id, details),
+                    AlertEvent::Recovered => format!("Alert {} has recovered for a namespace you are subscribed to by the ID of {} and is armed again.", alert.id, alert.namespace_id),
+                };
+
+                self.discord_handler
+                    .send_discord_alert(channel_id, &content)
+                    .await?;
+            }
+            Some(AlertChannel::Slack) => {
+                // Posted once to the channel whether or not anyone is subscribed in-app
+                let slack_webhook_url = alert
+                    .slack_webhook_url
+                    .as_deref()
+                    .ok_or(RequestError::InvalidSlackWebhookUrl)?;
+                let (title, webhook_event, details) = match event {
+                    AlertEvent::Fired(details) => {
+                        ("Alert triggered", AlertWebhookEvent::Fired, Some(details))
+                    }
+                    AlertEvent::Recovered => {
+                        ("Alert recovered", AlertWebhookEvent::Recovered, None)
+                    }
+                };
+                // Same snapshot of the alert's scope the webhook method sends
+                let snapshot = webhook_payload(self.db, alert, webhook_event, details, now).await?;
+                let namespace = format!(
+                    "{} ({})",
+                    snapshot.namespace.service_name, snapshot.namespace.environment_type
+                );
+                let details = details.map(str::to_string).unwrap_or_else(|| {
+                    format!("Alert {} has recovered and is armed again.", alert.id)
+                });
+
+                send_slack_alert(
+                    slack_webhook_url,
+                    &SlackAlert {
+                        title,
+                        details: &details,
+                        namespace: &namespace,
+                        error_message: snapshot.error.as_ref().map(|error| error.message.as_str()),
+                        location: snapshot
+                            .error
+                            .as_ref()
+                            .map(|error| format!("{}:{}", error.path, error.line)),
+                        count: snapshot.counts.last_hour,
+                        dashboard_url: &self.configs.domain,
+                        namespace_url: format!(
+                            "{}/namespace/{}",
+                            self.configs.domain, alert.namespace_id
+                        ),
+                    },
+                )
+                .await?;
+
+                return Ok(true);
+            }
+            Some(AlertChannel::Webhook) => {
+                // The target is reached whether or not anyone is subscribed in-app
+                let (webhook_url, secret) = webhook_target(alert)?;
+                let (webhook_event, details) = match event {
+                    AlertEvent::Fired(details) => (AlertWebhookEvent::Fired, Some(details)),
+                    AlertEvent::Recovered => (AlertWebhookEvent::Recovered, None),
+                };
+                let payload = webhook_payload(self.db, alert, webhook_event, details, now).await?;
+
+                let response = send_webhook(webhook_url, secret, &payload).await?;
+                if !response.is_success() {
+                    return Err(ServerError::ExternalError(ExternalError::Webhook(
+                        response.status,
+                    )));
+                }
+
+                return Ok(true);
+            }
+            None => {
+                warn!(
+                    "Alert {} has unknown alert method {}, only notifying in-app",
+                    alert.id, alert.alert_method
+                );
+                return Ok(false);
+            }
+        }
+
+        Ok(!subscribers.is_empty())
+    }
+
+    // Flips the triggered flag, returning false when it already had that value
+    async fn set_triggered(
+        &self,
+        alert_id: Uuid,
+        triggered: bool,
+        now: DateTime<Utc>,
+    ) -> Result<bool, ServerError> {
+        let mut update = NamespaceAlertEntity::update_many().col_expr(
+            <NamespaceAlertEntity as EntityTrait>::Column::Triggered,
+            Expr::value(triggered),
+        );
+        // Kept on re-arm, the cooldown only counts while triggered
+        if triggered {
+            update = update.col_expr(
+                <NamespaceAlertEntity as EntityTrait>::Column::TriggeredAt,
+                Expr::value(now),
+            );
+        }
+
+        let result = update
+            .filter(<NamespaceAlertEntity as EntityTrait>::Column::Id.eq(alert_id))
+            .filter(<NamespaceAlertEntity as EntityTrait>::Column::Triggered.eq(!triggered))
+            .exec(self.db)
+            .await
+            .map_err(ExternalError::from)?;
+
+        Ok(result.rows_affected == 1)
+    }
+
+    async fn subscribers(
+        &self,
+        alert_id: Uuid,
+    ) -> Result<Vec<(UserModel, Option<UserProfileModel>)>, ServerError> {
+        let user_ids: Vec<Uuid> = NamespaceAlertUserJunctionEntity::find()
+            .filter(
+                <NamespaceAlertUserJunctionEntity as EntityTrait>::Column::NamespaceAlertId
+                    .eq(alert_id),
+            )
+            .all(self.db)
+            .await
+            .map_err(ExternalError::from)?
+            .into_iter()
+            .map(|junction| junction.user_id)
+            .collect();
+
+        if user_ids.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let users = UserEntity::find()
+            .filter(<UserEntity as EntityTrait>::Column::Id.is_in(user_ids))
+            .find_also_related(UserProfileEntity)
+            .all(self.db)
+            .await
+            .map_err(ExternalError::from)?;
+
+        Ok(users)
+    }
+
+    async fn notify(
+        &self,
+        alert: &NamespaceAlertModel,
+        event: AlertEvent<'_>,
+        user: &UserModel,
+        now: DateTime<Utc>,
+    ) -> Result<(), ServerError> {
+        let notification = NotificationDTO {
+            id: Uuid::new_v4(),
+            user_id: user.id,
+            title: "Alert Notification".to_string(),
+            text: match event {
+                AlertEvent::Fired(_) => format!(
+                    "Alert {} has been triggered for a namespace you are subscribed to by the ID of {}",
+                    alert.id, alert.namespace_id
+                ),
+                AlertEvent::Recovered => format!(
+                    "Alert {} has recovered for a namespace you are subscribed to by the ID of {}",
+                    alert.id, alert.namespace_id
+                ),
+            },
+            source: "HiGuard Alert System".to_string(),
+            is_read: false,
+            created_at: now,
+        };
+
+        NotificationEntity::insert(NotificationModel::from(notification.clone()).into_active_model())
+            .exec(self.db)
+            .await
+            .map_err(ExternalError::from)?;
+
+        self.notification_manager
+            .broadcast_notification(notification, &user.id)
+            .await;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,486 @@
+pub mod conditions;
+pub mod delivery;
+pub mod webhook;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use log::error;
+use sea_orm::{ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, Select};
+use uuid::Uuid;
+
+use crate::models::error_model::Entity as ErrorEntity;
+use crate::models::namespace_alerts_model::Model as NamespaceAlertModel;
+use crate::services::error_services::PreparedError;
+use crate::shared::utils::errors::RequestError;
+use shared_types::error_dtos::ErrorLevel;
+use shared_types::namespace_alert_dtos::AlertConditionDTO;
+
+use conditions::{AllOf, AnyOf, CountInWindow, RateInWindow, UnresolvedAge};
+
+// Deeper trees are almost certainly a mistake and cost a query per leaf
+const MAX_CONDITION_DEPTH: usize = 4;
+const MAX_CONDITIONS_PER_GROUP: usize = 10;
+
+/// Which errors an alert watches. Every condition only counts errors in scope.
+#[derive(Debug, Clone, Default)]
+pub struct AlertScope {
+    pub path: Option<String>,
+    pub line: Option<i32>,
+    pub message: Option<String>,
+    // Errors below this level are out of scope, None watches every level
+    pub min_level: Option<ErrorLevel>,
+}
+
+impl AlertScope {
+    pub fn from_alert(alert: &NamespaceAlertModel) -> Self {
+        Self {
+            path: alert.path.clone(),
+            line: alert.line,
+            message: alert.message.clone(),
+            min_level: alert.min_level.as_deref().and_then(ErrorLevel::parse),
+        }
+    }
+
+    /// Whether an incoming error is one the alert watches.
+    pub fn matches(&self, prepared: &PreparedError) -> bool {
+        self.path
+            .as_ref()
+            .is_none_or(|path| *path == prepared.stack_trace_info.file_path)
+            && self
+                .line
+                .is_none_or(|line| line == prepared.stack_trace_info.line_number)
+            && self
+                .message
+                .as_ref()
+                .is_none_or(|message| *message == prepared.request.message)
+            && self.min_level.is_none_or(|min_level| {
+                prepared
+                    .request
+                    .level
+                    .unwrap_or_default()
+                    .is_at_least(min_level)
+            })
+    }
+
+    /// Narrows a query over a namespace's stored errors to the ones in scope.
+    pub fn filter(&self, namespace_id: Uuid, query: Select<ErrorEntity>) -> Select<ErrorEntity> {
+        let mut query = query.filter(
+            <ErrorEntity as EntityTrait>::Column::NamespaceId.eq(namespace_id),
+        );
+
+        if let Some(path) = &self.path {
+            query = query.filter(<ErrorEntity as EntityTrait>::Column::Path.eq(path.clone()));
+        }
+        if let Some(line) = self.line {
+            query = query.filter(<ErrorEntity as EntityTrait>::Column::Line.eq(line));
+        }
+        if let Some(message) = &self.message {
+            query = query.filter(<ErrorEntity as EntityTrait>::Column::Message.eq(message.clone()));
+        }
+        if let Some(min_level) = self.min_level {
+            query = query.filter(
+                <ErrorEntity as EntityTrait>::Column::Level
+                    .is_in(min_level.and_above().iter().map(ErrorLevel::as_str)),
+            );
+        }
+
+        query
+    }
+}
+
+/// What a condition is evaluated against.
+pub struct ConditionContext<'a> {
+    pub db: &'a DatabaseConnection,
+    pub namespace_id: Uuid,
+    pub scope: &'a AlertScope,
+    pub now: DateTime<Utc>,
+}
+
+/// A check an alert fires on. Conditions only look at stored errors, so they are
+/// evaluated the same way whichever channel the alert is delivered on.
+#[async_trait]
+pub trait AlertCondition: Send + Sync {
+    /// Describes what tripped the condition, or returns None while it holds.
+    async fn evaluate(&self, ctx: &ConditionContext<'_>) -> Result<Option<String>, DbErr>;
+
+    /// Whether the condition can start firing while no errors arrive, e.g. because an
+    /// error grows old. These are also evaluated by the alert scheduler.
+    fn is_time_based(&self) -> bool {
+        false
+    }
+}
+
+/// An alert's scope together with the condition tree it fires on.
+pub struct AlertRule {
+    pub scope: AlertScope,
+    pub condition: Box<dyn AlertCondition>,
+    // Re-arms the fired alert once it stops firing
+    pub recovery: Option<Box<dyn AlertCondition>>,
+}
+
+impl AlertRule {
+    /// Builds the rule from the alert's stored conditions, falling back to its threshold
+    /// columns. Returns None for an alert with nothing to check or conditions that don't
+    /// parse, which are logged since the alert can't fire until they're fixed.
+    pub fn from_alert(alert: &NamespaceAlertModel) -> Option<Self> {
+        let condition = match &alert.conditions {
+            Some(conditions) => parse_condition(alert, "conditions", conditions)?,
+            None => legacy_condition(alert)?,
+        };
+
+        // Without its recovery condition the alert still fires, it then waits for a reset
+        let recovery = alert
+            .recovery_condition
+            .as_ref()
+            .and_then(|recovery| parse_condition(alert, "recovery condition", recovery));
+
+        Some(Self {
+            scope: AlertScope::from_alert(alert),
+            condition: build_condition(&condition),
+            recovery: recovery.as_ref().map(build_condition),
+        })
+    }
+}
+
+fn parse_condition(
+    alert: &NamespaceAlertModel,
+    field: &str,
+    condition: &serde_json::Value,
+) -> Option<AlertConditionDTO> {
+    match serde_json::from_value(condition.clone()) {
+        Ok(condition) => Some(condition),
+        Err(err) => {
+            error!("Alert {} has malformed {}, skipping it: {}", alert.id, field, err);
+            None
+        }
+    }
+}
+
+pub fn build_condition(condition: &AlertConditionDTO) -> Box<dyn AlertCondition> {
+    match condition {
+        AlertConditionDTO::CountInWindow {
+            threshold,
+            time_window,
+        } => Box::new(CountInWindow {
+            threshold: *threshold,
+            time_window: *time_window,
+        }),
+        AlertConditionDTO::Rate {
+            threshold,
+            time_window,
+        } => Box::new(RateInWindow {
+            threshold: *threshold,
+            time_window: *time_window,
+        }),
+        AlertConditionDTO::UnresolvedAge { threshold } => Box::new(UnresolvedAge {
+            threshold: *threshold,
+        }),
+        AlertConditionDTO::All { conditions } => Box::new(AllOf {
+            conditions: conditions.iter().map(build_condition).collect(),
+        }),
+        AlertConditionDTO::Any { conditions } => Box::new(AnyOf {
+            conditions: conditions.iter().map(build_condition).collect(),
+        }),
+    }
+}
+
+/// Turns the threshold columns alerts had before conditions into a condition. Every
+/// threshold that is set can fire the alert on its own.
+pub fn legacy_condition(alert: &NamespaceAlertModel) -> Option<AlertConditionDTO> {
+    let mut conditions = Vec::new();
+
+    if let (Some(threshold), Some(time_window)) = (alert.count_threshold, alert.time_window) {
+        conditions.push(AlertConditionDTO::CountInWindow {
+            threshold,
+            time_window,
+        });
+    }
+    if let Some(threshold) = alert.unresolved_time_threshold {
+        conditions.push(AlertConditionDTO::UnresolvedAge { threshold });
+    }
+    if let (Some(threshold), Some(time_window)) = (alert.rate_threshold, alert.rate_time_window) {
+        conditions.push(AlertConditionDTO::Rate {
+            threshold,
+            time_window,
+        });
+    }
+
+    match conditions.len() {
+        0 => None,
+        1 => conditions.pop(),
+        _ => Some(AlertConditionDTO::Any { conditions }),
+    }
+}
+
+/// Rejects condition trees that could never fire or are too large to evaluate per error.
+pub fn validate_condition(condition: &AlertConditionDTO) -> Result<(), RequestError> {
+    validate_condition_at(condition, 1)
+}
+
+fn validate_condition_at(condition: &AlertConditionDTO, depth: usize) -> Result<(), RequestError> {
+    let valid = match condition {
+        AlertConditionDTO::CountInWindow {
+            threshold,
+            time_window,
+        }
+        | AlertConditionDTO::Rate {
+            threshold,
+            time_window,
+        } => *threshold >= 0 && *time_window > 0,
+        AlertConditionDTO::UnresolvedAge { threshold } => *threshold > 0,
+        AlertConditionDTO::All { conditions } | AlertConditionDTO::Any { conditions } => {
+            if depth >= MAX_CONDITION_DEPTH
+                || conditions.is_empty()
+                || conditions.len() > MAX_CONDITIONS_PER_GROUP
+            {
+                false
+            } else {
+                return conditions
+                    .iter()
+                    .try_for_each(|condition| validate_condition_at(condition, depth + 1));
+            }
+        }
+    };
+
+    match valid {
+        true => Ok(()),
+        false => Err(RequestError::InvalidAlertCondition),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    // Answers without a query, so groups can be evaluated without a database
+    struct Fixed {
+        fires: Option<&'static str>,
+        time_based: bool,
+    }
+
+    #[async_trait]
+    impl AlertCondition for Fixed {
+        async fn evaluate(&self, _ctx: &ConditionContext<'_>) -> Result<Option<String>, DbErr> {
+            Ok(self.fires.map(str::to_string))
+        }
+
+        fn is_time_based(&self) -> bool {
+            self.time_based
+        }
+    }
+
+    fn fires(detail: &'static str) -> Box<dyn AlertCondition> {
+        Box::new(Fixed {
+            fires: Some(detail),
+            time_based: false,
+        })
+    }
+
+    fn holds() -> Box<dyn AlertCondition> {
+        Box::new(Fixed {
+            fires: None,
+            time_based: false,
+        })
+    }
+
+    async fn evaluate(condition: &dyn AlertCondition) -> Option<String> {
+        let db = DatabaseConnection::Disconnected;
+        let scope = AlertScope::default();
+        let ctx = ConditionContext {
+            db: &db,
+            namespace_id: Uuid::nil(),
+            scope: &scope,
+            now: Utc::now(),
+        };
+        condition.evaluate(&ctx).await.unwrap()
+    }
+
+    fn alert() -> NamespaceAlertModel {
+        let now = Utc::now();
+        NamespaceAlertModel {
+            id: Uuid::new_v4(),
+            namespace_id: Uuid::new_v4(),
+            alert_method: "email".to_string(),
+            discord_channel_id: None,
+            triggered: false,
+            path: None,
+            line: None,
+            message: None,
+            stack_trace: None,
+            count_threshold: None,
+            time_window: None,
+            unresolved_time_threshold: None,
+            rate_threshold: None,
+            rate_time_window: None,
+            min_level: None,
+            conditions: None,
+            cooldown: None,
+            recovery_condition: None,
+            notify_recovery: false,
+            triggered_at: None,
+            webhook_url: None,
+            webhook_secret: None,
+            slack_webhook_url: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[tokio::test]
+    async fn all_of_fires_only_when_every_condition_fires() {
+        let all = AllOf {
+            conditions: vec![fires("a"), fires("b")],
+        };
+        assert_eq!(evaluate(&all).await.as_deref(), Some("a and b"));
+
+        let all = AllOf {
+            conditions: vec![fires("a"), holds()],
+        };
+        assert_eq!(evaluate(&all).await, None);
+    }
+
+    #[tokio::test]
+    async fn any_of_fires_on_the_first_firing_condition() {
+        let any = AnyOf {
+            conditions: vec![holds(), fires("b"), fires("c")],
+        };
+        assert_eq!(evaluate(&any).await.as_deref(), Some("b"));
+
+        let any = AnyOf {
+            conditions: vec![holds(), holds()],
+        };
+        assert_eq!(evaluate(&any).await, None);
+    }
+
+    #[tokio::test]
+    async fn groups_nest() {
+        // (a AND (x OR c)) with x holding
+        let nested = AllOf {
+            conditions: vec![
+                fires("a"),
+                Box::new(AnyOf {
+                    conditions: vec![holds(), fires("c")],
+                }),
+            ],
+        };
+        assert_eq!(evaluate(&nested).await.as_deref(), Some("a and c"));
+    }
+
+    #[test]
+    fn groups_are_time_based_when_any_child_is() {
+        let any = AnyOf {
+            conditions: vec![
+                holds(),
+                Box::new(Fixed {
+                    fires: None,
+                    time_based: true,
+                }),
+            ],
+        };
+        assert!(any.is_time_based());
+        assert!(!AllOf {
+            conditions: vec![holds(), fires("a")],
+        }
+        .is_time_based());
+
+        let condition = build_condition(&AlertConditionDTO::All {
+            conditions: vec![
+                AlertConditionDTO::CountInWindow {
+                    threshold: 5,
+                    time_window: 60_000,
+                },
+                AlertConditionDTO::UnresolvedAge { threshold: 60_000 },
+            ],
+        });
+        assert!(condition.is_time_based());
+    }
+
+    #[test]
+    fn legacy_thresholds_become_an_any_group() {
+        let mut alert = alert();
+        assert_eq!(legacy_condition(&alert), None);
+
+        alert.count_threshold = Some(5);
+        alert.time_window = Some(60_000);
+        assert_eq!(
+            legacy_condition(&alert),
+            Some(AlertConditionDTO::CountInWindow {
+                threshold: 5,
+                time_window: 60_000,
+            })
+        );
+
+        alert.unresolved_time_threshold = Some(3_600_000);
+        assert_eq!(
+            legacy_condition(&alert),
+            Some(AlertConditionDTO::Any {
+                conditions: vec![
+                    AlertConditionDTO::CountInWindow {
+                        threshold: 5,
+                        time_window: 60_000,
+                    },
+                    AlertConditionDTO::UnresolvedAge {
+                        threshold: 3_600_000,
+                    },
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn rule_prefers_stored_conditions() {
+        let mut alert = alert();
+        alert.count_threshold = Some(5);
+        alert.time_window = Some(60_000);
+        alert.conditions = Some(json!({ "type": "unresolvedAge", "threshold": 60_000 }));
+
+        let rule = AlertRule::from_alert(&alert).unwrap();
+        assert!(rule.condition.is_time_based());
+        assert!(rule.recovery.is_none());
+    }
+
+    #[test]
+    fn rule_skips_malformed_conditions() {
+        let mut alert = alert();
+        alert.count_threshold = Some(5);
+        alert.time_window = Some(60_000);
+        alert.conditions = Some(json!({ "type": "countInWindow", "threshold": "five" }));
+        assert!(AlertRule::from_alert(&alert).is_none());
+
+        // A malformed recovery condition leaves the alert firing without it
+        alert.conditions = None;
+        alert.recovery_condition = Some(json!({ "type": "nope" }));
+        let rule = AlertRule::from_alert(&alert).unwrap();
+        assert!(rule.recovery.is_none());
+    }
+
+    #[test]
+    fn validation_rejects_empty_deep_and_negative_conditions() {
+        let count = AlertConditionDTO::CountInWindow {
+            threshold: 5,
+            time_window: 60_000,
+        };
+        assert!(validate_condition(&count).is_ok());
+
+        assert!(validate_condition(&AlertConditionDTO::Any { conditions: vec![] }).is_err());
+        assert!(validate_condition(&AlertConditionDTO::CountInWindow {
+            threshold: -1,
+            time_window: 60_000,
+        })
+        .is_err());
+        assert!(validate_condition(&AlertConditionDTO::UnresolvedAge { threshold: 0 }).is_err());
+        assert!(validate_condition(&AlertConditionDTO::All {
+            conditions: vec![count.clone(); MAX_CONDITIONS_PER_GROUP + 1],
+        })
+        .is_err());
+
+        let mut deep = count;
+        for _ in 0..MAX_CONDITION_DEPTH {
+            deep = AlertConditionDTO::All {
+                conditions: vec![deep],
+            };
+        }
+        assert!(validate_condition(&deep).is_err());
+    }
+}
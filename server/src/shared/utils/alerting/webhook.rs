@@ -0,0 +1,389 @@
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use hmac::{Hmac, Mac};
+use once_cell::sync::Lazy;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::header::CONTENT_TYPE;
+use reqwest::{redirect, Client, StatusCode, Url};
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+};
+use sha2::Sha256;
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::AlertScope;
+use crate::models::error_model::Entity as ErrorEntity;
+use crate::models::namespace_alerts_model::Model as NamespaceAlertModel;
+use crate::models::namespace_model::Entity as NamespaceEntity;
+use crate::shared::utils::errors::{ExternalError, QueryError, RequestError, ServerError};
+use shared_types::error_dtos::ErrorLevel;
+use shared_types::namespace_alert_dtos::{
+    AlertWebhookAlertDTO, AlertWebhookCountsDTO, AlertWebhookErrorDTO, AlertWebhookEvent,
+    AlertWebhookNamespaceDTO, AlertWebhookPayloadDTO, ALERT_WEBHOOK_VERSION,
+};
+
+const SIGNATURE_HEADER: &str = "X-HiGuard-Signature";
+const TIMESTAMP_HEADER: &str = "X-HiGuard-Timestamp";
+const MAX_ATTEMPTS: u32 = 3;
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+const MAX_BODY_EXCERPT: usize = 4 * 1024;
+
+// Redirects aren't followed, they could point the request at an internal address
+static WEBHOOK_CLIENT: Lazy<Client> = Lazy::new(|| {
+    Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .redirect(redirect::Policy::none())
+        .dns_resolver(Arc::new(PublicResolver))
+        .build()
+        .expect("Failed to build the webhook HTTP client")
+});
+
+/// What the target answered the last attempt with.
+#[derive(Debug, Clone)]
+pub struct WebhookResponse {
+    pub status: u16,
+    pub attempts: u32,
+    // The start of the response body, lossily decoded
+    pub body: String,
+}
+
+impl WebhookResponse {
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+/// Refuses anything but an absolute http(s) URL whose host resolves to public addresses
+/// only, so alerts can't be pointed at the server's own network.
+pub async fn validate_webhook_url(webhook_url: &str) -> Result<(), RequestError> {
+    let url = Url::parse(webhook_url).map_err(|_| RequestError::InvalidWebhookUrl)?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(RequestError::InvalidWebhookUrl);
+    }
+
+    let host = url.host_str().ok_or(RequestError::InvalidWebhookUrl)?;
+    // IPv6 hosts keep their brackets in the URL
+    let addrs: Vec<IpAddr> = match host.trim_matches(['[', ']']).parse::<IpAddr>() {
+        Ok(ip) => vec![ip],
+        Err(_) => resolve(host)
+            .await
+            .map_err(|_| RequestError::InvalidWebhookUrl)?
+            .into_iter()
+            .map(|addr| addr.ip())
+            .collect(),
+    };
+
+    if addrs.is_empty() || !addrs.into_iter().all(is_public) {
+        return Err(RequestError::InvalidWebhookUrl);
+    }
+    Ok(())
+}
+
+// Resolves for the webhook client, so a host that changed its DNS records after
+// validation still can't reach an internal address
+struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let addrs = resolve(name.as_str()).await?;
+            if addrs.iter().any(|addr| !is_public(addr.ip())) {
+                return Err(io::Error::new(
+                    io::ErrorKind::PermissionDenied,
+                    "webhook host resolves to a non-public address",
+                )
+                .into());
+            }
+            let addrs: Addrs = Box::new(addrs.into_iter());
+            Ok(addrs)
+        })
+    }
+}
+
+async fn resolve(host: &str) -> io::Result<Vec<SocketAddr>> {
+    Ok(tokio::net::lookup_host((host, 0)).await?.collect())
+}
+
+// Loopback, private, link-local (including the 169.254.169.254 metadata endpoint) and
+// other reserved ranges aren't reachable targets
+fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_v4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_public_v4(ip),
+            None => is_public_v6(ip),
+        },
+    }
+}
+
+fn is_public_v4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    !(ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_unspecified()
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        || ip.is_documentation()
+        // 0.0.0.0/8 and the 100.64.0.0/10 carrier-grade NAT range
+        || a == 0
+        || (a == 100 && (64..128).contains(&b)))
+}
+
+fn is_public_v6(ip: Ipv6Addr) -> bool {
+    let first = ip.segments()[0];
+    !(ip.is_loopback()
+        || ip.is_unspecified()
+        || ip.is_multicast()
+        // Unique local fc00::/7 and link-local fe80::/10
+        || (first & 0xfe00) == 0xfc00
+        || (first & 0xffc0) == 0xfe80)
+}
+
+pub fn generate_webhook_secret() -> String {
+    hex::encode(rand::random::<[u8; 32]>())
+}
+
+/// `sha256=` followed by the hex HMAC-SHA256 of `{timestamp}.{body}`.
+pub fn sign(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// POSTs the signed payload, retrying timeouts, connection failures, 429 and 5xx
+/// responses with exponential backoff. Only fails when no attempt got a response.
+pub async fn send_webhook(
+    webhook_url: &str,
+    secret: &str,
+    payload: &AlertWebhookPayloadDTO,
+) -> Result<WebhookResponse, ServerError> {
+    // Checked again on send, literal IPs never go through the client's resolver
+    validate_webhook_url(webhook_url).await?;
+    let body = serde_json::to_vec(payload).map_err(ExternalError::from)?;
+    let mut backoff = INITIAL_BACKOFF;
+    let mut attempt = 1;
+
+    loop {
+        // Signed per attempt so receivers can reject stale timestamps
+        let timestamp = Utc::now().timestamp();
+        let result = WEBHOOK_CLIENT
+            .post(webhook_url)
+            .header(CONTENT_TYPE, "application/json")
+            .header(TIMESTAMP_HEADER, timestamp.to_string())
+            .header(SIGNATURE_HEADER, sign(secret, timestamp, &body))
+            .body(body.clone())
+            .send()
+            .await;
+
+        let retryable = match &result {
+            Ok(response) => {
+                response.status().is_server_error()
+                    || response.status() == StatusCode::TOO_MANY_REQUESTS
+            }
+            Err(err) => err.is_timeout() || err.is_connect(),
+        };
+
+        if !retryable || attempt >= MAX_ATTEMPTS {
+            let response = result.map_err(ExternalError::from)?;
+
+            return Ok(WebhookResponse {
+                status: response.status().as_u16(),
+                attempts: attempt,
+                body: body_excerpt(response).await,
+            });
+        }
+
+        tokio::time::sleep(backoff).await;
+        backoff *= 2;
+        attempt += 1;
+    }
+}
+
+// Reads no more of the body than the excerpt keeps, a body that fails halfway is cut
+// off where it failed
+async fn body_excerpt(mut response: reqwest::Response) -> String {
+    let mut excerpt = Vec::new();
+    while excerpt.len() < MAX_BODY_EXCERPT {
+        match response.chunk().await {
+            Ok(Some(chunk)) => excerpt.extend_from_slice(&chunk),
+            _ => break,
+        }
+    }
+    excerpt.truncate(MAX_BODY_EXCERPT);
+
+    String::from_utf8_lossy(&excerpt).into_owned()
+}
+
+/// Builds the payload for an alert event, with the latest error and counts in the
+/// alert's scope.
+pub async fn webhook_payload(
+    db: &DatabaseConnection,
+    alert: &NamespaceAlertModel,
+    event: AlertWebhookEvent,
+    details: Option<&str>,
+    now: DateTime<Utc>,
+) -> Result<AlertWebhookPayloadDTO, ServerError> {
+    let namespace = NamespaceEntity::find_by_id(alert.namespace_id)
+        .one(db)
+        .await
+        .map_err(ExternalError::from)?
+        .ok_or(ServerError::QueryError(QueryError::NamespaceNotFound))?;
+
+    let scope = AlertScope::from_alert(alert);
+    let in_scope = || scope.filter(alert.namespace_id, ErrorEntity::find());
+
+    let latest_error = in_scope()
+        .order_by_desc(<ErrorEntity as EntityTrait>::Column::CreatedAt)
+        .one(db)
+        .await
+        .map_err(ExternalError::from)?;
+    let last_hour = in_scope()
+        .filter(<ErrorEntity as EntityTrait>::Column::CreatedAt.gt(now - ChronoDuration::hours(1)))
+        .count(db)
+        .await
+        .map_err(ExternalError::from)?;
+    let unresolved = in_scope()
+        .filter(<ErrorEntity as EntityTrait>::Column::Resolved.eq(false))
+        .count(db)
+        .await
+        .map_err(ExternalError::from)?;
+
+    Ok(AlertWebhookPayloadDTO {
+        version: ALERT_WEBHOOK_VERSION,
+        event,
+        sent_at: now,
+        alert: AlertWebhookAlertDTO {
+            id: alert.id,
+            details: details.map(str::to_string),
+            path: alert.path.clone(),
+            line: alert.line,
+            message: alert.message.clone(),
+            min_level: scope.min_level,
+            triggered_at: alert.triggered_at,
+        },
+        namespace: AlertWebhookNamespaceDTO {
+            id: namespace.id,
+            service_name: namespace.service_name,
+            environment_type: namespace.environment_type,
+        },
+        error: latest_error.map(|error| AlertWebhookErrorDTO {
+            id: error.id,
+            issue_id: error.issue_id,
+            message: error.message,
+            path: error.path,
+            line: error.line,
+            level: ErrorLevel::parse(&error.level).unwrap_or_default(),
+            resolved: error.resolved,
+            created_at: error.created_at,
+        }),
+        counts: AlertWebhookCountsDTO {
+            last_hour,
+            unresolved,
+        },
+    })
+}
+
+/// The alert's webhook target, or an error when the alert has none.
+pub fn webhook_target(alert: &NamespaceAlertModel) -> Result<(&str, &str), QueryError> {
+    match (
+        alert.webhook_url.as_deref(),
+        alert.webhook_secret.as_deref(),
+    ) {
+        (Some(webhook_url), Some(secret)) => Ok((webhook_url, secret)),
+        _ => Err(QueryError::WebhookNotConfigured),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(addr: &str) -> IpAddr {
+        addr.parse().unwrap()
+    }
+
+    #[test]
+    fn is_public_rejects_internal_addresses() {
+        for addr in [
+            "127.0.0.1",
+            "::1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "100.127.255.254",
+            "0.0.0.0",
+            "::ffff:127.0.0.1",
+            "::ffff:169.254.169.254",
+            "fd00::1",
+            "fe80::1",
+        ] {
+            assert!(!is_public(ip(addr)), "{} should not be public", addr);
+        }
+    }
+
+    #[test]
+    fn is_public_accepts_public_addresses() {
+        for addr in [
+            "1.1.1.1",
+            "100.128.0.1",
+            "::ffff:8.8.8.8",
+            "2606:4700::1111",
+        ] {
+            assert!(is_public(ip(addr)), "{} should be public", addr);
+        }
+    }
+
+    #[tokio::test]
+    async fn validate_webhook_url_rejects_internal_and_malformed_targets() {
+        for url in [
+            "http://127.0.0.1/hook",
+            "http://[::1]/hook",
+            "https://10.0.0.5/hook",
+            "http://192.168.0.10:8080/hook",
+            "http://169.254.169.254/latest/meta-data",
+            "http://[::ffff:10.0.0.1]/hook",
+            "http://100.100.100.100/hook",
+            "ftp://8.8.8.8/hook",
+            "not a url",
+        ] {
+            assert!(
+                validate_webhook_url(url).await.is_err(),
+                "{} should be rejected",
+                url
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn validate_webhook_url_accepts_public_ips() {
+        assert!(validate_webhook_url("https://8.8.8.8/hook").await.is_ok());
+        assert!(validate_webhook_url("http://[2606:4700::1111]/hook")
+            .await
+            .is_ok());
+    }
+
+    #[test]
+    fn sign_is_hmac_sha256_of_timestamp_and_body() {
+        let signature = sign("secret", 1700000000, b"{}");
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"secret").unwrap();
+        mac.update(b"1700000000.{}");
+        assert_eq!(
+            signature,
+            format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+        );
+        assert_ne!(signature, sign("other", 1700000000, b"{}"));
+        assert_ne!(signature, sign("secret", 1700000001, b"{}"));
+        assert_ne!(signature, sign("secret", 1700000000, b"[]"));
+    }
+}
@@ -0,0 +1,46 @@
+use serde_json::{Map, Value};
+
+use shared_types::error_dtos::BreadcrumbDTO;
+
+// Server-side limits so a chatty SDK can't blow up the size of every stored error
+pub const MAX_BREADCRUMBS: usize = 100;
+pub const MAX_BREADCRUMB_MESSAGE_LENGTH: usize = 1024;
+pub const MAX_BREADCRUMB_DATA_BYTES: usize = 4096;
+
+/// Orders breadcrumbs oldest first and applies the size limits: only the most recent
+/// entries are kept, long messages are truncated and oversized data maps are replaced
+/// by a marker.
+pub fn limit_breadcrumbs(mut breadcrumbs: Vec<BreadcrumbDTO>) -> Vec<BreadcrumbDTO> {
+    breadcrumbs.sort_by_key(|breadcrumb| breadcrumb.timestamp);
+
+    let skip = breadcrumbs.len().saturating_sub(MAX_BREADCRUMBS);
+
+    breadcrumbs
+        .into_iter()
+        .skip(skip)
+        .map(|mut breadcrumb| {
+            if let Some(message) = &breadcrumb.message {
+                if message.chars().count() > MAX_BREADCRUMB_MESSAGE_LENGTH {
+                    breadcrumb.message = Some(
+                        message
+                            .chars()
+                            .take(MAX_BREADCRUMB_MESSAGE_LENGTH)
+                            .collect(),
+                    );
+                }
+            }
+
+            if let Some(data) = &breadcrumb.data {
+                let size = serde_json::to_vec(data).map(|data| data.len()).unwrap_or(0);
+                if size > MAX_BREADCRUMB_DATA_BYTES {
+                    let mut truncated = Map::new();
+                    truncated.insert("truncated".to_string(), Value::Bool(true));
+                    truncated.insert("originalSize".to_string(), Value::from(size));
+                    breadcrumb.data = Some(truncated);
+                }
+            }
+
+            breadcrumb
+        })
+        .collect()
+}
@@ -0,0 +1,69 @@
+use sea_orm::sea_query::{Expr, SimpleExpr};
+
+use crate::shared::utils::errors::RequestError;
+
+// Filters one listing request may combine, all of them have to match
+pub const MAX_CONTEXT_FILTERS: usize = 10;
+const MAX_PATH_DEPTH: usize = 5;
+const CONTEXT_KINDS: [&str; 5] = ["request", "runtime", "os", "device", "extra"];
+
+/// Matches errors whose stored context has `value` at `path`, e.g. `["request", "method"]`.
+/// Values are compared as text, so `extra.build:42` matches a number as well.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContextFilter {
+    pub path: Vec<String>,
+    pub value: String,
+}
+
+impl ContextFilter {
+    pub fn condition(&self) -> SimpleExpr {
+        // Segments are restricted to word characters and dashes, so the array literal is safe
+        Expr::cust_with_values(
+            r#""contexts" #>> CAST($1 AS text[]) = $2"#,
+            [format!("{{{}}}", self.path.join(",")), self.value.clone()],
+        )
+    }
+}
+
+/// Parses the `context` query parameter, a comma separated list of `path:value` pairs
+/// such as `request.method:POST,os.name:Linux`.
+pub fn parse_context_filters(filters: &str) -> Result<Vec<ContextFilter>, RequestError> {
+    let filters = filters
+        .split(',')
+        .filter(|filter| !filter.trim().is_empty())
+        .map(parse_context_filter)
+        .collect::<Result<Vec<ContextFilter>, RequestError>>()?;
+
+    if filters.len() > MAX_CONTEXT_FILTERS {
+        return Err(RequestError::InvalidContextFilter);
+    }
+
+    Ok(filters)
+}
+
+fn parse_context_filter(filter: &str) -> Result<ContextFilter, RequestError> {
+    let (path, value) = filter
+        .trim()
+        .split_once(':')
+        .ok_or(RequestError::InvalidContextFilter)?;
+
+    let path: Vec<String> = path.split('.').map(str::to_string).collect();
+    let valid_segment = |segment: &String| {
+        !segment.is_empty()
+            && segment
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    };
+    if path.len() < 2
+        || path.len() > MAX_PATH_DEPTH
+        || !CONTEXT_KINDS.contains(&path[0].as_str())
+        || !path.iter().all(valid_segment)
+    {
+        return Err(RequestError::InvalidContextFilter);
+    }
+
+    Ok(ContextFilter {
+        path,
+        value: value.to_string(),
+    })
+}
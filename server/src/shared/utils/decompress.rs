@@ -0,0 +1,216 @@
+use brotli::Decompressor as BrotliDecoder;
+use flate2::read::{GzDecoder, ZlibDecoder};
+use std::io::Read;
+use zstd::stream::read::Decoder as ZstdDecoder;
+
+use crate::shared::utils::errors::RequestError;
+
+// Largest ingestion body accepted after decompression
+pub const MAX_DECOMPRESSED_SIZE: usize = 5 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentEncoding {
+    Identity,
+    Gzip,
+    Deflate,
+    Brotli,
+    Zstd,
+}
+
+/// Reads a `Content-Encoding` header. Stacked encodings (`gzip, br`) aren't sent by
+/// any SDK we support and are rejected along with unknown ones.
+pub fn parse_content_encoding(header: Option<&str>) -> Result<ContentEncoding, RequestError> {
+    let Some(header) = header else {
+        return Ok(ContentEncoding::Identity);
+    };
+
+    match header.trim().to_lowercase().as_str() {
+        "" | "identity" => Ok(ContentEncoding::Identity),
+        "gzip" | "x-gzip" => Ok(ContentEncoding::Gzip),
+        "deflate" => Ok(ContentEncoding::Deflate),
+        "br" => Ok(ContentEncoding::Brotli),
+        "zstd" => Ok(ContentEncoding::Zstd),
+        _ => Err(RequestError::UnsupportedContentEncoding),
+    }
+}
+
+/// Decompresses a request body, giving up as soon as the output grows past
+/// `max_size` so a small compressed payload can't expand without bound.
+pub fn decompress(
+    encoding: ContentEncoding,
+    body: &[u8],
+    max_size: usize,
+) -> Result<Vec<u8>, RequestError> {
+    let decoder: Box<dyn Read + '_> = match encoding {
+        ContentEncoding::Identity => Box::new(body),
+        ContentEncoding::Gzip => Box::new(GzDecoder::new(body)),
+        ContentEncoding::Deflate => Box::new(ZlibDecoder::new(body)),
+        ContentEncoding::Brotli => Box::new(BrotliDecoder::new(body, 8 * 1024)),
+        ContentEncoding::Zstd => {
+            Box::new(ZstdDecoder::new(body).map_err(|_| RequestError::InvalidContentEncoding)?)
+        }
+    };
+
+    // Read one byte past the limit to tell "exactly at the limit" from "over it"
+    let mut decompressed = Vec::new();
+    decoder
+        .take(max_size as u64 + 1)
+        .read_to_end(&mut decompressed)
+        .map_err(|_| RequestError::InvalidContentEncoding)?;
+
+    if decompressed.len() > max_size {
+        return Err(RequestError::PayloadTooLarge(max_size));
+    }
+
+    Ok(decompressed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::write::{GzEncoder, ZlibEncoder};
+    use flate2::Compression;
+    use std::io::Write;
+
+    const ENCODINGS: [ContentEncoding; 5] = [
+        ContentEncoding::Identity,
+        ContentEncoding::Gzip,
+        ContentEncoding::Deflate,
+        ContentEncoding::Brotli,
+        ContentEncoding::Zstd,
+    ];
+
+    fn compress(encoding: ContentEncoding, data: &[u8]) -> Vec<u8> {
+        match encoding {
+            ContentEncoding::Identity => data.to_vec(),
+            ContentEncoding::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(data).unwrap();
+                encoder.finish().unwrap()
+            }
+            ContentEncoding::Deflate => {
+                let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(data).unwrap();
+                encoder.finish().unwrap()
+            }
+            ContentEncoding::Brotli => {
+                let mut compressed = Vec::new();
+                {
+                    let mut encoder = brotli::CompressorWriter::new(&mut compressed, 4096, 5, 22);
+                    encoder.write_all(data).unwrap();
+                }
+                compressed
+            }
+            ContentEncoding::Zstd => zstd::encode_all(data, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn round_trips_every_encoding() {
+        let data = br#"{"message":"boom","stackTrace":"Error: boom"}"#.repeat(100);
+
+        for encoding in ENCODINGS {
+            let compressed = compress(encoding, &data);
+            assert_eq!(
+                decompress(encoding, &compressed, MAX_DECOMPRESSED_SIZE).unwrap(),
+                data,
+                "{:?}",
+                encoding
+            );
+        }
+    }
+
+    #[test]
+    fn accepts_output_exactly_at_the_limit() {
+        let data = vec![b'a'; 1024];
+
+        for encoding in ENCODINGS {
+            let compressed = compress(encoding, &data);
+            assert_eq!(
+                decompress(encoding, &compressed, data.len()).unwrap().len(),
+                data.len(),
+                "{:?}",
+                encoding
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_output_one_byte_over_the_limit() {
+        let data = vec![b'a'; 1025];
+
+        for encoding in ENCODINGS {
+            let compressed = compress(encoding, &data);
+            assert!(
+                matches!(
+                    decompress(encoding, &compressed, 1024),
+                    Err(RequestError::PayloadTooLarge(1024))
+                ),
+                "{:?}",
+                encoding
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_corrupt_input() {
+        let corrupt = b"definitely not compressed data";
+
+        for encoding in &ENCODINGS[1..] {
+            assert!(
+                matches!(
+                    decompress(*encoding, corrupt, MAX_DECOMPRESSED_SIZE),
+                    Err(RequestError::InvalidContentEncoding)
+                ),
+                "{:?}",
+                encoding
+            );
+        }
+    }
+
+    #[test]
+    fn parses_known_encodings() {
+        assert_eq!(
+            parse_content_encoding(None).unwrap(),
+            ContentEncoding::Identity
+        );
+        assert_eq!(
+            parse_content_encoding(Some("identity")).unwrap(),
+            ContentEncoding::Identity
+        );
+        assert_eq!(
+            parse_content_encoding(Some(" GZIP ")).unwrap(),
+            ContentEncoding::Gzip
+        );
+        assert_eq!(
+            parse_content_encoding(Some("x-gzip")).unwrap(),
+            ContentEncoding::Gzip
+        );
+        assert_eq!(
+            parse_content_encoding(Some("deflate")).unwrap(),
+            ContentEncoding::Deflate
+        );
+        assert_eq!(
+            parse_content_encoding(Some("br")).unwrap(),
+            ContentEncoding::Brotli
+        );
+        assert_eq!(
+            parse_content_encoding(Some("zstd")).unwrap(),
+            ContentEncoding::Zstd
+        );
+    }
+
+    #[test]
+    fn rejects_stacked_and_unknown_encodings() {
+        for header in ["gzip, br", "gzip,gzip", "compress", "lz4"] {
+            assert!(
+                matches!(
+                    parse_content_encoding(Some(header)),
+                    Err(RequestError::UnsupportedContentEncoding)
+                ),
+                "{}",
+                header
+            );
+        }
+    }
+}
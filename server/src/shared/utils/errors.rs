@@ -1,4 +1,8 @@
-use actix_web::{error::ResponseError, http::StatusCode, Error as ActixError, HttpResponse};
+use actix_web::{
+    error::ResponseError,
+    http::{header, StatusCode},
+    Error as ActixError, HttpResponse,
+};
 use actix_web_actors::ws::ProtocolError;
 use anyhow::Error as AnyhowError;
 use bcrypt::BcryptError;
@@ -49,12 +53,17 @@ impl ResponseError for ServerError {
                     | QueryError::NotFound
                     | QueryError::NamespaceAlertUserJunctionNotFound
                     | QueryError::FeatureRequestNotFound
+                    | QueryError::IssueNotFound
+                    | QueryError::ArtifactNotFound
+                    | QueryError::AttachmentNotFound
+                    | QueryError::InboundFilterNotFound
                     | QueryError::AlertTypeNotFound
                     | QueryError::DiscordChannelNotFound => StatusCode::NOT_FOUND,
                     QueryError::UserExists
                     | QueryError::NamespaceExists
                     | QueryError::UserNamespaceJunctionExists
-                    | QueryError::UserAlreadySubscribed => StatusCode::CONFLICT,
+                    | QueryError::UserAlreadySubscribed
+                    | QueryError::EventAlreadyReceived => StatusCode::CONFLICT,
                     QueryError::PasswordIncorrect
                     | QueryError::OAuthTypeError
                     | QueryError::UserNotNamespaceMember
@@ -68,20 +77,45 @@ impl ResponseError for ServerError {
             ServerError::RequestError(ref err) => {
                 let status = match err {
                     RequestError::OAuthCallbackFailed => StatusCode::INTERNAL_SERVER_ERROR,
-                    RequestError::RateLimitExceeded => StatusCode::TOO_MANY_REQUESTS,
-                    RequestError::NamespaceLimitReached | RequestError::PermissionDenied => {
-                        StatusCode::FORBIDDEN
+                    RequestError::RateLimitExceeded | RequestError::QuotaExceeded(_) => {
+                        StatusCode::TOO_MANY_REQUESTS
                     }
+                    RequestError::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+                    RequestError::UnsupportedContentEncoding
+                    | RequestError::UnsupportedAttachmentType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                    RequestError::NamespaceLimitReached
+                    | RequestError::InboundFilterLimitReached
+                    | RequestError::AttachmentLimitReached
+                    | RequestError::PermissionDenied => StatusCode::FORBIDDEN,
                     RequestError::InvalidCookies
                     | RequestError::InvalidToken
                     | RequestError::MissingCookie => StatusCode::UNAUTHORIZED,
                     RequestError::MissingUserID
                     | RequestError::MissingHeader
                     | RequestError::StackTraceParsingError
+                    | RequestError::InvalidBatchSize
+                    | RequestError::InvalidSentryPayload
+                    | RequestError::InvalidSourceMap
+                    | RequestError::InvalidArtifactName
+                    | RequestError::InvalidAttachmentName
+                    | RequestError::InvalidContentEncoding
+                    | RequestError::InvalidQuota
+                    | RequestError::InvalidEventId
+                    | RequestError::InvalidScrubbingRule
+                    | RequestError::InvalidInboundFilter
+                    | RequestError::InvalidContextFilter
+                    | RequestError::InvalidAlertCondition
+                    | RequestError::InvalidAlertCooldown
+                    | RequestError::InvalidWebhookUrl
+                    | RequestError::InvalidSlackWebhookUrl
                     | RequestError::InvalidHeader
                     | RequestError::InvalidQueryParameter => StatusCode::BAD_REQUEST,
                 };
-                HttpResponse::build(status).json(format!("{}", self))
+                let mut response = HttpResponse::build(status);
+                if let RequestError::QuotaExceeded(retry_after) = err {
+                    response.insert_header((header::RETRY_AFTER, retry_after.to_string()));
+                }
+                response.json(format!("{}", self))
             }
             ServerError::HttpError(status, message) => HttpResponse::build(*status).json(message),
             _ => HttpResponse::build(StatusCode::INTERNAL_SERVER_ERROR).json(format!("{}", self)),
@@ -147,6 +181,12 @@ pub enum ExternalError {
 
     #[error("Serenity error: {0}")]
     Serenity(SerenityError),
+
+    #[error("Webhook responded with status {0}")]
+    Webhook(u16),
+
+    #[error("Slack responded with status {0}")]
+    Slack(u16),
 }
 
 #[derive(Debug, Error)]
@@ -205,6 +245,21 @@ pub enum QueryError {
     #[error("Error not found")]
     ErrorNotFound,
 
+    #[error("Issue not found")]
+    IssueNotFound,
+
+    #[error("Artifact not found")]
+    ArtifactNotFound,
+
+    #[error("Attachment not found")]
+    AttachmentNotFound,
+
+    #[error("Event was already received")]
+    EventAlreadyReceived,
+
+    #[error("Inbound filter not found")]
+    InboundFilterNotFound,
+
     #[error("Invalid Timestamp")]
     InvalidTimestamp,
 
@@ -228,6 +283,9 @@ pub enum QueryError {
 
     #[error("Invalid discord channel")]
     InvalidDiscordChannel,
+
+    #[error("Alert has no webhook configured")]
+    WebhookNotConfigured,
 }
 
 #[derive(Debug, Error)]
@@ -267,6 +325,69 @@ pub enum RequestError {
 
     #[error("Stack trace parsing error")]
     StackTraceParsingError,
+
+    #[error("Batch must contain between 1 and 500 errors")]
+    InvalidBatchSize,
+
+    #[error("Invalid Sentry payload")]
+    InvalidSentryPayload,
+
+    #[error("Artifact is not a valid source map")]
+    InvalidSourceMap,
+
+    #[error("Artifact release and name must not be empty")]
+    InvalidArtifactName,
+
+    #[error("Invalid attachment file name, expected up to 255 characters without slashes")]
+    InvalidAttachmentName,
+
+    #[error("Attachment Content-Type must be text, JSON, an image, an archive or a minidump")]
+    UnsupportedAttachmentType,
+
+    #[error("Request body exceeds {0} bytes once decompressed")]
+    PayloadTooLarge(usize),
+
+    #[error("Unsupported Content-Encoding, expected gzip, deflate, br or zstd")]
+    UnsupportedContentEncoding,
+
+    #[error("Request body does not match its Content-Encoding")]
+    InvalidContentEncoding,
+
+    #[error("Ingestion quota exceeded, retry in {0} seconds")]
+    QuotaExceeded(u64),
+
+    #[error("Quotas must not be negative and the sample rate must be between 0 and 1")]
+    InvalidQuota,
+
+    #[error("Event ID must be between 1 and 64 characters")]
+    InvalidEventId,
+
+    #[error("Invalid scrubbing rules, expected at most 50 regexes of up to 512 characters")]
+    InvalidScrubbingRule,
+
+    #[error("Invalid inbound filter, check its pattern and, for tag filters, its tag key")]
+    InvalidInboundFilter,
+
+    #[error("Invalid context filter, expected up to 10 comma separated path:value pairs")]
+    InvalidContextFilter,
+
+    #[error("Invalid alert condition, thresholds and windows must be positive and groups hold 1 to 10 conditions, nested at most 4 deep")]
+    InvalidAlertCondition,
+
+    #[error("Invalid alert cooldown, it must be a positive number of milliseconds")]
+    InvalidAlertCooldown,
+
+    #[error("Invalid webhook URL, webhook alerts need an http or https URL on a public address")]
+    InvalidWebhookUrl,
+
+    #[error("Invalid Slack webhook URL, expected https://hooks.slack.com/services/...")]
+    InvalidSlackWebhookUrl,
+
+    #[error("Inbound Filter Limit Reached")]
+    InboundFilterLimitReached,
+
+    #[error("Attachment Limit Reached")]
+    AttachmentLimitReached,
 }
 
 impl From<ExternalError> for ServerError {
@@ -0,0 +1,142 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use sha2::{Digest, Sha256};
+
+use crate::shared::utils::parse::{StackFrame, StackTraceInfo};
+
+static UUID_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}\b").unwrap()
+});
+
+static HEX_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)\b(0x[0-9a-f]+|[0-9a-f]{8,})\b").unwrap());
+
+static NUMBER_PATTERN: Lazy<Regex> = Lazy::new(|| Regex::new(r"\d+").unwrap());
+
+/// Strips the volatile parts (UUIDs, hex values and numbers) from a message so
+/// that otherwise identical errors produce the same fingerprint.
+pub fn normalize_message(message: &str) -> String {
+    let normalized = UUID_PATTERN.replace_all(message.trim(), "<uuid>");
+    let normalized = HEX_PATTERN.replace_all(&normalized, "<hex>");
+    let normalized = NUMBER_PATTERN.replace_all(&normalized, "<num>");
+
+    normalized.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+/// Computes the grouping fingerprint for an error from its normalized type,
+/// its parsed frames and its normalized message.
+pub fn compute_fingerprint(stack_trace_info: &StackTraceInfo, message: &str) -> String {
+    let mut hasher = Sha256::new();
+
+    hasher.update(stack_trace_info.error_type.trim().to_lowercase());
+    hasher.update(b"\n");
+
+    // Library frames differ with dependency versions, so group on the application's
+    // own frames whenever the trace has any
+    let in_app_frames: Vec<&StackFrame> = stack_trace_info
+        .frames
+        .iter()
+        .filter(|frame| frame.is_in_app())
+        .collect();
+    let frames = match in_app_frames.is_empty() {
+        true => stack_trace_info.frames.iter().collect(),
+        false => in_app_frames,
+    };
+
+    // Line numbers shift between deploys, so only the frame's identity is hashed
+    for frame in frames {
+        hasher.update(&frame.module_name);
+        hasher.update(b"|");
+        hasher.update(&frame.function_name);
+        hasher.update(b"|");
+        hasher.update(normalize_message(&frame.file_path));
+        hasher.update(b"\n");
+    }
+
+    hasher.update(normalize_message(message));
+
+    hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(file_path: &str, function_name: &str, module_name: &str) -> StackFrame {
+        StackFrame {
+            file_path: file_path.to_string(),
+            line_number: 1,
+            column_number: None,
+            function_name: function_name.to_string(),
+            module_name: module_name.to_string(),
+            in_app: None,
+        }
+    }
+
+    fn trace(frames: Vec<StackFrame>) -> StackTraceInfo {
+        let mut info = StackTraceInfo::default();
+        info.error_type = "TypeError".to_string();
+        info.frames = frames;
+        info
+    }
+
+    #[test]
+    fn normalize_message_replaces_volatile_values() {
+        assert_eq!(
+            normalize_message("  user 123e4567-e89b-12d3-a456-426614174000 not found  "),
+            "user <uuid> not found"
+        );
+        assert_eq!(
+            normalize_message("bad pointer 0x7ffd5e8c at deadbeef01"),
+            "bad pointer <hex> at <hex>"
+        );
+        assert_eq!(normalize_message("retry 3 of   5"), "retry <num> of <num>");
+    }
+
+    #[test]
+    fn fingerprint_ignores_volatile_message_parts_and_line_numbers() {
+        let first = trace(vec![frame("src/app.js", "handler", "app")]);
+        let mut second = first.clone();
+        second.frames[0].line_number = 42;
+
+        assert_eq!(
+            compute_fingerprint(&first, "order 17 failed"),
+            compute_fingerprint(&second, "order 93 failed")
+        );
+        assert_ne!(
+            compute_fingerprint(&first, "order 17 failed"),
+            compute_fingerprint(&first, "order 17 timed out")
+        );
+    }
+
+    #[test]
+    fn fingerprint_groups_on_in_app_frames_only() {
+        let app = frame("src/app.js", "handler", "app");
+        let library = frame("node_modules/express/index.js", "dispatch", "express");
+        let upgraded = frame("node_modules/express/router.js", "next", "express");
+
+        assert_eq!(
+            compute_fingerprint(&trace(vec![app.clone(), library]), "boom"),
+            compute_fingerprint(&trace(vec![app, upgraded]), "boom")
+        );
+    }
+
+    #[test]
+    fn fingerprint_falls_back_to_all_frames_without_in_app_ones() {
+        let first = frame("node_modules/express/index.js", "dispatch", "express");
+        let second = frame("node_modules/express/router.js", "next", "express");
+
+        assert_ne!(
+            compute_fingerprint(&trace(vec![first.clone()]), "boom"),
+            compute_fingerprint(&trace(vec![second]), "boom")
+        );
+        assert_eq!(
+            compute_fingerprint(&trace(vec![first.clone()]), "boom"),
+            compute_fingerprint(&trace(vec![first]), "boom")
+        );
+    }
+}
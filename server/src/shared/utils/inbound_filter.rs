@@ -0,0 +1,179 @@
+use regex::{Regex, RegexBuilder};
+use uuid::Uuid;
+
+use shared_types::error_dtos::CreateErrorRequest;
+use shared_types::inbound_filter_dtos::InboundFilterType;
+
+pub const MAX_FILTER_PATTERN_LENGTH: usize = 512;
+const MAX_COMPILED_PATTERN_SIZE: usize = 1 << 20;
+
+/// A namespace's filter rule, compiled for matching incoming errors.
+pub struct InboundFilter {
+    pub id: Uuid,
+    filter_type: InboundFilterType,
+    tag_key: Option<String>,
+    matcher: Regex,
+}
+
+impl InboundFilter {
+    /// Message filters take a regex, every other type a glob where `*` matches any run
+    /// of characters and `?` a single one.
+    pub fn compile(
+        id: Uuid,
+        filter_type: InboundFilterType,
+        tag_key: Option<String>,
+        pattern: &str,
+    ) -> Result<Self, regex::Error> {
+        let pattern = match filter_type {
+            InboundFilterType::Message => pattern.to_string(),
+            _ => glob_to_regex(pattern),
+        };
+
+        let matcher = RegexBuilder::new(&pattern)
+            .size_limit(MAX_COMPILED_PATTERN_SIZE)
+            .build()?;
+
+        Ok(Self {
+            id,
+            filter_type,
+            tag_key,
+            matcher,
+        })
+    }
+
+    /// Whether the filter looks at the file of the top frame.
+    pub fn matches_path(&self) -> bool {
+        self.filter_type == InboundFilterType::Path
+    }
+
+    /// Checks the error as it was received, before scrubbing, with `path` being the file
+    /// of the top frame.
+    pub fn matches(&self, request: &CreateErrorRequest, path: &str) -> bool {
+        match self.filter_type {
+            InboundFilterType::Message => self.matcher.is_match(&request.message),
+            InboundFilterType::Path => self.matcher.is_match(path),
+            InboundFilterType::UserAffected => self.matcher.is_match(&request.user_affected),
+            InboundFilterType::Tag => {
+                let Some(tag_key) = &self.tag_key else {
+                    return false;
+                };
+
+                request.tags.iter().flatten().any(|tag| {
+                    tag.tag_key.eq_ignore_ascii_case(tag_key)
+                        && self.matcher.is_match(&tag.tag_value)
+                })
+            }
+        }
+    }
+}
+
+// Anchored so that a glob has to match the whole value
+fn glob_to_regex(glob: &str) -> String {
+    let mut pattern = String::from("^");
+    for c in glob.chars() {
+        match c {
+            '*' => pattern.push_str(".*"),
+            '?' => pattern.push('.'),
+            c => pattern.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    pattern.push('$');
+    pattern
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared_types::tag_dtos::CreateTagClientNoIdDTO;
+
+    fn filter(
+        filter_type: InboundFilterType,
+        tag_key: Option<&str>,
+        pattern: &str,
+    ) -> InboundFilter {
+        InboundFilter::compile(
+            Uuid::new_v4(),
+            filter_type,
+            tag_key.map(str::to_string),
+            pattern,
+        )
+        .unwrap()
+    }
+
+    fn request(message: &str, user_affected: &str, tags: &[(&str, &str)]) -> CreateErrorRequest {
+        CreateErrorRequest {
+            user_affected: user_affected.to_string(),
+            stack_trace: String::new(),
+            message: message.to_string(),
+            tags: Some(
+                tags.iter()
+                    .map(|(tag_key, tag_value)| CreateTagClientNoIdDTO {
+                        tag_key: tag_key.to_string(),
+                        tag_value: tag_value.to_string(),
+                    })
+                    .collect(),
+            ),
+            platform: None,
+            frames: None,
+            breadcrumbs: None,
+            release: None,
+            environment: None,
+            event_id: None,
+            contexts: None,
+            level: None,
+        }
+    }
+
+    #[test]
+    fn glob_to_regex_anchors_and_escapes() {
+        assert_eq!(glob_to_regex("*.js"), r"^.*\.js$");
+        assert_eq!(glob_to_regex("a?c"), "^a.c$");
+        assert_eq!(glob_to_regex("(x)+[y]"), r"^\(x\)\+\[y\]$");
+
+        let matcher = Regex::new(&glob_to_regex("vendor/*.js")).unwrap();
+        assert!(matcher.is_match("vendor/lib.js"));
+        assert!(!matcher.is_match("src/vendor/lib.js"));
+        assert!(!matcher.is_match("vendor/lib.jsx"));
+        assert!(!matcher.is_match("vendor/libxjs"));
+    }
+
+    #[test]
+    fn message_filters_take_a_regex() {
+        let filter = filter(InboundFilterType::Message, None, "^ResizeObserver loop");
+
+        assert!(filter.matches(&request("ResizeObserver loop limit exceeded", "", &[]), ""));
+        assert!(!filter.matches(&request("Error: ResizeObserver loop", "", &[]), ""));
+    }
+
+    #[test]
+    fn path_filters_match_the_top_frame() {
+        let filter = filter(InboundFilterType::Path, None, "*/node_modules/*");
+        let request = request("boom", "", &[]);
+
+        assert!(filter.matches_path());
+        assert!(filter.matches(&request, "/app/node_modules/react/index.js"));
+        assert!(!filter.matches(&request, "/app/src/index.js"));
+    }
+
+    #[test]
+    fn user_filters_match_the_affected_user() {
+        let filter = filter(InboundFilterType::UserAffected, None, "bot-*");
+
+        assert!(!filter.matches_path());
+        assert!(filter.matches(&request("boom", "bot-crawler", &[]), ""));
+        assert!(!filter.matches(&request("boom", "robot-1", &[]), ""));
+    }
+
+    #[test]
+    fn tag_filters_match_the_named_tag_only() {
+        let filter = filter(InboundFilterType::Tag, Some("browser"), "Headless*");
+
+        assert!(filter.matches(&request("boom", "", &[("Browser", "HeadlessChrome")]), ""));
+        assert!(!filter.matches(&request("boom", "", &[("os", "HeadlessChrome")]), ""));
+        assert!(!filter.matches(&request("boom", "", &[("browser", "Chrome")]), ""));
+
+        // Without a key there is no tag to look at
+        let keyless = self::filter(InboundFilterType::Tag, None, "*");
+        assert!(!keyless.matches(&request("boom", "", &[("browser", "Chrome")]), ""));
+    }
+}
@@ -61,15 +61,20 @@ pub async fn validate_namespace_secret_jwt(
         .map_err(|err| ServerError::from(ExternalError::DB(err)))?;
 
     match found_namespace {
-        Some(namespace) => {
-            let _ = verify(client_secret, &namespace.client_secret.to_string())
-                .map_err(|err| ServerError::from(ExternalError::Bcrypt(err)));
-            Ok(true)
-        }
+        Some(namespace) => Ok(namespace_secret_matches(
+            namespace.client_secret,
+            &client_secret,
+        )),
         None => Err(ServerError::from(QueryError::NamespaceNotFound)),
     }
 }
 
+/// Whether the secret an SDK presented is the bcrypt hash SDKs are handed for the
+/// namespace's secret. A malformed hash is just a wrong secret.
+pub fn namespace_secret_matches(namespace_secret: Uuid, client_secret: &str) -> bool {
+    verify(namespace_secret.to_string(), client_secret).unwrap_or(false)
+}
+
 pub fn extract_user_id_from_jwt_header(
     headers: &HeaderMap,
     secret_key: &str,
@@ -205,3 +210,34 @@ pub async fn refresh_access_token_util(
 
     Ok(access_token)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bcrypt::hash;
+
+    #[test]
+    fn namespace_secret_matches_its_hash() {
+        let secret = Uuid::new_v4();
+        let client_secret = hash(secret.to_string(), 4).unwrap();
+
+        assert!(namespace_secret_matches(secret, &client_secret));
+    }
+
+    #[test]
+    fn namespace_secret_rejects_another_secrets_hash() {
+        let client_secret = hash(Uuid::new_v4().to_string(), 4).unwrap();
+
+        assert!(!namespace_secret_matches(Uuid::new_v4(), &client_secret));
+    }
+
+    #[test]
+    fn namespace_secret_rejects_malformed_hashes() {
+        let secret = Uuid::new_v4();
+
+        assert!(!namespace_secret_matches(secret, ""));
+        assert!(!namespace_secret_matches(secret, "not-a-bcrypt-hash"));
+        // The raw secret itself isn't accepted in place of its hash
+        assert!(!namespace_secret_matches(secret, &secret.to_string()));
+    }
+}
@@ -1,8 +1,20 @@
+pub mod alerting;
+pub mod breadcrumbs;
+pub mod context_filter;
+pub mod decompress;
 pub mod discord;
 pub mod errors;
+pub mod fingerprint;
+pub mod inbound_filter;
 pub mod jwt;
 pub mod mailing;
+pub mod otlp;
 pub mod parse;
 pub mod query;
+pub mod quota;
 pub mod rate_limit;
 pub mod role;
+pub mod scrub;
+pub mod slack;
+pub mod sentry;
+pub mod symbolicate;
@@ -0,0 +1,582 @@
+use prost::{Message, Oneof};
+use serde::{Deserialize, Deserializer, Serialize};
+use uuid::Uuid;
+
+use shared_types::error_dtos::{
+    CreateErrorRequest, DeviceContextDTO, ErrorContextsDTO, ErrorLevel, OsContextDTO,
+    RequestContextDTO, RuntimeContextDTO,
+};
+use shared_types::tag_dtos::CreateTagClientNoIdDTO;
+
+// Hand-written subset of the OTLP v1 protocol (opentelemetry-proto) covering what we
+// ingest. Field tags match the upstream .proto files so protobuf payloads decode, and
+// the serde attributes follow the OTLP/HTTP JSON mapping. Unknown fields are ignored.
+
+// Resource attribute that routes telemetry to a namespace, overriding the client_id header
+pub const CLIENT_ID_ATTRIBUTE: &str = "higuard.client_id";
+
+#[derive(Clone, PartialEq, Message, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ExportLogsServiceRequest {
+    #[prost(message, repeated, tag = "1")]
+    pub resource_logs: Vec<ResourceLogs>,
+}
+
+#[derive(Clone, PartialEq, Message, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ResourceLogs {
+    #[prost(message, optional, tag = "1")]
+    pub resource: Option<Resource>,
+    #[prost(message, repeated, tag = "2")]
+    pub scope_logs: Vec<ScopeLogs>,
+}
+
+#[derive(Clone, PartialEq, Message, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ScopeLogs {
+    #[prost(message, repeated, tag = "2")]
+    pub log_records: Vec<LogRecord>,
+}
+
+#[derive(Clone, PartialEq, Message, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LogRecord {
+    // SeverityNumber, 1 to 24 from TRACE to FATAL4 and 0 when unspecified
+    #[prost(int32, tag = "2")]
+    pub severity_number: i32,
+    #[prost(string, tag = "3")]
+    pub severity_text: String,
+    #[prost(message, optional, tag = "5")]
+    pub body: Option<AnyValue>,
+    #[prost(message, repeated, tag = "6")]
+    pub attributes: Vec<KeyValue>,
+}
+
+#[derive(Clone, PartialEq, Message, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ExportTraceServiceRequest {
+    #[prost(message, repeated, tag = "1")]
+    pub resource_spans: Vec<ResourceSpans>,
+}
+
+#[derive(Clone, PartialEq, Message, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ResourceSpans {
+    #[prost(message, optional, tag = "1")]
+    pub resource: Option<Resource>,
+    #[prost(message, repeated, tag = "2")]
+    pub scope_spans: Vec<ScopeSpans>,
+}
+
+#[derive(Clone, PartialEq, Message, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ScopeSpans {
+    #[prost(message, repeated, tag = "2")]
+    pub spans: Vec<Span>,
+}
+
+#[derive(Clone, PartialEq, Message, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Span {
+    #[prost(string, tag = "5")]
+    pub name: String,
+    #[prost(message, repeated, tag = "9")]
+    pub attributes: Vec<KeyValue>,
+    #[prost(message, repeated, tag = "11")]
+    pub events: Vec<SpanEvent>,
+}
+
+#[derive(Clone, PartialEq, Message, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SpanEvent {
+    #[prost(string, tag = "2")]
+    pub name: String,
+    #[prost(message, repeated, tag = "3")]
+    pub attributes: Vec<KeyValue>,
+}
+
+#[derive(Clone, PartialEq, Message, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Resource {
+    #[prost(message, repeated, tag = "1")]
+    pub attributes: Vec<KeyValue>,
+}
+
+#[derive(Clone, PartialEq, Message, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct KeyValue {
+    #[prost(string, tag = "1")]
+    pub key: String,
+    #[prost(message, optional, tag = "2")]
+    pub value: Option<AnyValue>,
+}
+
+#[derive(Clone, PartialEq, Message, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AnyValue {
+    #[prost(oneof = "Value", tags = "1, 2, 3, 4, 5, 6")]
+    #[serde(flatten)]
+    pub value: Option<Value>,
+}
+
+#[derive(Clone, PartialEq, Oneof, Deserialize)]
+pub enum Value {
+    #[prost(string, tag = "1")]
+    #[serde(rename = "stringValue")]
+    String(String),
+    #[prost(bool, tag = "2")]
+    #[serde(rename = "boolValue")]
+    Bool(bool),
+    // int64 is encoded as a string in OTLP JSON, though some exporters send numbers
+    #[prost(int64, tag = "3")]
+    #[serde(rename = "intValue")]
+    Int(#[serde(deserialize_with = "int_from_string_or_number")] i64),
+    #[prost(double, tag = "4")]
+    #[serde(rename = "doubleValue")]
+    Double(f64),
+    #[prost(message, tag = "5")]
+    #[serde(rename = "arrayValue")]
+    Array(ArrayValue),
+    #[prost(message, tag = "6")]
+    #[serde(rename = "kvlistValue")]
+    Kvlist(KeyValueList),
+}
+
+#[derive(Clone, PartialEq, Message, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ArrayValue {
+    #[prost(message, repeated, tag = "1")]
+    pub values: Vec<AnyValue>,
+}
+
+#[derive(Clone, PartialEq, Message, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct KeyValueList {
+    #[prost(message, repeated, tag = "1")]
+    pub values: Vec<KeyValue>,
+}
+
+// Export responses share the same shape for logs and traces: field 1 holds the partial
+// success, whose first field counts the rejected records/spans.
+#[derive(Clone, PartialEq, Message, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportServiceResponse {
+    #[prost(message, optional, tag = "1")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub partial_success: Option<ExportPartialSuccess>,
+}
+
+#[derive(Clone, PartialEq, Message, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportPartialSuccess {
+    #[prost(int64, tag = "1")]
+    #[serde(serialize_with = "int_to_string")]
+    pub rejected: i64,
+    #[prost(string, tag = "2")]
+    pub error_message: String,
+}
+
+fn int_from_string_or_number<'de, D>(deserializer: D) -> Result<i64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum StringOrNumber {
+        String(String),
+        Number(i64),
+    }
+
+    match StringOrNumber::deserialize(deserializer)? {
+        StringOrNumber::String(value) => value.parse().map_err(serde::de::Error::custom),
+        StringOrNumber::Number(value) => Ok(value),
+    }
+}
+
+fn int_to_string<S>(value: &i64, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_str(&value.to_string())
+}
+
+/// An exception extracted from a log record or span event, along with the namespace
+/// it was routed to (`None` when neither the resource nor the request named one).
+pub struct OtlpException {
+    pub client_id: Option<Uuid>,
+    pub request: CreateErrorRequest,
+}
+
+/// Extracts every log record that carries `exception.*` attributes.
+pub fn exceptions_from_logs(
+    request: ExportLogsServiceRequest,
+    header_client_id: Option<Uuid>,
+) -> Vec<OtlpException> {
+    let mut exceptions = Vec::new();
+
+    for resource_logs in request.resource_logs {
+        let resource = resource_logs.resource.unwrap_or_default();
+        let client_id = resource_client_id(&resource).or(header_client_id);
+
+        for log_record in resource_logs
+            .scope_logs
+            .into_iter()
+            .flat_map(|scope_logs| scope_logs.log_records)
+        {
+            let body = log_record
+                .body
+                .as_ref()
+                .and_then(|body| body.value.as_ref())
+                .map(value_to_string);
+
+            let level = severity_level(log_record.severity_number)
+                .or_else(|| ErrorLevel::parse(&log_record.severity_text));
+
+            if let Some(request) =
+                exception_to_error_request(&log_record.attributes, &resource, body, level)
+            {
+                exceptions.push(OtlpException { client_id, request });
+            }
+        }
+    }
+
+    exceptions
+}
+
+/// Extracts every span event recorded through `span.recordException` (or otherwise
+/// carrying `exception.*` attributes).
+pub fn exceptions_from_traces(
+    request: ExportTraceServiceRequest,
+    header_client_id: Option<Uuid>,
+) -> Vec<OtlpException> {
+    let mut exceptions = Vec::new();
+
+    for resource_spans in request.resource_spans {
+        let resource = resource_spans.resource.unwrap_or_default();
+        let client_id = resource_client_id(&resource).or(header_client_id);
+
+        for span in resource_spans
+            .scope_spans
+            .into_iter()
+            .flat_map(|scope_spans| scope_spans.spans)
+        {
+            for event in &span.events {
+                // Span attributes such as enduser.id apply to the events recorded on it
+                let attributes: Vec<KeyValue> = event
+                    .attributes
+                    .iter()
+                    .chain(span.attributes.iter())
+                    .cloned()
+                    .collect();
+
+                // Span events carry no severity, a recorded exception is an error
+                if let Some(request) = exception_to_error_request(
+                    &attributes,
+                    &resource,
+                    Some(span.name.clone()),
+                    None,
+                ) {
+                    exceptions.push(OtlpException { client_id, request });
+                }
+            }
+        }
+    }
+
+    exceptions
+}
+
+fn exception_to_error_request(
+    attributes: &[KeyValue],
+    resource: &Resource,
+    fallback_message: Option<String>,
+    level: Option<ErrorLevel>,
+) -> Option<CreateErrorRequest> {
+    let exception_type = attribute(attributes, "exception.type");
+    let exception_message = attribute(attributes, "exception.message");
+    let exception_stacktrace = attribute(attributes, "exception.stacktrace");
+
+    if exception_type.is_none() && exception_message.is_none() && exception_stacktrace.is_none() {
+        return None;
+    }
+
+    let message = exception_message
+        .clone()
+        .or_else(|| exception_type.clone())
+        .or(fallback_message)
+        .unwrap_or_else(|| "<no message>".to_string());
+
+    let header = match (&exception_type, &exception_message) {
+        (Some(exception_type), Some(exception_message)) => {
+            format!("{}: {}", exception_type, exception_message)
+        }
+        (Some(exception_type), None) => exception_type.clone(),
+        _ => message.clone(),
+    };
+
+    // Without a stack trace there is nothing to parse, so the error is stored frameless
+    let (stack_trace, frames) = match exception_stacktrace {
+        Some(stack_trace) => (stack_trace, None),
+        None => (header, Some(Vec::new())),
+    };
+
+    let user_affected = attribute(attributes, "enduser.id")
+        .or_else(|| attribute(&resource.attributes, "enduser.id"))
+        .unwrap_or_else(|| "unknown".to_string());
+
+    let tags = resource
+        .attributes
+        .iter()
+        .filter(|attribute| attribute.key != CLIENT_ID_ATTRIBUTE)
+        .filter_map(|attribute| {
+            attribute
+                .value
+                .as_ref()
+                .and_then(|value| value.value.as_ref())
+                .map(|value| CreateTagClientNoIdDTO {
+                    tag_key: attribute.key.clone(),
+                    tag_value: value_to_string(value),
+                })
+        })
+        .collect();
+
+    Some(CreateErrorRequest {
+        user_affected,
+        stack_trace,
+        message,
+        tags: Some(tags),
+        platform: attribute(&resource.attributes, "telemetry.sdk.language"),
+        frames,
+        breadcrumbs: None,
+        release: attribute(&resource.attributes, "service.version"),
+        environment: attribute(&resource.attributes, "deployment.environment.name")
+            .or_else(|| attribute(&resource.attributes, "deployment.environment")),
+        event_id: None,
+        contexts: semantic_contexts(attributes, &resource.attributes),
+        level,
+    })
+}
+
+// TRACE and DEBUG both map to debug since we have no trace level
+fn severity_level(severity_number: i32) -> Option<ErrorLevel> {
+    match severity_number {
+        1..=8 => Some(ErrorLevel::Debug),
+        9..=12 => Some(ErrorLevel::Info),
+        13..=16 => Some(ErrorLevel::Warning),
+        17..=20 => Some(ErrorLevel::Error),
+        21..=24 => Some(ErrorLevel::Fatal),
+        _ => None,
+    }
+}
+
+// Maps the semantic convention attributes that describe the request, process and host.
+// Span attributes carry the HTTP request, the resource everything else
+fn semantic_contexts(
+    attributes: &[KeyValue],
+    resource_attributes: &[KeyValue],
+) -> Option<ErrorContextsDTO> {
+    let method = attribute(attributes, "http.request.method")
+        .or_else(|| attribute(attributes, "http.method"));
+    let url = attribute(attributes, "url.full").or_else(|| attribute(attributes, "http.url"));
+    let request = (method.is_some() || url.is_some()).then_some(RequestContextDTO {
+        method,
+        url,
+        headers: None,
+    });
+
+    let runtime_name = attribute(resource_attributes, "process.runtime.name");
+    let runtime_version = attribute(resource_attributes, "process.runtime.version");
+    let runtime =
+        (runtime_name.is_some() || runtime_version.is_some()).then_some(RuntimeContextDTO {
+            name: runtime_name,
+            version: runtime_version,
+        });
+
+    let os_name = attribute(resource_attributes, "os.name")
+        .or_else(|| attribute(resource_attributes, "os.type"));
+    let os_version = attribute(resource_attributes, "os.version");
+    let os = (os_name.is_some() || os_version.is_some()).then_some(OsContextDTO {
+        name: os_name,
+        version: os_version,
+    });
+
+    let device_model = attribute(resource_attributes, "device.model.name")
+        .or_else(|| attribute(resource_attributes, "device.model.identifier"));
+    let device_arch = attribute(resource_attributes, "host.arch");
+    let device = (device_model.is_some() || device_arch.is_some()).then_some(DeviceContextDTO {
+        model: device_model,
+        family: None,
+        arch: device_arch,
+    });
+
+    if request.is_none() && runtime.is_none() && os.is_none() && device.is_none() {
+        return None;
+    }
+
+    Some(ErrorContextsDTO {
+        request,
+        runtime,
+        os,
+        device,
+        extra: None,
+    })
+}
+
+fn resource_client_id(resource: &Resource) -> Option<Uuid> {
+    attribute(&resource.attributes, CLIENT_ID_ATTRIBUTE)
+        .and_then(|client_id| Uuid::parse_str(&client_id).ok())
+}
+
+fn attribute(attributes: &[KeyValue], key: &str) -> Option<String> {
+    attributes
+        .iter()
+        .find(|attribute| attribute.key == key)
+        .and_then(|attribute| attribute.value.as_ref())
+        .and_then(|value| value.value.as_ref())
+        .map(value_to_string)
+}
+
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.clone(),
+        Value::Bool(value) => value.to_string(),
+        Value::Int(value) => value.to_string(),
+        Value::Double(value) => value.to_string(),
+        Value::Array(array) => {
+            let values: Vec<String> = array
+                .values
+                .iter()
+                .filter_map(|value| value.value.as_ref())
+                .map(value_to_string)
+                .collect();
+            format!("[{}]", values.join(", "))
+        }
+        Value::Kvlist(list) => {
+            let values: Vec<String> = list
+                .values
+                .iter()
+                .map(|pair| {
+                    let value = pair
+                        .value
+                        .as_ref()
+                        .and_then(|value| value.value.as_ref())
+                        .map(value_to_string)
+                        .unwrap_or_default();
+                    format!("{}={}", pair.key, value)
+                })
+                .collect();
+            format!("{{{}}}", values.join(", "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOGS_JSON: &str = include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/otlp/logs.json"
+    ));
+    const TRACES_PROTOBUF: &[u8] = include_bytes!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/otlp/traces.pb"
+    ));
+
+    fn tag<'a>(request: &'a CreateErrorRequest, key: &str) -> Option<&'a str> {
+        request
+            .tags
+            .as_ref()?
+            .iter()
+            .find(|tag| tag.tag_key == key)
+            .map(|tag| tag.tag_value.as_str())
+    }
+
+    #[test]
+    fn extracts_exceptions_from_json_logs() {
+        let request: ExportLogsServiceRequest = serde_json::from_str(LOGS_JSON).unwrap();
+        let exceptions = exceptions_from_logs(request, None);
+
+        // The plain info record carries no exception.* attributes
+        assert_eq!(exceptions.len(), 1);
+        let exception = &exceptions[0];
+        assert_eq!(
+            exception.client_id,
+            Uuid::parse_str("7f2c1a2e-5b0d-4c3e-9a61-3d2f8e4b9c10").ok()
+        );
+
+        let error = &exception.request;
+        assert_eq!(error.message, "card declined");
+        assert!(error
+            .stack_trace
+            .starts_with("Traceback (most recent call last):"));
+        assert!(error.frames.is_none());
+        assert_eq!(error.user_affected, "user-17");
+        assert_eq!(error.level, Some(ErrorLevel::Error));
+        assert_eq!(error.platform.as_deref(), Some("python"));
+        assert_eq!(error.release.as_deref(), Some("2.4.1"));
+        assert_eq!(error.environment.as_deref(), Some("production"));
+        assert_eq!(tag(error, "service.version"), Some("2.4.1"));
+        assert_eq!(tag(error, "deployment.environment"), Some("production"));
+        assert_eq!(tag(error, CLIENT_ID_ATTRIBUTE), None);
+    }
+
+    #[test]
+    fn extracts_exceptions_from_protobuf_traces() {
+        let request = ExportTraceServiceRequest::decode(TRACES_PROTOBUF).unwrap();
+        let header_client_id = Uuid::new_v4();
+        let exceptions = exceptions_from_traces(request, Some(header_client_id));
+
+        // Only the event recorded through recordException becomes an error
+        assert_eq!(exceptions.len(), 1);
+        let exception = &exceptions[0];
+        assert_eq!(exception.client_id, Some(header_client_id));
+
+        let error = &exception.request;
+        assert_eq!(error.message, "order already shipped");
+        assert!(error
+            .stack_trace
+            .starts_with("java.lang.IllegalStateException: order already shipped"));
+        // Span attributes apply to the exceptions recorded on the span
+        assert_eq!(error.user_affected, "user-42");
+        assert_eq!(
+            error
+                .contexts
+                .as_ref()
+                .and_then(|contexts| contexts.request.as_ref())
+                .and_then(|request| request.method.as_deref()),
+            Some("POST")
+        );
+        assert_eq!(error.level, None);
+        assert_eq!(error.platform.as_deref(), Some("java"));
+        assert_eq!(error.release.as_deref(), Some("1.9.0"));
+        assert_eq!(error.environment.as_deref(), Some("staging"));
+        assert_eq!(tag(error, "service.version"), Some("1.9.0"));
+        assert_eq!(tag(error, "deployment.environment"), Some("staging"));
+    }
+
+    #[test]
+    fn stores_exceptions_without_a_stack_trace_frameless() {
+        let request: ExportLogsServiceRequest = serde_json::from_value(serde_json::json!({
+            "resourceLogs": [{
+                "scopeLogs": [{
+                    "logRecords": [{
+                        "severityText": "fatal",
+                        "attributes": [
+                            { "key": "exception.type", "value": { "stringValue": "OOMError" } }
+                        ]
+                    }]
+                }]
+            }]
+        }))
+        .unwrap();
+        let exceptions = exceptions_from_logs(request, None);
+
+        let error = &exceptions[0].request;
+        assert_eq!(exceptions[0].client_id, None);
+        assert_eq!(error.message, "OOMError");
+        assert_eq!(error.stack_trace, "OOMError");
+        assert!(error.frames.as_ref().is_some_and(Vec::is_empty));
+        assert_eq!(error.level, Some(ErrorLevel::Fatal));
+        assert_eq!(error.user_affected, "unknown");
+    }
+}
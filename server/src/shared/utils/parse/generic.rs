@@ -0,0 +1,57 @@
+use super::{split_error_header, Platform, StackFrame, StackTraceInfo, StackTraceParser};
+
+/// Fallback for the original SDK format: a `Type: message` header followed by
+/// whitespace separated `at path:line function module` lines.
+pub struct GenericParser;
+
+impl StackTraceParser for GenericParser {
+    fn platform(&self) -> Platform {
+        Platform::Generic
+    }
+
+    fn can_parse(&self, _stack_trace: &str) -> bool {
+        true
+    }
+
+    fn parse(&self, stack_trace: &str) -> Result<StackTraceInfo, &'static str> {
+        let lines: Vec<&str> = stack_trace.lines().collect();
+
+        if lines.is_empty() {
+            return Err("Stack trace is empty");
+        }
+
+        let (error_type, _) = split_error_header(lines[0]);
+        let mut frames = Vec::new();
+
+        for line in lines.iter().skip(1) {
+            let line_parts: Vec<&str> = line.split_whitespace().collect();
+
+            if line_parts.len() < 2 {
+                continue;
+            }
+
+            // Anything that isn't `path:line` is not a frame we understand, skip it
+            let (file_path, line_number) = match line_parts[1].rsplit_once(':') {
+                Some((path, line_number)) => match line_number.parse::<i32>() {
+                    Ok(line_number) => (path.to_string(), line_number),
+                    Err(_) => continue,
+                },
+                None => continue,
+            };
+
+            frames.push(StackFrame {
+                file_path,
+                line_number,
+                column_number: None,
+                function_name: line_parts.get(2).unwrap_or(&"").to_string(),
+                module_name: line_parts.get(3).unwrap_or(&"").to_string(),
+                in_app: None,
+            });
+        }
+
+        // The legacy format reports the innermost frame last
+        frames.reverse();
+
+        Ok(StackTraceInfo::from_frames(error_type, frames, 0))
+    }
+}
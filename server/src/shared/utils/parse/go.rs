@@ -0,0 +1,104 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use super::{Platform, StackFrame, StackTraceInfo, StackTraceParser};
+
+static GOROUTINE_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^goroutine \d+ \[.*\]:\s*$").unwrap());
+
+// `main.handler(0xc000010000, ...)` or `created by main.main in goroutine 1`
+static FUNCTION_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(?:created by )?(\S+?)(?:\([^()]*\))?(?: in goroutine \d+)?\s*$").unwrap());
+
+// `\t/app/main.go:12 +0x1d`
+static LOCATION_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s+(.+?):(\d+)(?: \+0x[0-9a-fA-F]+)?\s*$").unwrap());
+
+/// Parses Go panic output (`panic: ...` followed by goroutine dumps). Only the
+/// first goroutine, the one that panicked, is turned into frames.
+pub struct GoParser;
+
+impl GoParser {
+    /// Splits `github.com/org/repo/pkg.(*Type).Method` into package and function.
+    fn split_function(qualified: &str) -> (String, String) {
+        let (prefix, last) = match qualified.rsplit_once('/') {
+            Some((prefix, last)) => (format!("{}/", prefix), last),
+            None => (String::new(), qualified),
+        };
+
+        match last.split_once('.') {
+            Some((package, function)) => (format!("{}{}", prefix, package), function.to_string()),
+            None => (String::new(), qualified.to_string()),
+        }
+    }
+}
+
+impl StackTraceParser for GoParser {
+    fn platform(&self) -> Platform {
+        Platform::Go
+    }
+
+    fn can_parse(&self, stack_trace: &str) -> bool {
+        stack_trace
+            .lines()
+            .any(|line| GOROUTINE_PATTERN.is_match(line))
+    }
+
+    fn parse(&self, stack_trace: &str) -> Result<StackTraceInfo, &'static str> {
+        let mut error_type = String::new();
+        let mut frames = Vec::new();
+        let mut in_goroutine = false;
+        let mut pending_function: Option<&str> = None;
+
+        for line in stack_trace.lines() {
+            if error_type.is_empty() {
+                for prefix in ["panic", "fatal error"] {
+                    if line.starts_with(&format!("{}: ", prefix)) {
+                        error_type = prefix.to_string();
+                    }
+                }
+            }
+
+            if GOROUTINE_PATTERN.is_match(line) {
+                // Later goroutines are bystanders, stop after the first one
+                if in_goroutine {
+                    break;
+                }
+                in_goroutine = true;
+                continue;
+            }
+
+            if !in_goroutine || line.trim().is_empty() {
+                continue;
+            }
+
+            if let Some(caps) = LOCATION_PATTERN.captures(line) {
+                if let Some(function) = pending_function.take() {
+                    let (module_name, function_name) = Self::split_function(function);
+                    frames.push(StackFrame {
+                        file_path: caps[1].to_string(),
+                        line_number: caps[2].parse().unwrap_or(0),
+                        column_number: None,
+                        function_name,
+                        module_name,
+                        in_app: None,
+                    });
+                }
+            } else if let Some(caps) = FUNCTION_PATTERN.captures(line) {
+                pending_function = caps.get(1).map(|function| function.as_str());
+            }
+        }
+
+        if frames.is_empty() {
+            return Err("No goroutine frames found");
+        }
+
+        // The first frames belong to the runtime's panic machinery, not the caller
+        let culprit = frames
+            .iter()
+            .position(|frame| frame.module_name != "runtime" && !frame.module_name.is_empty())
+            .unwrap_or(0);
+
+        Ok(StackTraceInfo::from_frames(error_type, frames, culprit))
+    }
+}
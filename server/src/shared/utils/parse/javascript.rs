@@ -0,0 +1,76 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use super::{
+    module_from_path, split_error_header, Platform, StackFrame, StackTraceInfo, StackTraceParser,
+};
+
+// V8 (Chrome, Node, Edge): `at fn (file:line:col)` or `at file:line:col`
+static V8_FRAME_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*at\s+(?:new\s+)?(?:async\s+)?(?:(.+?)\s+\()?(.+?):(\d+):(\d+)\)?\s*$").unwrap()
+});
+
+// SpiderMonkey (Firefox) and JavaScriptCore (Safari): `fn@file:line:col`
+static GECKO_FRAME_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*(.*?)@(.+?):(\d+):(\d+)\s*$").unwrap());
+
+/// Parses V8 and SpiderMonkey/JavaScriptCore style `error.stack` output.
+pub struct JavaScriptParser;
+
+impl JavaScriptParser {
+    fn parse_frame(line: &str) -> Option<StackFrame> {
+        let caps = V8_FRAME_PATTERN
+            .captures(line)
+            .or_else(|| GECKO_FRAME_PATTERN.captures(line))?;
+
+        let file_path = caps[2].to_string();
+
+        Some(StackFrame {
+            module_name: module_from_path(&file_path),
+            in_app: None,
+            file_path,
+            line_number: caps[3].parse().unwrap_or(0),
+            column_number: caps[4].parse().ok(),
+            function_name: caps
+                .get(1)
+                .map(|function| function.as_str().to_string())
+                .unwrap_or_default(),
+        })
+    }
+}
+
+impl StackTraceParser for JavaScriptParser {
+    fn platform(&self) -> Platform {
+        Platform::JavaScript
+    }
+
+    fn can_parse(&self, stack_trace: &str) -> bool {
+        stack_trace
+            .lines()
+            .any(|line| V8_FRAME_PATTERN.is_match(line) || GECKO_FRAME_PATTERN.is_match(line))
+    }
+
+    fn parse(&self, stack_trace: &str) -> Result<StackTraceInfo, &'static str> {
+        let mut lines = stack_trace.lines().filter(|line| !line.trim().is_empty());
+        let first_line = lines.next().ok_or("Stack trace is empty")?;
+
+        let mut frames = Vec::new();
+
+        // Firefox and Safari stacks start straight with a frame, without a message header
+        let error_type = match Self::parse_frame(first_line) {
+            Some(frame) => {
+                frames.push(frame);
+                String::new()
+            }
+            None => split_error_header(first_line).0,
+        };
+
+        frames.extend(lines.filter_map(Self::parse_frame));
+
+        if frames.is_empty() {
+            return Err("No JavaScript frames found");
+        }
+
+        Ok(StackTraceInfo::from_frames(error_type, frames, 0))
+    }
+}
@@ -0,0 +1,78 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use super::{split_error_header, Platform, StackFrame, StackTraceInfo, StackTraceParser};
+
+// `at [module/]package.Class.method(File.java:42)`
+static FRAME_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*at\s+(?:[\w.$@-]+/)?([\w$.<>\[\]-]+)\.([\w$<>\[\]-]+)\(([^)]*)\)").unwrap()
+});
+
+/// Parses JVM exception traces (Java, Kotlin, Scala) including `Caused by:` chains.
+///
+/// The reported type and message are the outermost exception's, while the error
+/// location points at the first frame of the root cause, where the failure started.
+pub struct JvmParser;
+
+impl StackTraceParser for JvmParser {
+    fn platform(&self) -> Platform {
+        Platform::Jvm
+    }
+
+    fn can_parse(&self, stack_trace: &str) -> bool {
+        stack_trace.lines().any(|line| FRAME_PATTERN.is_match(line))
+    }
+
+    fn parse(&self, stack_trace: &str) -> Result<StackTraceInfo, &'static str> {
+        let header = stack_trace
+            .lines()
+            .find(|line| !line.trim().is_empty())
+            .ok_or("Stack trace is empty")?;
+        let (error_type, _) = split_error_header(header);
+
+        let mut frames = Vec::new();
+        let mut root_cause_start = 0;
+
+        for line in stack_trace.lines() {
+            if line.trim_start().starts_with("Caused by:") {
+                root_cause_start = frames.len();
+                continue;
+            }
+
+            let caps = match FRAME_PATTERN.captures(line) {
+                Some(caps) => caps,
+                None => continue,
+            };
+
+            let class_name = caps[1].to_string();
+            let (file_path, line_number) = match caps[3].rsplit_once(':') {
+                Some((file, line_number)) => (file.to_string(), line_number.parse().unwrap_or(0)),
+                // `Native Method` / `Unknown Source`
+                None => (caps[3].to_string(), 0),
+            };
+
+            frames.push(StackFrame {
+                file_path,
+                line_number,
+                column_number: None,
+                function_name: caps[2].to_string(),
+                module_name: class_name,
+                in_app: None,
+            });
+        }
+
+        if frames.is_empty() {
+            return Err("No JVM frames found");
+        }
+
+        // The root cause's first frame is often inside a driver or framework, the
+        // application frame that called into it is the more useful location
+        let culprit = frames
+            .iter()
+            .skip(root_cause_start)
+            .position(StackFrame::is_in_app)
+            .map_or(root_cause_start, |index| root_cause_start + index);
+
+        Ok(StackTraceInfo::from_frames(error_type, frames, culprit))
+    }
+}
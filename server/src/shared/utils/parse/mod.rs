@@ -0,0 +1,377 @@
+pub mod generic;
+pub mod go;
+pub mod javascript;
+pub mod jvm;
+pub mod python;
+pub mod rust;
+
+use std::str::FromStr;
+
+use shared_types::error_dtos::StackFrameDTO;
+
+use generic::GenericParser;
+use go::GoParser;
+use javascript::JavaScriptParser;
+use jvm::JvmParser;
+use python::PythonParser;
+use rust::RustParser;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StackFrame {
+    pub file_path: String,
+    pub line_number: i32,
+    pub column_number: Option<i32>,
+    pub function_name: String,
+    pub module_name: String,
+    // Set when the SDK reported it, otherwise derived from the path and module
+    pub in_app: Option<bool>,
+}
+
+// Path fragments and module prefixes that mark a frame as belonging to a
+// dependency or the language runtime rather than the application itself
+const LIBRARY_PATHS: [&str; 9] = [
+    "node_modules/",
+    "site-packages/",
+    "dist-packages/",
+    "/lib/python",
+    ".cargo/registry/",
+    "/rustc/",
+    "/pkg/mod/",
+    "/go/src/",
+    "<frozen ",
+];
+
+const LIBRARY_MODULES: [&str; 12] = [
+    "java.", "javax.", "jdk.", "sun.", "kotlin.", "kotlinx.", "scala.", "std::", "core::",
+    "alloc::", "node:", "internal/",
+];
+
+// Widely used JVM libraries and frameworks, whose frames would otherwise pass for
+// application code since JVM traces carry no path to tell them apart
+const JVM_LIBRARY_PACKAGES: [&str; 30] = [
+    "org.springframework.",
+    "org.apache.",
+    "org.hibernate.",
+    "org.postgresql.",
+    "org.eclipse.",
+    "org.glassfish.",
+    "org.jboss.",
+    "org.slf4j.",
+    "org.junit.",
+    "org.mockito.",
+    "org.h2.",
+    "org.flywaydb.",
+    "org.jooq.",
+    "com.fasterxml.",
+    "com.google.",
+    "com.mysql.",
+    "com.zaxxer.",
+    "com.sun.",
+    "io.netty.",
+    "io.grpc.",
+    "io.micrometer.",
+    "io.reactivex.",
+    "io.vertx.",
+    "io.quarkus.",
+    "reactor.",
+    "okhttp3.",
+    "retrofit2.",
+    "ch.qos.logback.",
+    "android.",
+    "androidx.",
+];
+
+impl StackFrame {
+    /// Whether the frame comes from application code rather than a dependency or the runtime.
+    pub fn is_in_app(&self) -> bool {
+        if let Some(in_app) = self.in_app {
+            return in_app;
+        }
+
+        let file_path = self.file_path.replace('\\', "/");
+
+        self.module_name != "runtime"
+            && !LIBRARY_PATHS.iter().any(|path| file_path.contains(path))
+            && !LIBRARY_MODULES
+                .iter()
+                .any(|module| self.module_name.starts_with(module) || file_path.starts_with(module))
+            && !JVM_LIBRARY_PACKAGES
+                .iter()
+                .any(|package| self.module_name.starts_with(package))
+    }
+}
+
+impl From<&StackFrame> for StackFrameDTO {
+    fn from(frame: &StackFrame) -> Self {
+        Self {
+            file: frame.file_path.clone(),
+            line: frame.line_number,
+            column: frame.column_number,
+            function: frame.function_name.clone(),
+            module: frame.module_name.clone(),
+            in_app: frame.is_in_app(),
+        }
+    }
+}
+
+impl From<StackFrameDTO> for StackFrame {
+    fn from(frame: StackFrameDTO) -> Self {
+        let module_name = match frame.module.is_empty() {
+            true => module_from_path(&frame.file),
+            false => frame.module,
+        };
+
+        Self {
+            file_path: frame.file,
+            line_number: frame.line,
+            column_number: frame.column,
+            function_name: frame.function,
+            module_name,
+            in_app: Some(frame.in_app),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StackTraceInfo {
+    pub error_type: String,
+    pub file_path: String,
+    pub line_number: i32,
+    pub function_name: String,
+    pub module_name: String,
+    // Most recent call first, whatever order the platform prints them in
+    pub frames: Vec<StackFrame>,
+}
+
+impl StackTraceInfo {
+    pub fn default() -> Self {
+        StackTraceInfo {
+            error_type: String::new(),
+            file_path: String::new(),
+            line_number: 0,
+            function_name: String::new(),
+            module_name: String::new(),
+            frames: Vec::new(),
+        }
+    }
+
+    /// Builds the info from parsed frames, taking the location of the error from
+    /// the frame at `culprit` (or leaving it empty when there are no frames).
+    pub fn from_frames(error_type: String, frames: Vec<StackFrame>, culprit: usize) -> Self {
+        let mut info = StackTraceInfo {
+            error_type,
+            ..StackTraceInfo::default()
+        };
+
+        if let Some(frame) = frames.get(culprit).or_else(|| frames.first()) {
+            info.file_path = frame.file_path.clone();
+            info.line_number = frame.line_number;
+            info.function_name = frame.function_name.clone();
+            info.module_name = frame.module_name.clone();
+        }
+
+        info.frames = frames;
+        info
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Platform {
+    Python,
+    Jvm,
+    JavaScript,
+    Go,
+    Rust,
+    Generic,
+}
+
+impl FromStr for Platform {
+    type Err = ();
+
+    fn from_str(platform: &str) -> Result<Self, Self::Err> {
+        match platform.trim().to_lowercase().as_str() {
+            "python" => Ok(Platform::Python),
+            "java" | "jvm" | "kotlin" | "scala" | "groovy" => Ok(Platform::Jvm),
+            "javascript" | "js" | "node" | "nodejs" | "typescript" | "browser" => {
+                Ok(Platform::JavaScript)
+            }
+            "go" | "golang" => Ok(Platform::Go),
+            "rust" => Ok(Platform::Rust),
+            "generic" | "other" => Ok(Platform::Generic),
+            _ => Err(()),
+        }
+    }
+}
+
+pub trait StackTraceParser: Send + Sync {
+    fn platform(&self) -> Platform;
+
+    /// Cheap check used by the detector to decide whether this parser understands the trace.
+    fn can_parse(&self, stack_trace: &str) -> bool;
+
+    fn parse(&self, stack_trace: &str) -> Result<StackTraceInfo, &'static str>;
+}
+
+// Detection order matters: Rust backtraces contain `at path:line:col` lines that the
+// JavaScript parser would otherwise claim, so the stricter formats go first.
+static PARSERS: [&dyn StackTraceParser; 5] = [
+    &PythonParser,
+    &JvmParser,
+    &GoParser,
+    &RustParser,
+    &JavaScriptParser,
+];
+
+pub fn parser_for_platform(platform: Platform) -> &'static dyn StackTraceParser {
+    PARSERS
+        .iter()
+        .copied()
+        .find(|parser| parser.platform() == platform)
+        .unwrap_or(&GenericParser)
+}
+
+pub fn detect_parser(stack_trace: &str) -> &'static dyn StackTraceParser {
+    PARSERS
+        .iter()
+        .copied()
+        .find(|parser| parser.can_parse(stack_trace))
+        .unwrap_or(&GenericParser)
+}
+
+/// Parses a stack trace with the parser for `platform` when the SDK named a known one,
+/// falling back to auto-detection otherwise.
+pub fn parse_stack_trace(
+    stack_trace: &str,
+    platform: Option<&str>,
+) -> Result<StackTraceInfo, &'static str> {
+    if stack_trace.trim().is_empty() {
+        return Err("Stack trace is empty");
+    }
+
+    let parser = match platform.and_then(|platform| platform.parse::<Platform>().ok()) {
+        Some(platform) => parser_for_platform(platform),
+        None => detect_parser(stack_trace),
+    };
+
+    parser.parse(stack_trace)
+}
+
+/// Builds the info for a trace whose frames the SDK already sent in structured form
+/// (most recent call first), so the text is only used for the error type.
+pub fn stack_trace_from_frames(stack_trace: &str, frames: Vec<StackFrameDTO>) -> StackTraceInfo {
+    let (error_type, _) = split_error_header(stack_trace.lines().next().unwrap_or_default());
+    let frames: Vec<StackFrame> = frames.into_iter().map(StackFrame::from).collect();
+    let culprit = frames
+        .iter()
+        .position(StackFrame::is_in_app)
+        .unwrap_or_default();
+
+    StackTraceInfo::from_frames(error_type, frames, culprit)
+}
+
+/// Splits a `Type: message` header line, returning an empty type when there is none.
+pub(crate) fn split_error_header(line: &str) -> (String, String) {
+    let line = line.trim();
+    match line.split_once(": ") {
+        Some((error_type, message)) if !error_type.contains(char::is_whitespace) => {
+            (error_type.to_string(), message.to_string())
+        }
+        _ => match line.strip_suffix(':') {
+            Some(error_type) if !error_type.contains(char::is_whitespace) => {
+                (error_type.to_string(), String::new())
+            }
+            _ => (String::new(), line.to_string()),
+        },
+    }
+}
+
+/// Derives a module name from a source path (`/app/services/user.py` -> `user`).
+pub(crate) fn module_from_path(path: &str) -> String {
+    let file_name = path
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or(path)
+        .split(['?', '#'])
+        .next()
+        .unwrap_or_default();
+
+    match file_name.split_once('.') {
+        Some((stem, _)) => stem.to_string(),
+        None => file_name.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    // Each `<name>.txt` trace is checked against the parse result in `<name>.json`
+    macro_rules! fixture {
+        ($name:literal) => {
+            (
+                $name,
+                include_str!(concat!(
+                    env!("CARGO_MANIFEST_DIR"),
+                    "/tests/fixtures/stack_traces/",
+                    $name,
+                    ".txt"
+                )),
+                include_str!(concat!(
+                    env!("CARGO_MANIFEST_DIR"),
+                    "/tests/fixtures/stack_traces/",
+                    $name,
+                    ".json"
+                )),
+            )
+        };
+    }
+
+    const FIXTURES: [(&str, &str, &str); 7] = [
+        fixture!("python"),
+        fixture!("java"),
+        fixture!("node"),
+        fixture!("firefox"),
+        fixture!("go"),
+        fixture!("rust"),
+        fixture!("generic"),
+    ];
+
+    fn parsed(stack_trace: &str, platform: Option<&str>) -> Value {
+        let info = parse_stack_trace(stack_trace, platform).unwrap();
+        let frames: Vec<StackFrameDTO> = info.frames.iter().map(StackFrameDTO::from).collect();
+
+        json!({
+            "platform": format!("{:?}", detect_parser(stack_trace).platform()),
+            "errorType": info.error_type,
+            "filePath": info.file_path,
+            "lineNumber": info.line_number,
+            "frames": frames,
+        })
+    }
+
+    #[test]
+    fn detects_and_parses_fixtures() {
+        for (name, stack_trace, expected) in FIXTURES {
+            let expected: Value = serde_json::from_str(expected).unwrap();
+            assert_eq!(parsed(stack_trace, None), expected, "fixture {}", name);
+        }
+    }
+
+    #[test]
+    fn named_platform_skips_detection() {
+        let (_, stack_trace, expected) = fixture!("java");
+        let expected: Value = serde_json::from_str(expected).unwrap();
+        assert_eq!(parsed(stack_trace, Some("kotlin")), expected);
+
+        // Unknown platforms fall back to detection
+        let (_, stack_trace, expected) = fixture!("go");
+        let expected: Value = serde_json::from_str(expected).unwrap();
+        assert_eq!(parsed(stack_trace, Some("cobol")), expected);
+    }
+
+    #[test]
+    fn rejects_empty_traces() {
+        assert!(parse_stack_trace("  \n", None).is_err());
+    }
+}
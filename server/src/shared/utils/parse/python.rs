@@ -0,0 +1,68 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use super::{
+    module_from_path, split_error_header, Platform, StackFrame, StackTraceInfo, StackTraceParser,
+};
+
+static FRAME_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"^\s*File "(.+)", line (\d+)(?:, in (.+))?$"#).unwrap());
+
+/// Parses CPython tracebacks, including chained exceptions where only the last
+/// traceback (the one that was actually raised) is kept.
+pub struct PythonParser;
+
+impl StackTraceParser for PythonParser {
+    fn platform(&self) -> Platform {
+        Platform::Python
+    }
+
+    fn can_parse(&self, stack_trace: &str) -> bool {
+        stack_trace.contains("Traceback (most recent call last):")
+            || stack_trace.lines().any(|line| FRAME_PATTERN.is_match(line))
+    }
+
+    fn parse(&self, stack_trace: &str) -> Result<StackTraceInfo, &'static str> {
+        let mut frames = Vec::new();
+        let mut header = "";
+
+        for line in stack_trace.lines() {
+            if line.starts_with("Traceback (most recent call last):") {
+                frames.clear();
+                continue;
+            }
+
+            if let Some(caps) = FRAME_PATTERN.captures(line) {
+                let file_path = caps[1].to_string();
+                frames.push(StackFrame {
+                    module_name: module_from_path(&file_path),
+                    in_app: None,
+                    file_path,
+                    line_number: caps[2].parse().unwrap_or(0),
+                    column_number: None,
+                    function_name: caps
+                        .get(3)
+                        .map(|function| function.as_str().to_string())
+                        .unwrap_or_default(),
+                });
+                continue;
+            }
+
+            // The exception line is the last unindented line of the traceback
+            if !line.trim().is_empty() && !line.starts_with(char::is_whitespace) {
+                header = line;
+            }
+        }
+
+        if frames.is_empty() && header.is_empty() {
+            return Err("No Python traceback found");
+        }
+
+        // Python prints the most recent call last
+        frames.reverse();
+
+        let (error_type, _) = split_error_header(header);
+
+        Ok(StackTraceInfo::from_frames(error_type, frames, 0))
+    }
+}
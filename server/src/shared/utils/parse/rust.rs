@@ -0,0 +1,108 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use super::{Platform, StackFrame, StackTraceInfo, StackTraceParser};
+
+// `thread 'main' panicked at src/main.rs:4:5:` (1.73+) or `... panicked at 'msg', src/main.rs:4:5`
+static PANIC_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^thread '(.*?)' panicked at (?:'(.*)', )?(.+?):(\d+):(\d+):?\s*$").unwrap()
+});
+
+// `  12: myapp::handler::h0123456789abcdef` or `  12:     0x55d0 - myapp::handler`
+static FRAME_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*\d+:\s+(?:0x[0-9a-fA-F]+ - )?(.+?)\s*$").unwrap());
+
+static LOCATION_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s+at (.+?):(\d+)(?::(\d+))?\s*$").unwrap());
+
+static HASH_SUFFIX: Lazy<Regex> = Lazy::new(|| Regex::new(r"::h[0-9a-f]{16}$").unwrap());
+
+const RUNTIME_PREFIXES: [&str; 6] = [
+    "std::",
+    "core::",
+    "alloc::",
+    "rust_begin_unwind",
+    "__rust",
+    "<alloc::",
+];
+
+/// Parses Rust panic messages and the `RUST_BACKTRACE` output that follows them.
+pub struct RustParser;
+
+impl StackTraceParser for RustParser {
+    fn platform(&self) -> Platform {
+        Platform::Rust
+    }
+
+    fn can_parse(&self, stack_trace: &str) -> bool {
+        stack_trace.lines().any(|line| PANIC_PATTERN.is_match(line))
+    }
+
+    fn parse(&self, stack_trace: &str) -> Result<StackTraceInfo, &'static str> {
+        let lines: Vec<&str> = stack_trace.lines().collect();
+
+        let (panic_index, caps) = lines
+            .iter()
+            .enumerate()
+            .find_map(|(index, line)| PANIC_PATTERN.captures(line).map(|caps| (index, caps)))
+            .ok_or("No Rust panic found")?;
+
+        let panic_frame = StackFrame {
+            file_path: caps[3].to_string(),
+            line_number: caps[4].parse().unwrap_or(0),
+            column_number: caps[5].parse().ok(),
+            function_name: String::new(),
+            module_name: String::new(),
+            in_app: None,
+        };
+
+        let mut frames: Vec<StackFrame> = Vec::new();
+
+        for line in lines.iter().skip(panic_index + 1) {
+            if let Some(location) = LOCATION_PATTERN.captures(line) {
+                if let Some(frame) = frames.last_mut() {
+                    frame.file_path = location[1].to_string();
+                    frame.line_number = location[2].parse().unwrap_or(0);
+                    frame.column_number = location.get(3).and_then(|col| col.as_str().parse().ok());
+                }
+            } else if let Some(symbol) = FRAME_PATTERN.captures(line) {
+                let symbol = HASH_SUFFIX.replace(&symbol[1], "").to_string();
+                let (module_name, function_name) = match symbol.rsplit_once("::") {
+                    Some((module, function)) => (module.to_string(), function.to_string()),
+                    None => (String::new(), symbol.clone()),
+                };
+
+                frames.push(StackFrame {
+                    file_path: String::new(),
+                    line_number: 0,
+                    column_number: None,
+                    function_name,
+                    module_name,
+                    in_app: None,
+                });
+            }
+        }
+
+        // Frames before the first user frame are the panic machinery itself
+        let first_user_frame = frames.iter().position(|frame| {
+            let symbol = format!("{}::{}", frame.module_name, frame.function_name);
+            !RUNTIME_PREFIXES
+                .iter()
+                .any(|prefix| symbol.starts_with(prefix) || frame.function_name.starts_with(prefix))
+        });
+
+        let frames = match first_user_frame {
+            Some(index) => frames.split_off(index),
+            // No backtrace captured, the panic location is all we have
+            None => vec![panic_frame.clone()],
+        };
+
+        let mut info = StackTraceInfo::from_frames("panic".to_string(), frames, 0);
+
+        // The panic header is the most precise location we get
+        info.file_path = panic_frame.file_path;
+        info.line_number = panic_frame.line_number;
+
+        Ok(info)
+    }
+}
@@ -0,0 +1,285 @@
+use chrono::{DateTime, Duration, NaiveDate, TimeZone, Timelike, Utc};
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, DatabaseConnection, DbErr, EntityTrait, IntoActiveModel,
+    QuerySelect, TransactionTrait,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::models::namespace_model::Model as NamespaceModel;
+use crate::models::namespace_usage_model::{
+    Column as NamespaceUsageColumn, Entity as NamespaceUsageEntity, Model as NamespaceUsageModel,
+};
+use shared_types::namespace_dtos::NamespaceUsageDTO;
+
+/// Ingestion limits of a namespace. A limit of `None` means unlimited.
+#[derive(Clone, Copy, Debug)]
+pub struct QuotaLimits {
+    pub per_minute: Option<u64>,
+    pub per_day: Option<u64>,
+    pub sample_rate: f64,
+}
+
+impl From<&NamespaceModel> for QuotaLimits {
+    fn from(namespace: &NamespaceModel) -> Self {
+        let limit = |quota: Option<i32>| quota.map(|quota| quota.max(0) as u64);
+
+        Self {
+            per_minute: limit(namespace.quota_per_minute),
+            per_day: limit(namespace.quota_per_day),
+            sample_rate: namespace.sample_rate.clamp(0.0, 1.0),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QuotaDecision {
+    Accept,
+    // Discarded by the sample rate, SDKs should not retry these
+    Sampled,
+    // Seconds until the window that was exhausted resets
+    RateLimited { retry_after: u64 },
+}
+
+#[derive(Clone, Copy, Default)]
+struct NamespaceUsage {
+    minute: i64,
+    minute_count: u64,
+    day: Option<NaiveDate>,
+    day_count: u64,
+    dropped: u64,
+    sampled_out: u64,
+}
+
+impl NamespaceUsage {
+    // Starts new windows once the current minute or day is over
+    fn roll(&mut self, now: DateTime<Utc>) {
+        let minute = now.timestamp().div_euclid(60);
+        if self.minute != minute {
+            self.minute = minute;
+            self.minute_count = 0;
+        }
+
+        let today = now.date_naive();
+        if self.day != Some(today) {
+            *self = Self {
+                minute: self.minute,
+                minute_count: self.minute_count,
+                day: Some(today),
+                ..Self::default()
+            };
+        }
+    }
+
+    /// Decides whether one more event is stored. Sampling runs first so that
+    /// sampled-out events never use up the quota.
+    fn decide(&mut self, limits: QuotaLimits, now: DateTime<Utc>) -> QuotaDecision {
+        self.roll(now);
+
+        if limits.sample_rate < 1.0 && rand::random::<f64>() >= limits.sample_rate {
+            self.sampled_out += 1;
+            return QuotaDecision::Sampled;
+        }
+
+        if limits
+            .per_minute
+            .is_some_and(|limit| self.minute_count >= limit)
+        {
+            self.dropped += 1;
+            return QuotaDecision::RateLimited {
+                retry_after: 60 - u64::from(now.second()),
+            };
+        }
+
+        if limits.per_day.is_some_and(|limit| self.day_count >= limit) {
+            self.dropped += 1;
+            return QuotaDecision::RateLimited {
+                retry_after: seconds_until_tomorrow(now),
+            };
+        }
+
+        self.minute_count += 1;
+        self.day_count += 1;
+        QuotaDecision::Accept
+    }
+}
+
+impl From<&NamespaceUsageModel> for NamespaceUsage {
+    fn from(model: &NamespaceUsageModel) -> Self {
+        let count = |count: i64| count.max(0) as u64;
+
+        Self {
+            minute: model.minute,
+            minute_count: count(model.minute_count),
+            day: model.day,
+            day_count: count(model.day_count),
+            dropped: count(model.dropped),
+            sampled_out: count(model.sampled_out),
+        }
+    }
+}
+
+/// Counts ingested events per namespace over a fixed one-minute window and the UTC
+/// day. Counters live in the database, so all server instances share one quota.
+pub struct QuotaTracker {
+    db: Arc<DatabaseConnection>,
+}
+
+impl QuotaTracker {
+    pub fn new(db: Arc<DatabaseConnection>) -> Arc<Self> {
+        Arc::new(Self { db })
+    }
+
+    /// Decides for each of `count` events of the namespace whether it is stored. The
+    /// namespace's counters stay locked until the decisions are written back, so
+    /// concurrent requests can't both take the last free slot.
+    pub async fn check(
+        &self,
+        namespace_id: Uuid,
+        limits: QuotaLimits,
+        count: usize,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<QuotaDecision>, DbErr> {
+        let transaction = self.db.begin().await?;
+
+        // Makes sure there is a row to lock the first time the namespace ingests
+        NamespaceUsageEntity::insert(
+            NamespaceUsageModel {
+                namespace_id,
+                minute: 0,
+                minute_count: 0,
+                day: None,
+                day_count: 0,
+                dropped: 0,
+                sampled_out: 0,
+                updated_at: now,
+            }
+            .into_active_model(),
+        )
+        .on_conflict(
+            OnConflict::column(NamespaceUsageColumn::NamespaceId)
+                .do_nothing()
+                .to_owned(),
+        )
+        .do_nothing()
+        .exec(&transaction)
+        .await?;
+
+        let stored = NamespaceUsageEntity::find_by_id(namespace_id)
+            .lock_exclusive()
+            .one(&transaction)
+            .await?
+            .ok_or_else(|| DbErr::RecordNotFound("namespace_usage".to_string()))?;
+
+        let mut usage = NamespaceUsage::from(&stored);
+        let decisions = (0..count).map(|_| usage.decide(limits, now)).collect();
+
+        let mut update = stored.into_active_model();
+        update.minute = ActiveValue::Set(usage.minute);
+        update.minute_count = ActiveValue::Set(usage.minute_count as i64);
+        update.day = ActiveValue::Set(usage.day);
+        update.day_count = ActiveValue::Set(usage.day_count as i64);
+        update.dropped = ActiveValue::Set(usage.dropped as i64);
+        update.sampled_out = ActiveValue::Set(usage.sampled_out as i64);
+        update.updated_at = ActiveValue::Set(now);
+        update.update(&transaction).await?;
+
+        transaction.commit().await?;
+
+        Ok(decisions)
+    }
+
+    pub async fn usage(
+        &self,
+        namespace_id: Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<NamespaceUsageDTO, DbErr> {
+        let mut usage = NamespaceUsageEntity::find_by_id(namespace_id)
+            .one(&*self.db)
+            .await?
+            .as_ref()
+            .map(NamespaceUsage::from)
+            .unwrap_or_default();
+        usage.roll(now);
+
+        Ok(NamespaceUsageDTO {
+            events_this_minute: usage.minute_count,
+            events_today: usage.day_count,
+            dropped_today: usage.dropped,
+            sampled_out_today: usage.sampled_out,
+        })
+    }
+}
+
+fn seconds_until_tomorrow(now: DateTime<Utc>) -> u64 {
+    let tomorrow = now.date_naive() + Duration::days(1);
+    let midnight = Utc.from_utc_datetime(&tomorrow.and_hms_opt(0, 0, 0).unwrap());
+    (midnight - now).num_seconds().max(1) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(per_minute: Option<u64>, per_day: Option<u64>) -> QuotaLimits {
+        QuotaLimits {
+            per_minute,
+            per_day,
+            sample_rate: 1.0,
+        }
+    }
+
+    fn at(hour: u32, minute: u32, second: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, 17, hour, minute, second)
+            .unwrap()
+    }
+
+    #[test]
+    fn refuses_events_over_the_minute_limit_until_the_next_minute() {
+        let mut usage = NamespaceUsage::default();
+        let limits = limits(Some(2), None);
+
+        assert_eq!(usage.decide(limits, at(12, 0, 10)), QuotaDecision::Accept);
+        assert_eq!(usage.decide(limits, at(12, 0, 20)), QuotaDecision::Accept);
+        assert_eq!(
+            usage.decide(limits, at(12, 0, 45)),
+            QuotaDecision::RateLimited { retry_after: 15 }
+        );
+        assert_eq!(usage.decide(limits, at(12, 1, 0)), QuotaDecision::Accept);
+        assert_eq!(usage.dropped, 1);
+    }
+
+    #[test]
+    fn resets_the_daily_counters_at_midnight() {
+        let mut usage = NamespaceUsage::default();
+        let limits = limits(None, Some(1));
+
+        assert_eq!(usage.decide(limits, at(23, 59, 0)), QuotaDecision::Accept);
+        assert_eq!(
+            usage.decide(limits, at(23, 59, 30)),
+            QuotaDecision::RateLimited { retry_after: 30 }
+        );
+
+        let tomorrow = at(0, 0, 5) + Duration::days(1);
+        assert_eq!(usage.decide(limits, tomorrow), QuotaDecision::Accept);
+        assert_eq!(usage.day_count, 1);
+        assert_eq!(usage.dropped, 0);
+    }
+
+    #[test]
+    fn sampled_out_events_do_not_use_up_the_quota() {
+        let mut usage = NamespaceUsage::default();
+        let limits = QuotaLimits {
+            per_minute: Some(1),
+            per_day: None,
+            sample_rate: 0.0,
+        };
+
+        for _ in 0..3 {
+            assert_eq!(usage.decide(limits, at(12, 0, 0)), QuotaDecision::Sampled);
+        }
+        assert_eq!(usage.minute_count, 0);
+        assert_eq!(usage.sampled_out, 3);
+    }
+}
@@ -0,0 +1,465 @@
+use hmac::{Hmac, Mac};
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex, RegexBuilder};
+use serde_json::Value;
+use sha2::Sha256;
+use std::collections::HashSet;
+use std::net::Ipv6Addr;
+
+use shared_types::error_dtos::CreateErrorRequest;
+
+// Limits on the custom rules a namespace may configure
+pub const MAX_SCRUB_RULES: usize = 50;
+pub const MAX_SCRUB_PATTERN_LENGTH: usize = 512;
+const MAX_COMPILED_PATTERN_SIZE: usize = 1 << 20;
+
+static BEARER_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)\bbearer\s+[a-z0-9\-._~+/]+=*").unwrap());
+
+static EMAIL_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)\b[a-z0-9._%+\-]+@[a-z0-9.\-]+\.[a-z]{2,}\b").unwrap());
+
+// Candidate card numbers, 13 to 19 digits optionally grouped by spaces or dashes
+static CARD_PATTERN: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b(?:\d[ \-]?){12,18}\d\b").unwrap());
+
+static IPV4_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"\b(?:(?:25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)\.){3}(?:25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)\b",
+    )
+    .unwrap()
+});
+
+static IPV6_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)\b[0-9a-f]{0,4}(?::[0-9a-f]{0,4}){2,7}\b").unwrap());
+
+// Credentials passed in a URL's query string
+static SECRET_QUERY_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)([?&](?:access_token|api_key|apikey|auth|key|password|secret|token)=)[^&#]*")
+        .unwrap()
+});
+
+// Which detectors run on a field. Card numbers and IP addresses are only looked for in
+// text written for people, in stack traces and structured data they mostly match ids,
+// offsets and version numbers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Detectors {
+    All,
+    Code,
+}
+
+// Request headers whose value is dropped entirely, compared lowercase
+const SENSITIVE_HEADERS: [&str; 7] = [
+    "authorization",
+    "cookie",
+    "proxy-authorization",
+    "set-cookie",
+    "x-api-key",
+    "x-auth-token",
+    "x-csrf-token",
+];
+
+/// Compiles a namespace's custom scrubbing rules, failing on the first invalid pattern.
+pub fn compile_rules(patterns: &[String]) -> Result<Vec<Regex>, regex::Error> {
+    patterns
+        .iter()
+        .map(|pattern| {
+            RegexBuilder::new(pattern)
+                .size_limit(MAX_COMPILED_PATTERN_SIZE)
+                .build()
+        })
+        .collect()
+}
+
+/// Redacts personal data from incoming errors: emails, card numbers passing the Luhn
+/// check, bearer tokens and IP addresses, plus whatever the namespace's own rules match.
+/// Tags whose key is denylisted have their value removed, as do credential headers and
+/// query parameters of the request context. Emails in the affected user are replaced by
+/// a keyed pseudonym instead, so the same user is still counted once.
+pub struct Scrubber {
+    rules: Vec<Regex>,
+    denied_tag_keys: HashSet<String>,
+    pseudonym_key: Vec<u8>,
+}
+
+impl Scrubber {
+    pub fn new(rules: Vec<Regex>, denied_tag_keys: &[String], pseudonym_key: &[u8]) -> Self {
+        Self {
+            rules,
+            denied_tag_keys: denied_tag_keys
+                .iter()
+                .map(|key| key.to_lowercase())
+                .collect(),
+            pseudonym_key: pseudonym_key.to_vec(),
+        }
+    }
+
+    /// Scrubs the request in place and returns the names of the fields that were
+    /// redacted, e.g. `message` or `tags.email`.
+    pub fn scrub_request(&self, request: &mut CreateErrorRequest) -> Vec<String> {
+        let mut redacted_fields = Vec::new();
+
+        if self.scrub_text(&mut request.message, Detectors::All) {
+            redacted_fields.push("message".to_string());
+        }
+        if self.scrub_text(&mut request.stack_trace, Detectors::Code) {
+            redacted_fields.push("stackTrace".to_string());
+        }
+        if self.scrub_user(&mut request.user_affected) {
+            redacted_fields.push("userAffected".to_string());
+        }
+
+        for tag in request.tags.iter_mut().flatten() {
+            let redacted = match self.denied_tag_keys.contains(&tag.tag_key.to_lowercase()) {
+                true => {
+                    tag.tag_value = "[redacted]".to_string();
+                    true
+                }
+                false => self.scrub_text(&mut tag.tag_value, Detectors::All),
+            };
+            if redacted {
+                redacted_fields.push(format!("tags.{}", tag.tag_key));
+            }
+        }
+
+        let mut breadcrumbs_redacted = false;
+        for breadcrumb in request.breadcrumbs.iter_mut().flatten() {
+            if let Some(message) = breadcrumb.message.as_mut() {
+                breadcrumbs_redacted |= self.scrub_text(message, Detectors::All);
+            }
+            for value in breadcrumb
+                .data
+                .iter_mut()
+                .flat_map(|data| data.values_mut())
+            {
+                breadcrumbs_redacted |= self.scrub_value(value);
+            }
+        }
+        if breadcrumbs_redacted {
+            redacted_fields.push("breadcrumbs".to_string());
+        }
+
+        if let Some(contexts) = request.contexts.as_mut() {
+            if let Some(http_request) = contexts.request.as_mut() {
+                let mut request_redacted = false;
+                if let Some(url) = http_request.url.as_mut() {
+                    request_redacted |= self.scrub_url(url);
+                }
+                for (name, value) in http_request.headers.iter_mut().flatten() {
+                    request_redacted |=
+                        match SENSITIVE_HEADERS.contains(&name.to_lowercase().as_str()) {
+                            true => {
+                                *value = "[redacted]".to_string();
+                                true
+                            }
+                            false => self.scrub_text(value, Detectors::All),
+                        };
+                }
+                if request_redacted {
+                    redacted_fields.push("contexts.request".to_string());
+                }
+            }
+
+            let extra_redacted = contexts
+                .extra
+                .iter_mut()
+                .flat_map(|extra| extra.values_mut())
+                .fold(false, |redacted, value| self.scrub_value(value) | redacted);
+            if extra_redacted {
+                redacted_fields.push("contexts.extra".to_string());
+            }
+        }
+
+        redacted_fields
+    }
+
+    // The affected user is grouped on, so emails become a stable pseudonym there
+    fn scrub_user(&self, user: &mut String) -> bool {
+        let pseudonymized = EMAIL_PATTERN
+            .replace_all(user, |captures: &Captures| self.pseudonym(&captures[0]))
+            .into_owned();
+        let email_redacted = pseudonymized != *user;
+        *user = pseudonymized;
+        self.scrub_text(user, Detectors::All) | email_redacted
+    }
+
+    // `user-` and the start of the email's HMAC, keyed so it can't be reversed by
+    // hashing guessed addresses
+    fn pseudonym(&self, email: &str) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.pseudonym_key)
+            .expect("HMAC accepts keys of any length");
+        mac.update(email.to_lowercase().as_bytes());
+        let digest = hex::encode(mac.finalize().into_bytes());
+
+        format!("user-{}", &digest[..16])
+    }
+
+    // Returns whether anything was replaced
+    fn scrub_text(&self, text: &mut String, detectors: Detectors) -> bool {
+        let mut scrubbed = BEARER_PATTERN
+            .replace_all(text, "Bearer [token]")
+            .into_owned();
+        scrubbed = EMAIL_PATTERN.replace_all(&scrubbed, "[email]").into_owned();
+        if detectors == Detectors::All {
+            scrubbed = CARD_PATTERN
+                .replace_all(&scrubbed, |captures: &Captures| {
+                    match is_card_number(&captures[0]) {
+                        true => "[card]".to_string(),
+                        false => captures[0].to_string(),
+                    }
+                })
+                .into_owned();
+            scrubbed = IPV4_PATTERN.replace_all(&scrubbed, "[ip]").into_owned();
+            scrubbed = IPV6_PATTERN
+                .replace_all(&scrubbed, |captures: &Captures| {
+                    match is_ipv6(&captures[0]) {
+                        true => "[ip]".to_string(),
+                        false => captures[0].to_string(),
+                    }
+                })
+                .into_owned();
+        }
+
+        for rule in &self.rules {
+            scrubbed = rule.replace_all(&scrubbed, "[redacted]").into_owned();
+        }
+
+        if scrubbed == *text {
+            return false;
+        }
+        *text = scrubbed;
+        true
+    }
+
+    fn scrub_url(&self, url: &mut String) -> bool {
+        let scrubbed = SECRET_QUERY_PATTERN
+            .replace_all(url, "${1}[redacted]")
+            .into_owned();
+        let query_redacted = scrubbed != *url;
+        *url = scrubbed;
+        self.scrub_text(url, Detectors::All) | query_redacted
+    }
+
+    fn scrub_value(&self, value: &mut Value) -> bool {
+        match value {
+            Value::String(text) => self.scrub_text(text, Detectors::Code),
+            Value::Array(values) => values
+                .iter_mut()
+                .fold(false, |redacted, value| self.scrub_value(value) | redacted),
+            Value::Object(map) => map
+                .values_mut()
+                .fold(false, |redacted, value| self.scrub_value(value) | redacted),
+            _ => false,
+        }
+    }
+}
+
+fn is_card_number(candidate: &str) -> bool {
+    let digits: Vec<u32> = candidate.chars().filter_map(|c| c.to_digit(10)).collect();
+    if !(13..=19).contains(&digits.len()) {
+        return false;
+    }
+
+    // Luhn: double every second digit from the right
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(position, &digit)| match position % 2 {
+            1 if digit * 2 > 9 => digit * 2 - 9,
+            1 => digit * 2,
+            _ => digit,
+        })
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+// Paths such as `dead::beef` parse as IPv6 too, so short forms are left alone
+fn is_ipv6(candidate: &str) -> bool {
+    candidate.matches(':').count() >= 3 && candidate.parse::<Ipv6Addr>().is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared_types::error_dtos::{ErrorContextsDTO, RequestContextDTO};
+    use shared_types::tag_dtos::CreateTagClientNoIdDTO;
+    use std::collections::BTreeMap;
+
+    const KEY: &[u8] = b"test-key";
+
+    fn scrubber() -> Scrubber {
+        Scrubber::new(vec![], &["session".to_string()], KEY)
+    }
+
+    fn request(message: &str, stack_trace: &str, user_affected: &str) -> CreateErrorRequest {
+        CreateErrorRequest {
+            user_affected: user_affected.to_string(),
+            stack_trace: stack_trace.to_string(),
+            message: message.to_string(),
+            tags: None,
+            platform: None,
+            frames: None,
+            breadcrumbs: None,
+            release: None,
+            environment: None,
+            event_id: None,
+            contexts: None,
+            level: None,
+        }
+    }
+
+    fn scrubbed(text: &str) -> String {
+        let mut text = text.to_string();
+        scrubber().scrub_text(&mut text, Detectors::All);
+        text
+    }
+
+    #[test]
+    fn luhn_accepts_valid_card_numbers_only() {
+        assert!(is_card_number("4111111111111111"));
+        assert!(is_card_number("4111 1111 1111 1111"));
+        assert!(is_card_number("5500-0000-0000-0004"));
+        assert!(is_card_number("378282246310005"));
+        // One digit off
+        assert!(!is_card_number("4111111111111112"));
+        // Too short and too long
+        assert!(!is_card_number("411111111111"));
+        assert!(!is_card_number("41111111111111111111"));
+    }
+
+    #[test]
+    fn detects_personal_data() {
+        assert_eq!(
+            scrubbed("mail jane.doe@example.com now"),
+            "mail [email] now"
+        );
+        assert_eq!(
+            scrubbed("Authorization: Bearer abc.DEF-123_~+/=="),
+            "Authorization: Bearer [token]"
+        );
+        assert_eq!(
+            scrubbed("card 4111 1111 1111 1111 declined"),
+            "card [card] declined"
+        );
+        assert_eq!(scrubbed("from 192.168.1.20:8080"), "from [ip]:8080");
+        assert_eq!(scrubbed("from 2001:db8::8a2e:370:7334"), "from [ip]");
+    }
+
+    #[test]
+    fn leaves_lookalikes_alone() {
+        // Fails the Luhn check
+        assert_eq!(scrubbed("order 4111111111111112"), "order 4111111111111112");
+        // Not a valid octet
+        assert_eq!(scrubbed("version 1.2.3.400"), "version 1.2.3.400");
+        // Rust paths parse as short IPv6 addresses
+        assert_eq!(scrubbed("at core::fmt::write"), "at core::fmt::write");
+    }
+
+    #[test]
+    fn stack_traces_keep_numbers_and_addresses() {
+        let mut request = request(
+            "payment 4111111111111111 from 10.0.0.1 failed",
+            "Error: failed for 4111111111111111 at 10.0.0.1\n    at pay (/app/pay.js:1:1)",
+            "anonymous",
+        );
+
+        let redacted = scrubber().scrub_request(&mut request);
+
+        assert_eq!(request.message, "payment [card] from [ip] failed");
+        assert!(request.stack_trace.contains("4111111111111111"));
+        assert!(request.stack_trace.contains("10.0.0.1"));
+        assert_eq!(redacted, vec!["message".to_string()]);
+    }
+
+    #[test]
+    fn stack_traces_still_lose_emails_and_tokens() {
+        let mut request = request(
+            "failed",
+            "Error: no user jane@example.com with Bearer secret123",
+            "anonymous",
+        );
+
+        scrubber().scrub_request(&mut request);
+
+        assert_eq!(
+            request.stack_trace,
+            "Error: no user [email] with Bearer [token]"
+        );
+    }
+
+    #[test]
+    fn pseudonymizes_the_affected_user() {
+        let mut first = request("failed", "Error", "Jane@Example.com");
+        let mut second = request("failed", "Error", "jane@example.com");
+        let mut other = request("failed", "Error", "john@example.com");
+
+        let redacted = scrubber().scrub_request(&mut first);
+        scrubber().scrub_request(&mut second);
+        scrubber().scrub_request(&mut other);
+
+        assert!(first.user_affected.starts_with("user-"));
+        assert_eq!(first.user_affected.len(), "user-".len() + 16);
+        assert_eq!(first.user_affected, second.user_affected);
+        assert_ne!(first.user_affected, other.user_affected);
+        assert_eq!(redacted, vec!["userAffected".to_string()]);
+
+        // A different key gives unrelated pseudonyms
+        let mut rekeyed = request("failed", "Error", "jane@example.com");
+        Scrubber::new(vec![], &[], b"other-key").scrub_request(&mut rekeyed);
+        assert_ne!(rekeyed.user_affected, first.user_affected);
+    }
+
+    #[test]
+    fn redacts_tags_headers_and_query_secrets() {
+        let mut request = request("failed", "Error", "anonymous");
+        request.tags = Some(vec![
+            CreateTagClientNoIdDTO {
+                tag_key: "Session".to_string(),
+                tag_value: "abc".to_string(),
+            },
+            CreateTagClientNoIdDTO {
+                tag_key: "region".to_string(),
+                tag_value: "eu".to_string(),
+            },
+        ]);
+        request.contexts = Some(ErrorContextsDTO {
+            request: Some(RequestContextDTO {
+                method: Some("GET".to_string()),
+                url: Some("https://shop.example.com/cart?id=4&token=s3cret#top".to_string()),
+                headers: Some(BTreeMap::from([
+                    ("Cookie".to_string(), "sid=1".to_string()),
+                    ("Accept".to_string(), "text/html".to_string()),
+                ])),
+            }),
+            ..ErrorContextsDTO::default()
+        });
+
+        let redacted = scrubber().scrub_request(&mut request);
+
+        let tags = request.tags.unwrap();
+        assert_eq!(tags[0].tag_value, "[redacted]");
+        assert_eq!(tags[1].tag_value, "eu");
+        let http_request = request.contexts.unwrap().request.unwrap();
+        assert_eq!(
+            http_request.url.as_deref(),
+            Some("https://shop.example.com/cart?id=4&token=[redacted]#top")
+        );
+        let headers = http_request.headers.unwrap();
+        assert_eq!(headers["Cookie"], "[redacted]");
+        assert_eq!(headers["Accept"], "text/html");
+        assert_eq!(
+            redacted,
+            vec!["tags.Session".to_string(), "contexts.request".to_string()]
+        );
+    }
+
+    #[test]
+    fn applies_custom_rules() {
+        let rules = compile_rules(&[r"ORD-\d+".to_string()]).unwrap();
+        let mut text = "order ORD-1234 failed".to_string();
+
+        assert!(Scrubber::new(rules, &[], KEY).scrub_text(&mut text, Detectors::Code));
+        assert_eq!(text, "order [redacted] failed");
+        assert!(compile_rules(&["(".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,139 @@
+use once_cell::sync::Lazy;
+use reqwest::{Client, Url};
+use serde_json::{json, Value};
+use std::time::Duration;
+
+use crate::shared::utils::errors::{ExternalError, RequestError, ServerError};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+// Slack rejects fields over 2000 characters
+const MAX_ERROR_MESSAGE: usize = 1000;
+
+static SLACK_CLIENT: Lazy<Client> = Lazy::new(|| {
+    Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .expect("Failed to build the Slack HTTP client")
+});
+
+/// What a Slack alert message shows.
+pub struct SlackAlert<'a> {
+    pub title: &'a str,
+    pub details: &'a str,
+    pub namespace: &'a str,
+    // The latest error in the alert's scope
+    pub error_message: Option<&'a str>,
+    pub location: Option<String>,
+    pub count: u64,
+    pub dashboard_url: &'a str,
+    pub namespace_url: String,
+}
+
+/// Incoming webhook URLs look like `https://hooks.slack.com/services/T000/B000/XXXX`.
+pub fn validate_slack_webhook_url(webhook_url: &str) -> Result<(), RequestError> {
+    match Url::parse(webhook_url) {
+        Ok(url)
+            if url.scheme() == "https"
+                && url.host_str() == Some("hooks.slack.com")
+                && url.path().starts_with("/services/") =>
+        {
+            Ok(())
+        }
+        _ => Err(RequestError::InvalidSlackWebhookUrl),
+    }
+}
+
+/// Hides the token at the end of an incoming webhook URL, anyone holding it can post to
+/// the channel. The team and channel IDs stay visible to tell webhooks apart.
+pub fn redact_slack_webhook_url(webhook_url: &str) -> String {
+    match webhook_url.rsplit_once('/') {
+        Some((prefix, _)) => format!("{}/********", prefix),
+        None => "********".to_string(),
+    }
+}
+
+pub async fn send_slack_alert(
+    webhook_url: &str,
+    alert: &SlackAlert<'_>,
+) -> Result<(), ServerError> {
+    let response = SLACK_CLIENT
+        .post(webhook_url)
+        .json(&slack_message(alert))
+        .send()
+        .await
+        .map_err(ExternalError::from)?;
+
+    if !response.status().is_success() {
+        return Err(ServerError::ExternalError(ExternalError::Slack(
+            response.status().as_u16(),
+        )));
+    }
+
+    Ok(())
+}
+
+fn slack_message(alert: &SlackAlert<'_>) -> Value {
+    let mut fields = vec![
+        json!({ "type": "mrkdwn", "text": format!("*Namespace*\n{}", escape(alert.namespace)) }),
+        json!({ "type": "mrkdwn", "text": format!("*Errors in the last hour*\n{}", alert.count) }),
+    ];
+    if let Some(error_message) = alert.error_message {
+        fields.push(
+            json!({ "type": "mrkdwn", "text": format!("*Error*\n{}", escape(&clip(error_message))) }),
+        );
+    }
+    if let Some(location) = &alert.location {
+        fields.push(
+            json!({ "type": "mrkdwn", "text": format!("*Location*\n`{}`", escape(location)) }),
+        );
+    }
+
+    json!({
+        // Shown in notifications, where blocks aren't rendered
+        "text": format!("{}: {}", alert.title, alert.details),
+        "blocks": [
+            {
+                "type": "header",
+                "text": { "type": "plain_text", "text": alert.title }
+            },
+            {
+                "type": "section",
+                "text": { "type": "mrkdwn", "text": escape(alert.details) }
+            },
+            {
+                "type": "section",
+                "fields": fields
+            },
+            {
+                "type": "actions",
+                "elements": [
+                    {
+                        "type": "button",
+                        "text": { "type": "plain_text", "text": "Open dashboard" },
+                        "url": alert.dashboard_url,
+                        "style": "primary"
+                    },
+                    {
+                        "type": "button",
+                        "text": { "type": "plain_text", "text": "View namespace" },
+                        "url": alert.namespace_url
+                    }
+                ]
+            }
+        ]
+    })
+}
+
+// Slack reads these three as control characters in mrkdwn
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn clip(text: &str) -> String {
+    match text.char_indices().nth(MAX_ERROR_MESSAGE) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text.to_string(),
+    }
+}
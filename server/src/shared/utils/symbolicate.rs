@@ -0,0 +1,289 @@
+use chrono::{DateTime, Utc};
+use hashlink::LinkedHashMap;
+use sourcemap::DecodedMap;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+use crate::shared::utils::parse::{module_from_path, StackFrame};
+
+const JAVASCRIPT_EXTENSIONS: [&str; 3] = [".js", ".mjs", ".cjs"];
+
+/// Decoded source maps shared across events, least recently used first. The budget
+/// is counted in uploaded source map bytes, which is what the decoded size follows.
+pub struct SourceMapCache {
+    capacity: usize,
+    entries: Mutex<CachedSourceMaps>,
+}
+
+#[derive(Default)]
+struct CachedSourceMaps {
+    maps: LinkedHashMap<Uuid, CachedSourceMap>,
+    size: usize,
+}
+
+struct CachedSourceMap {
+    // The artifact's `updated_at`, so a map re-uploaded through another server
+    // instance isn't served stale
+    version: DateTime<Utc>,
+    size: usize,
+    map: Arc<DecodedMap>,
+}
+
+impl SourceMapCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(CachedSourceMaps::default()),
+        }
+    }
+
+    /// The cached map for this version of the artifact, marked as recently used.
+    pub fn get(&self, artifact_id: Uuid, version: DateTime<Utc>) -> Option<Arc<DecodedMap>> {
+        let mut entries = self.entries.lock().unwrap();
+
+        match entries.maps.to_back(&artifact_id) {
+            Some(cached) if cached.version == version => Some(cached.map.clone()),
+            Some(_) => {
+                entries.remove(artifact_id);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Caches a map, evicting the least recently used ones until it fits. A map larger
+    /// than the whole budget isn't cached.
+    pub fn insert(
+        &self,
+        artifact_id: Uuid,
+        version: DateTime<Utc>,
+        size: usize,
+        map: Arc<DecodedMap>,
+    ) {
+        if size > self.capacity {
+            return;
+        }
+
+        let mut entries = self.entries.lock().unwrap();
+        entries.remove(artifact_id);
+
+        while entries.size + size > self.capacity {
+            match entries.maps.pop_front() {
+                Some((_, evicted)) => entries.size -= evicted.size,
+                None => break,
+            }
+        }
+
+        entries.size += size;
+        entries
+            .maps
+            .insert(artifact_id, CachedSourceMap { version, size, map });
+    }
+
+    pub fn invalidate(&self, artifact_id: Uuid) {
+        self.entries.lock().unwrap().remove(artifact_id);
+    }
+}
+
+impl CachedSourceMaps {
+    fn remove(&mut self, artifact_id: Uuid) {
+        if let Some(removed) = self.maps.remove(&artifact_id) {
+            self.size -= removed.size;
+        }
+    }
+}
+
+/// Whether a frame points into a generated JavaScript bundle that a source map could
+/// resolve. Frames without a column can't be mapped.
+pub fn is_minified_frame(frame: &StackFrame) -> bool {
+    let path = strip_query(&frame.file_path);
+
+    frame.column_number.is_some()
+        && JAVASCRIPT_EXTENSIONS
+            .iter()
+            .any(|extension| path.ends_with(extension))
+}
+
+/// Reduces an artifact name or a frame path to the path part of its URL, so that
+/// `https://cdn.example.com/static/main.js?v=2`, `~/static/main.js` and
+/// `/static/main.js` all compare equal. A trailing `.map` is dropped as well.
+pub fn normalize_artifact_name(name: &str) -> String {
+    let name = strip_query(name.trim());
+    let name = name.strip_suffix(".map").unwrap_or(name);
+
+    let path = match name.split_once("://") {
+        Some((_, rest)) => rest.find('/').map(|index| &rest[index..]).unwrap_or(""),
+        None => name.strip_prefix('~').unwrap_or(name),
+    };
+
+    format!("/{}", path.trim_start_matches('/'))
+}
+
+/// File name component of a normalized artifact name, used when the uploaded name
+/// doesn't carry the same directories as the deployed URL.
+pub fn artifact_basename(name: &str) -> &str {
+    name.rsplit('/').next().unwrap_or(name)
+}
+
+/// Rewrites a frame to its original location. Returns `false` and leaves the frame
+/// untouched when the map has no mapping for that exact line.
+pub fn symbolicate_frame(source_map: &DecodedMap, frame: &mut StackFrame) -> bool {
+    let (Some(column), Ok(line)) = (frame.column_number, u32::try_from(frame.line_number - 1))
+    else {
+        return false;
+    };
+    let Ok(column) = u32::try_from(column - 1) else {
+        return false;
+    };
+
+    // The lookup returns the closest token before the position, which may sit on an
+    // earlier line when this one has no mappings
+    let Some(token) = source_map
+        .lookup_token(line, column)
+        .filter(|token| token.get_dst_line() == line)
+    else {
+        return false;
+    };
+    let Some(source) = token.get_source() else {
+        return false;
+    };
+
+    frame.file_path = clean_source_path(source);
+    frame.module_name = module_from_path(&frame.file_path);
+    // Whether the bundle was app code says nothing about the original file
+    frame.in_app = None;
+    frame.line_number = token.get_src_line() as i32 + 1;
+    frame.column_number = Some(token.get_src_col() as i32 + 1);
+    if let Some(name) = token.get_name() {
+        frame.function_name = name.to_string();
+    }
+
+    true
+}
+
+// Bundlers prefix sources with their own scheme (`webpack:///./src/app.js`)
+fn clean_source_path(source: &str) -> String {
+    let source = source.strip_prefix("webpack://").unwrap_or(source);
+    let source = source.trim_start_matches('/');
+
+    source.strip_prefix("./").unwrap_or(source).to_string()
+}
+
+fn strip_query(path: &str) -> &str {
+    path.split(['?', '#']).next().unwrap_or(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use sourcemap::SourceMapBuilder;
+
+    fn frame(file_path: &str, line_number: i32, column_number: Option<i32>) -> StackFrame {
+        StackFrame {
+            file_path: file_path.to_string(),
+            line_number,
+            column_number,
+            function_name: "a".to_string(),
+            module_name: "main".to_string(),
+            in_app: Some(false),
+        }
+    }
+
+    // Line 1 column 11 of the bundle maps to line 42 column 5 of src/app.js
+    fn source_map() -> DecodedMap {
+        let mut builder = SourceMapBuilder::new(Some("main.js"));
+        builder.add(
+            0,
+            10,
+            41,
+            4,
+            Some("webpack:///./src/app.js"),
+            Some("handleClick"),
+            false,
+        );
+
+        DecodedMap::Regular(builder.into_sourcemap())
+    }
+
+    #[test]
+    fn normalize_artifact_name_reduces_urls_to_paths() {
+        for name in [
+            "https://cdn.example.com/static/main.js?v=2",
+            "https://cdn.example.com/static/main.js#top",
+            "~/static/main.js",
+            "/static/main.js",
+            "static/main.js",
+            " static/main.js.map ",
+        ] {
+            assert_eq!(normalize_artifact_name(name), "/static/main.js", "{}", name);
+        }
+        assert_eq!(normalize_artifact_name("https://cdn.example.com"), "/");
+    }
+
+    #[test]
+    fn symbolicate_frame_rewrites_mapped_frames() {
+        let mut mapped = frame("https://cdn.example.com/main.js", 1, Some(11));
+
+        assert!(symbolicate_frame(&source_map(), &mut mapped));
+        assert_eq!(mapped.file_path, "src/app.js");
+        assert_eq!(mapped.module_name, "app");
+        assert_eq!(mapped.line_number, 42);
+        assert_eq!(mapped.column_number, Some(5));
+        assert_eq!(mapped.function_name, "handleClick");
+        assert_eq!(mapped.in_app, None);
+    }
+
+    #[test]
+    fn symbolicate_frame_leaves_unmapped_frames_untouched() {
+        for unmapped in [
+            // No mappings on that line
+            frame("main.js", 2, Some(11)),
+            // No column to look up
+            frame("main.js", 1, None),
+            frame("main.js", 0, Some(11)),
+        ] {
+            let mut symbolicated = unmapped.clone();
+            assert!(!symbolicate_frame(&source_map(), &mut symbolicated));
+            assert_eq!(symbolicated, unmapped);
+        }
+    }
+
+    #[test]
+    fn source_map_cache_evicts_least_recently_used_maps() {
+        let cache = SourceMapCache::new(100);
+        let version = Utc::now();
+        let (first, second, third) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+
+        cache.insert(first, version, 40, Arc::new(source_map()));
+        cache.insert(second, version, 40, Arc::new(source_map()));
+        assert!(cache.get(first, version).is_some());
+
+        cache.insert(third, version, 40, Arc::new(source_map()));
+        assert!(cache.get(first, version).is_some());
+        assert!(cache.get(second, version).is_none());
+        assert!(cache.get(third, version).is_some());
+
+        // Too large for the whole budget, and nothing is evicted for it
+        cache.insert(second, version, 101, Arc::new(source_map()));
+        assert!(cache.get(second, version).is_none());
+        assert!(cache.get(first, version).is_some());
+    }
+
+    #[test]
+    fn source_map_cache_drops_stale_and_invalidated_maps() {
+        let cache = SourceMapCache::new(100);
+        let version = Utc::now();
+        let artifact_id = Uuid::new_v4();
+
+        cache.insert(artifact_id, version, 10, Arc::new(source_map()));
+        assert!(cache
+            .get(artifact_id, version + Duration::seconds(1))
+            .is_none());
+        assert!(cache.get(artifact_id, version).is_none());
+
+        cache.insert(artifact_id, version, 10, Arc::new(source_map()));
+        cache.invalidate(artifact_id);
+        assert!(cache.get(artifact_id, version).is_none());
+    }
+}
@@ -0,0 +1,30 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_valid::Validate;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtifactDTO {
+    pub id: Uuid,
+    pub namespace_id: Uuid,
+    pub release: String,
+    pub name: String,
+    pub size: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadArtifactQueryParams {
+    pub release: String,
+    // The minified file the source map belongs to, as it appears in stack traces
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtifactQueryParams {
+    pub release: String,
+}
@@ -0,0 +1,31 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_valid::Validate;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct AttachmentDTO {
+    pub id: Uuid,
+    pub namespace_id: Uuid,
+    pub error_id: Uuid,
+    pub file_name: String,
+    pub content_type: String,
+    pub size: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadAttachmentQueryParams {
+    // The error the file belongs to, as returned when it was created
+    pub error_id: Uuid,
+    // Shown and used as the download name, e.g. `crash.log`
+    pub file_name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AttachmentPathParams {
+    pub error_id: Uuid,
+    pub attachment_id: Uuid,
+}
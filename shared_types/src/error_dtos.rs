@@ -1,12 +1,76 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use serde_valid::Validate;
+use std::collections::BTreeMap;
 use uuid::Uuid;
 
+use super::issue_dtos::IssueDTO;
 use super::tag_dtos::{
     CreateTagClientNoIdDTO, CreateTagRequestDTO, ShortTagDTO, ShortTagNoIdDTO, TagDTO,
 };
 
+// How severe an error is, from a crash down to diagnostics
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum ErrorLevel {
+    #[serde(alias = "critical")]
+    Fatal,
+    #[default]
+    Error,
+    #[serde(alias = "warn")]
+    Warning,
+    #[serde(alias = "log")]
+    Info,
+    Debug,
+}
+
+impl ErrorLevel {
+    // Most severe first
+    pub const ALL: [ErrorLevel; 5] = [
+        ErrorLevel::Fatal,
+        ErrorLevel::Error,
+        ErrorLevel::Warning,
+        ErrorLevel::Info,
+        ErrorLevel::Debug,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorLevel::Fatal => "fatal",
+            ErrorLevel::Error => "error",
+            ErrorLevel::Warning => "warning",
+            ErrorLevel::Info => "info",
+            ErrorLevel::Debug => "debug",
+        }
+    }
+
+    // Also accepts the names some SDKs use instead, case-insensitively
+    pub fn parse(level: &str) -> Option<Self> {
+        match level.to_lowercase().as_str() {
+            "fatal" | "critical" => Some(ErrorLevel::Fatal),
+            "error" => Some(ErrorLevel::Error),
+            "warning" | "warn" => Some(ErrorLevel::Warning),
+            "info" | "log" => Some(ErrorLevel::Info),
+            "debug" => Some(ErrorLevel::Debug),
+            _ => None,
+        }
+    }
+
+    /// This level and every more severe one, e.g. `Warning` gives fatal, error and warning.
+    pub fn and_above(self) -> Vec<ErrorLevel> {
+        ErrorLevel::ALL
+            .into_iter()
+            .take_while(|level| *level != self)
+            .chain([self])
+            .collect()
+    }
+
+    pub fn is_at_least(self, minimum: ErrorLevel) -> bool {
+        minimum.and_above().contains(&self)
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Validate)]
 #[serde(rename_all = "camelCase")]
 pub struct ShortErrorDTO {
@@ -44,12 +108,23 @@ pub struct GetAggregatedLineErrorDTO {
     pub error_count: i32,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAggregatedLevelErrorDTO {
+    pub level: ErrorLevel,
+    pub aggregated_tags: Vec<ShortTagNoIdDTO>,
+    pub user_affected_count: i32,
+    pub error_count: i32,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum AggregatedResult {
+    ByIssue(Vec<IssueDTO>),
     ByTags(Vec<TagAggregatedErrorDTO>),
     ByLine(Vec<GetAggregatedLineErrorDTO>),
     ByMessage(Vec<GetAggregatedMessageErrorDTO>),
+    ByLevel(Vec<GetAggregatedLevelErrorDTO>),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -67,6 +142,7 @@ pub struct CreateErrorDTO {
     pub message: String,
     pub resolved: bool,
     pub namespace_id: Uuid,
+    pub issue_id: Option<Uuid>,
     pub stack_trace: String,
     pub user_affected: String,
 }
@@ -89,12 +165,84 @@ pub struct ErrorDTO {
     pub line: i32,
     pub stack_trace: String,
     pub namespace_id: Uuid,
+    pub issue_id: Option<Uuid>,
     pub resolved: bool,
     pub tags: Option<Vec<ShortTagDTO>>,
+    pub frames: Vec<StackFrameDTO>,
+    pub breadcrumbs: Vec<BreadcrumbDTO>,
+    pub release: Option<String>,
+    pub environment: Option<String>,
+    pub event_id: Option<String>,
+    pub redacted_fields: Vec<String>,
+    pub contexts: ErrorContextsDTO,
+    pub level: ErrorLevel,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct BreadcrumbDTO {
+    pub timestamp: DateTime<Utc>,
+    pub category: Option<String>,
+    pub level: Option<String>,
+    pub message: Option<String>,
+    pub data: Option<Map<String, Value>>,
+}
+
+// Where an error happened beyond its stack trace, every part is optional
+#[derive(Debug, Serialize, Deserialize, Clone, Default, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorContextsDTO {
+    pub request: Option<RequestContextDTO>,
+    pub runtime: Option<RuntimeContextDTO>,
+    pub os: Option<OsContextDTO>,
+    pub device: Option<DeviceContextDTO>,
+    pub extra: Option<Map<String, Value>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestContextDTO {
+    pub method: Option<String>,
+    pub url: Option<String>,
+    // Credentials such as Authorization or Cookie are redacted before storing
+    pub headers: Option<BTreeMap<String, String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeContextDTO {
+    pub name: Option<String>,
+    pub version: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct OsContextDTO {
+    pub name: Option<String>,
+    pub version: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceContextDTO {
+    pub model: Option<String>,
+    pub family: Option<String>,
+    pub arch: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct StackFrameDTO {
+    pub file: String,
+    pub line: i32,
+    pub column: Option<i32>,
+    pub function: String,
+    pub module: String,
+    pub in_app: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Validate)]
 #[serde(rename_all = "camelCase")]
 pub struct AggregateIndividualErrorDTO {
@@ -107,6 +255,7 @@ pub struct AggregateIndividualErrorDTO {
     pub namespace_id: Uuid,
     pub resolved: bool,
     pub tags: Option<Vec<ShortTagNoIdDTO>>,
+    pub level: ErrorLevel,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -133,6 +282,54 @@ pub struct CreateErrorRequest {
     pub stack_trace: String,
     pub message: String,
     pub tags: Option<Vec<CreateTagClientNoIdDTO>>,
+    // Names the stack trace format (python, java, javascript, go, rust), detected when absent
+    pub platform: Option<String>,
+    // Frames already parsed by the SDK, most recent call first. The stack trace text is
+    // kept for display but not parsed again when these are present
+    pub frames: Option<Vec<StackFrameDTO>>,
+    // What happened before the error, oldest first. Trimmed server-side to the most
+    // recent entries
+    pub breadcrumbs: Option<Vec<BreadcrumbDTO>>,
+    // Version of the build that raised the error, e.g. a semver string or commit hash
+    pub release: Option<String>,
+    pub environment: Option<String>,
+    // Generated by the SDK, at most 64 characters. Resending an error with an event ID
+    // already stored in the namespace returns the original instead of a new row
+    pub event_id: Option<String>,
+    // HTTP request, runtime, OS, device and free-form extra data
+    pub contexts: Option<ErrorContextsDTO>,
+    // Defaults to error
+    pub level: Option<ErrorLevel>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum BatchItemStatus {
+    Accepted,
+    Rejected,
+    Duplicate,
+    // Valid but not stored because of the namespace quota or sample rate
+    Dropped,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchItemResultDTO {
+    // Position of the item in the submitted array
+    pub index: usize,
+    pub status: BatchItemStatus,
+    pub id: Option<Uuid>,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchCreateErrorResultDTO {
+    pub accepted: usize,
+    pub rejected: usize,
+    pub duplicate: usize,
+    pub dropped: usize,
+    pub results: Vec<BatchItemResultDTO>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Validate)]
@@ -148,3 +345,12 @@ pub struct AggregateErrorDTO {
     pub count: i64,
     pub time: DateTime<Utc>,
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ReleaseErrorCountDTO {
+    pub release: String,
+    pub count: i64,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+}
@@ -3,12 +3,19 @@ use serde::{Deserialize, Serialize};
 use serde_valid::Validate;
 use uuid::Uuid;
 
+use super::error_dtos::ErrorLevel;
+
 #[derive(Debug, Serialize, Deserialize, Clone, Validate)]
 #[serde(rename_all = "camelCase")]
 pub struct ErrorQueryParams {
     pub offset: u64,
     pub limit: u64,
     pub group_by: Option<String>,
+    // Comma separated `path:value` pairs matched against the error contexts, e.g.
+    // `request.method:POST,os.name:Linux`
+    pub context: Option<String>,
+    // Only count errors of this level
+    pub level: Option<ErrorLevel>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Validate)]
@@ -30,6 +37,7 @@ pub struct ErrorMetadataQueryParams {
     pub limit: u64,
     pub group_by: String,
     pub group_key: String,
+    pub level: Option<ErrorLevel>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Validate)]
@@ -37,6 +45,7 @@ pub struct ErrorMetadataQueryParams {
 pub struct ErrorPieChartQueryParams {
     pub group_by: String,
     pub group_key: String,
+    pub level: Option<ErrorLevel>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Validate)]
@@ -52,9 +61,32 @@ pub struct TimeParams {
     pub start_time: NaiveDate,
     pub time_interval_minutes: i64,
     pub timezone: String,
+    pub level: Option<ErrorLevel>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Validate)]
 pub struct IdRequestDTO {
     pub id: Uuid,
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IngestionMetricsDTO {
+    pub queue_depth: usize,
+    pub queue_capacity: usize,
+    pub workers: usize,
+    pub enqueued: u64,
+    pub processed: u64,
+    pub failed: u64,
+    pub dropped: u64,
+}
+
+/// Deserializes a field of an update request where a missing key keeps the stored
+/// value and an explicit `null` clears it. Use with `#[serde(default)]`.
+pub fn deserialize_nullable<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: serde::Deserializer<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
@@ -0,0 +1,79 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_valid::Validate;
+use uuid::Uuid;
+
+use super::extra_dtos::deserialize_nullable;
+
+// What part of an incoming error a filter looks at
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum InboundFilterType {
+    // Regex searched in the message
+    Message,
+    // Glob matched against the path of the top frame
+    Path,
+    // Glob matched against the value of the tag named by `tag_key`
+    Tag,
+    // Glob matched against user_affected
+    UserAffected,
+}
+
+impl InboundFilterType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InboundFilterType::Message => "message",
+            InboundFilterType::Path => "path",
+            InboundFilterType::Tag => "tag",
+            InboundFilterType::UserAffected => "userAffected",
+        }
+    }
+
+    pub fn parse(filter_type: &str) -> Option<Self> {
+        match filter_type {
+            "message" => Some(InboundFilterType::Message),
+            "path" => Some(InboundFilterType::Path),
+            "tag" => Some(InboundFilterType::Tag),
+            "userAffected" => Some(InboundFilterType::UserAffected),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct InboundFilterDTO {
+    pub id: Uuid,
+    pub namespace_id: Uuid,
+    pub filter_type: InboundFilterType,
+    pub tag_key: Option<String>,
+    pub pattern: String,
+    pub active: bool,
+    // Events this filter has dropped since it was created
+    pub dropped_count: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateInboundFilterDTO {
+    pub filter_type: InboundFilterType,
+    // Required for tag filters
+    pub tag_key: Option<String>,
+    pub pattern: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateInboundFilterDTO {
+    // Left out keeps the current key, null clears it
+    #[serde(
+        default,
+        deserialize_with = "deserialize_nullable",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub tag_key: Option<Option<String>>,
+    pub pattern: Option<String>,
+    pub active: Option<bool>,
+}
@@ -0,0 +1,33 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_valid::Validate;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct IssueDTO {
+    pub id: Uuid,
+    pub namespace_id: Uuid,
+    pub fingerprint: String,
+    pub error_type: String,
+    pub message: String,
+    pub path: String,
+    pub line: i32,
+    pub resolved: bool,
+    pub resolved_in_release: Option<String>,
+    // Set when the issue reappeared in a release newer than the one it was resolved in
+    pub regression: bool,
+    pub occurrences: i64,
+    pub users_affected: i32,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateIssueDTO {
+    pub id: Uuid,
+    pub resolved: Option<bool>,
+    // Release that shipped the fix, only used when resolving
+    pub resolved_in_release: Option<String>,
+}
@@ -1,10 +1,15 @@
+pub mod artifact_dtos;
+pub mod attachment_dtos;
 pub mod auth_dtos;
 pub mod bug_report_dtos;
 pub mod error_dtos;
 pub mod extra_dtos;
 pub mod feature_request_dtos;
+pub mod inbound_filter_dtos;
+pub mod issue_dtos;
 pub mod namespace_alert_dtos;
 pub mod namespace_dtos;
 pub mod notification_dtos;
+pub mod sentry_dtos;
 pub mod tag_dtos;
 pub mod user_dtos;
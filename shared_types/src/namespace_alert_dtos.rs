@@ -4,12 +4,35 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_valid::Validate;
 
+use super::error_dtos::ErrorLevel;
+use super::extra_dtos::deserialize_nullable;
+
+// What an alert fires on. Conditions nest through `all` (AND) and `any` (OR) groups,
+// windows and ages are in milliseconds like the alert's threshold fields
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum AlertConditionDTO {
+    // More than `threshold` matching errors within the window
+    #[serde(rename_all = "camelCase")]
+    CountInWindow { threshold: i32, time_window: i64 },
+    // More than `threshold` matching errors per minute, averaged over the window
+    #[serde(rename_all = "camelCase")]
+    Rate { threshold: i32, time_window: i64 },
+    // A matching error left unresolved for longer than `threshold`
+    #[serde(rename_all = "camelCase")]
+    UnresolvedAge { threshold: i64 },
+    All { conditions: Vec<AlertConditionDTO> },
+    Any { conditions: Vec<AlertConditionDTO> },
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Validate)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateNamespaceAlertRequestDTO {
     pub namespace_id: Uuid,
     pub alert_method: String,
     pub discord_channel_id: Option<String>,
+    // Slack incoming webhook, required by the `slack` method
+    pub slack_webhook_url: Option<String>,
     pub path: Option<String>,
     pub line: Option<i32>,
     pub message: Option<String>,
@@ -19,6 +42,18 @@ pub struct CreateNamespaceAlertRequestDTO {
     pub unresolved_time_threshold: Option<i64>,
     pub rate_threshold: Option<i32>,
     pub rate_time_window: Option<i64>,
+    pub min_level: Option<ErrorLevel>,
+    // Replaces the threshold fields above when set
+    pub conditions: Option<AlertConditionDTO>,
+    // Milliseconds after firing before the alert re-arms on its own
+    pub cooldown: Option<i64>,
+    // The alert re-arms once this stops firing, e.g. a rate dropping back under its
+    // threshold. Combined with a cooldown both must pass
+    pub recovery_condition: Option<AlertConditionDTO>,
+    // Send a "recovered" message on the alert's channel when the recovery condition re-arms it
+    pub notify_recovery: Option<bool>,
+    // Required by the `webhook` method, the signing secret is generated server side
+    pub webhook_url: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Validate)]
@@ -28,6 +63,8 @@ pub struct NamespaceAlertDTO<'a> {
     pub namespace_id: Uuid,
     pub alert_method: &'a str,
     pub discord_channel_id: Option<&'a str>,
+    // The token at the end is redacted
+    pub slack_webhook_url: Option<&'a str>,
     pub triggered: bool,
     pub path: Option<&'a str>,
     pub line: Option<i32>,
@@ -38,6 +75,13 @@ pub struct NamespaceAlertDTO<'a> {
     pub unresolved_time_threshold: Option<i64>,
     pub rate_threshold: Option<i32>,
     pub rate_time_window: Option<i64>,
+    pub min_level: Option<ErrorLevel>,
+    pub conditions: Option<AlertConditionDTO>,
+    pub cooldown: Option<i64>,
+    pub recovery_condition: Option<AlertConditionDTO>,
+    pub notify_recovery: bool,
+    pub triggered_at: Option<DateTime<Utc>>,
+    pub webhook_url: Option<&'a str>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -49,6 +93,8 @@ pub struct ShortNamespaceAlertDTO {
     pub namespace_id: Uuid,
     pub alert_method: String,
     pub discord_channel_id: Option<String>,
+    // The token at the end is redacted
+    pub slack_webhook_url: Option<String>,
     pub triggered: bool,
     pub path: Option<String>,
     pub line: Option<i32>,
@@ -59,6 +105,29 @@ pub struct ShortNamespaceAlertDTO {
     pub unresolved_time_threshold: Option<i64>,
     pub rate_threshold: Option<i32>,
     pub rate_time_window: Option<i64>,
+    pub min_level: Option<ErrorLevel>,
+    pub conditions: Option<AlertConditionDTO>,
+    pub cooldown: Option<i64>,
+    pub recovery_condition: Option<AlertConditionDTO>,
+    pub notify_recovery: bool,
+    pub triggered_at: Option<DateTime<Utc>>,
+    pub webhook_url: Option<String>,
+}
+
+// The webhook signing secret is only ever shown here and on rotation
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatedNamespaceAlertDTO {
+    pub id: Uuid,
+    // Verifies the signature header on webhook deliveries
+    pub webhook_secret: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdatedNamespaceAlertDTO {
+    // Only set when the update generated a new signing secret
+    pub webhook_secret: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Validate)]
@@ -67,6 +136,7 @@ pub struct UpdateNamespaceAlertRequestDTO {
     pub namespace_id: Uuid,
     pub alert_method: Option<String>,
     pub discord_channel_id: Option<String>,
+    pub slack_webhook_url: Option<String>,
     pub triggered: bool,
     pub path: Option<String>,
     pub line: Option<i32>,
@@ -77,6 +147,25 @@ pub struct UpdateNamespaceAlertRequestDTO {
     pub unresolved_time_threshold: Option<i64>,
     pub rate_threshold: Option<i32>,
     pub rate_time_window: Option<i64>,
+    pub min_level: Option<ErrorLevel>,
+    pub conditions: Option<AlertConditionDTO>,
+    // Left out keeps the current value, null removes it
+    #[serde(
+        default,
+        deserialize_with = "deserialize_nullable",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub cooldown: Option<Option<i64>>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_nullable",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub recovery_condition: Option<Option<AlertConditionDTO>>,
+    pub notify_recovery: Option<bool>,
+    pub webhook_url: Option<String>,
+    // Replaces the webhook signing secret when true
+    pub rotate_webhook_secret: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Validate)]
@@ -86,3 +175,81 @@ pub struct NamespaceAlertSubscriptionRequestDTO {
     pub namespace_id: Uuid,
     pub namespace_alert_id: Uuid,
 }
+
+// Bumped whenever a webhook payload field is removed or changes meaning
+pub const ALERT_WEBHOOK_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum AlertWebhookEvent {
+    Fired,
+    Recovered,
+    // Sent from the test-delivery endpoint
+    Test,
+}
+
+// Body POSTed to an alert's webhook. The `X-HiGuard-Signature` header holds
+// `sha256=<hex HMAC-SHA256 of "{X-HiGuard-Timestamp}.{body}">` keyed with the alert's secret
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertWebhookPayloadDTO {
+    pub version: u32,
+    pub event: AlertWebhookEvent,
+    pub sent_at: DateTime<Utc>,
+    pub alert: AlertWebhookAlertDTO,
+    pub namespace: AlertWebhookNamespaceDTO,
+    // The latest error in the alert's scope
+    pub error: Option<AlertWebhookErrorDTO>,
+    pub counts: AlertWebhookCountsDTO,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertWebhookAlertDTO {
+    pub id: Uuid,
+    // What tripped the alert, only set when it fired
+    pub details: Option<String>,
+    pub path: Option<String>,
+    pub line: Option<i32>,
+    pub message: Option<String>,
+    pub min_level: Option<ErrorLevel>,
+    pub triggered_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertWebhookNamespaceDTO {
+    pub id: Uuid,
+    pub service_name: String,
+    pub environment_type: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertWebhookErrorDTO {
+    pub id: Uuid,
+    pub issue_id: Option<Uuid>,
+    pub message: String,
+    pub path: String,
+    pub line: i32,
+    pub level: ErrorLevel,
+    pub resolved: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+// Errors in the alert's scope
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertWebhookCountsDTO {
+    pub last_hour: u64,
+    pub unresolved: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookTestResultDTO {
+    pub status: u16,
+    pub attempts: u32,
+    // The first 4 KiB of the target's response body
+    pub body: String,
+}
@@ -21,6 +21,12 @@ pub struct GetNamespaceResponseDTO {
     pub client_secret: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub quota_per_minute: Option<i32>,
+    pub quota_per_day: Option<i32>,
+    pub sample_rate: f64,
+    pub usage: NamespaceUsageDTO,
+    pub scrub_patterns: Vec<String>,
+    pub scrub_tag_keys: Vec<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Validate)]
@@ -32,6 +38,25 @@ pub struct UpdateNamespaceDTO {
     pub environment_type: Option<String>,
     pub client_id: Option<Uuid>,
     pub client_secret: Option<bool>,
+    // Setting a quota to 0 removes the limit
+    pub quota_per_minute: Option<i32>,
+    pub quota_per_day: Option<i32>,
+    pub sample_rate: Option<f64>,
+    // Replace the namespace's custom scrubbing regexes and denylisted tag keys
+    pub scrub_patterns: Option<Vec<String>>,
+    pub scrub_tag_keys: Option<Vec<String>>,
+}
+
+// Ingestion counters for the current minute and UTC day
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct NamespaceUsageDTO {
+    pub events_this_minute: u64,
+    pub events_today: u64,
+    // Events refused because a quota was reached
+    pub dropped_today: u64,
+    // Events discarded by the sample rate
+    pub sampled_out_today: u64,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Validate)]
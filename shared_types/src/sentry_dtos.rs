@@ -0,0 +1,133 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+// Subset of the Sentry event protocol that maps onto our error model. Field names follow
+// the protocol (snake_case), unknown fields are ignored.
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SentryEvent {
+    pub event_id: Option<String>,
+    pub message: Option<SentryMessage>,
+    pub logentry: Option<SentryLogEntry>,
+    pub exception: Option<SentryValues<SentryException>>,
+    pub breadcrumbs: Option<SentryValues<SentryBreadcrumb>>,
+    pub tags: Option<SentryTags>,
+    pub user: Option<SentryUser>,
+    pub release: Option<String>,
+    pub environment: Option<String>,
+    pub platform: Option<String>,
+    pub level: Option<String>,
+    pub request: Option<SentryRequest>,
+    // Keyed by context type (os, runtime, device, ...), each an object of its own
+    pub contexts: Option<BTreeMap<String, Value>>,
+    pub extra: Option<serde_json::Map<String, Value>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum SentryMessage {
+    Plain(String),
+    Formatted(SentryLogEntry),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SentryLogEntry {
+    pub formatted: Option<String>,
+    pub message: Option<String>,
+}
+
+// SDKs send either `{"values": [...]}` or a bare list
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum SentryValues<T> {
+    Wrapped { values: Vec<T> },
+    List(Vec<T>),
+}
+
+impl<T> SentryValues<T> {
+    pub fn into_vec(self) -> Vec<T> {
+        match self {
+            SentryValues::Wrapped { values } => values,
+            SentryValues::List(values) => values,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SentryException {
+    #[serde(rename = "type")]
+    pub exception_type: Option<String>,
+    pub value: Option<String>,
+    pub module: Option<String>,
+    pub stacktrace: Option<SentryStacktrace>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SentryStacktrace {
+    // Oldest call first, as the protocol specifies
+    #[serde(default)]
+    pub frames: Vec<SentryFrame>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SentryFrame {
+    pub filename: Option<String>,
+    pub abs_path: Option<String>,
+    pub function: Option<String>,
+    pub module: Option<String>,
+    pub lineno: Option<i32>,
+    pub colno: Option<i32>,
+    pub in_app: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SentryBreadcrumb {
+    // Seconds since the epoch as a float, or an RFC 3339 string
+    pub timestamp: Option<Value>,
+    pub category: Option<String>,
+    pub level: Option<String>,
+    pub message: Option<String>,
+    pub data: Option<serde_json::Map<String, Value>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum SentryTags {
+    Map(BTreeMap<String, Value>),
+    List(Vec<(String, Value)>),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SentryRequest {
+    pub url: Option<String>,
+    pub method: Option<String>,
+    // Same shapes as tags, an object or a list of pairs
+    pub headers: Option<SentryTags>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SentryUser {
+    pub id: Option<Value>,
+    pub email: Option<String>,
+    pub username: Option<String>,
+    pub ip_address: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SentryEnvelopeHeader {
+    pub event_id: Option<String>,
+    pub dsn: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SentryEnvelopeItemHeader {
+    #[serde(rename = "type")]
+    pub item_type: String,
+    pub length: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SentryEventIdDTO {
+    pub id: String,
+}